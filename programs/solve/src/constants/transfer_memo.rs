@@ -3,3 +3,9 @@ pub const TRANSFER_MEMO_COLLECT_FEES: &str = "Solve CollectFees";
 pub const TRANSFER_MEMO_COLLECT_REWARD: &str = "Solve CollectReward";
 pub const TRANSFER_MEMO_DECREASE_LIQUIDITY: &str = "Solve Withdraw";
 pub const TRANSFER_MEMO_SWAP: &str = "Solve Trade";
+pub const TRANSFER_MEMO_FLASH_BORROW: &str = "Solve FlashBorrow";
+pub const TRANSFER_MEMO_CLOSE_LIMIT_ORDER: &str = "Solve CloseLimitOrder";
+pub const TRANSFER_MEMO_REPOSITION_POSITION: &str = "Solve RepositionPosition";
+pub const TRANSFER_MEMO_ZAP: &str = "Solve Zap";
+pub const TRANSFER_MEMO_DISTRIBUTE_PROTOCOL_FEES: &str = "Solve DistributeProtocolFees";
+pub const TRANSFER_MEMO_WITHDRAW_UNALLOCATED_REWARD: &str = "Solve WithdrawUnallocatedReward";
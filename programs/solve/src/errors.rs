@@ -166,6 +166,146 @@ pub enum ErrorCode {
 
     #[msg("Rent calculation error")]
     RentCalculationError, // 0x17b1 (6065)
+
+    #[msg("Oracle observations are not initialized")]
+    ObservationsNotInitialized, // 0x17b2 (6066)
+    #[msg("Invalid observation cardinality")]
+    InvalidObservationCardinality, // 0x17b3 (6067)
+    #[msg("Requested timestamp is older than the oldest observation")]
+    ObservationTooOld, // 0x17b4 (6068)
+    #[msg("Invalid observation window")]
+    InvalidObservationWindow, // 0x17b5 (6069)
+
+    #[msg("Invalid number of hops in the route")]
+    InvalidRouteLength, // 0x17b6 (6070)
+    #[msg("Duplicate pool in the route")]
+    DuplicateRoutePool, // 0x17b7 (6071)
+
+    #[msg("Flash loan instructions cannot be invoked via CPI")]
+    FlashLoanCpiNotAllowed, // 0x17b8 (6072)
+    #[msg("No matching flash repay instruction found")]
+    FlashRepayNotFound, // 0x17b9 (6073)
+    #[msg("No matching flash borrow instruction found")]
+    FlashBorrowNotFound, // 0x17ba (6074)
+    #[msg("Flash borrow and flash repay amounts do not match")]
+    FlashLoanAmountMismatch, // 0x17bb (6075)
+
+    #[msg("Invalid flash swap callback program")]
+    InvalidFlashSwapCallbackProgram, // 0x17bc (6076)
+    #[msg("Input amount of the flash swap has not been paid")]
+    FlashSwapNotPaid, // 0x17bd (6077)
+
+    #[msg("Invalid tick index for a limit order")]
+    InvalidLimitOrderTickIndex, // 0x17be (6078)
+    #[msg("Limit order range must be on the far side of the current price")]
+    InvalidLimitOrderRange, // 0x17bf (6079)
+    #[msg("Limit order capacity exceeded")]
+    LimitOrderCapacityExceeded, // 0x17c0 (6080)
+    #[msg("Limit order not found")]
+    LimitOrderNotFound, // 0x17c1 (6081)
+    #[msg("Limit orders cannot be filled with the provided tick arrays")]
+    LimitOrderTickArrayNotFound, // 0x17c2 (6082)
+
+    #[msg("Swap amount exceeds the tokens withdrawn from the position")]
+    RepositionSwapAmountExceeded, // 0x17c3 (6083)
+    #[msg("Liquidity is below the minimum")]
+    LiquidityBelowMinimum, // 0x17c4 (6084)
+
+    #[msg("Invalid unlock timestamp")]
+    InvalidUnlockTimestamp, // 0x17c5 (6085)
+    #[msg("Position cannot be unlocked yet")]
+    PositionNotUnlockable, // 0x17c6 (6086)
+    #[msg("Liquidity amount exceeds the vested liquidity of the locked position")]
+    VestedLiquidityExceeded, // 0x17c7 (6087)
+
+    #[msg("Positions must be distinct with the same pool, range and owner")]
+    PositionsNotMergeable, // 0x17c8 (6088)
+
+    #[msg("Referral fee rate exceeds the maximum")]
+    ReferralFeeRateMaxExceeded, // 0x17c9 (6089)
+    #[msg("Referral token account is missing or does not hold the input token")]
+    InvalidReferralTokenAccount, // 0x17ca (6090)
+
+    #[msg("Protocol fee recipients must be distinct with shares adding up to 10000")]
+    InvalidProtocolFeeRecipients, // 0x17cb (6091)
+    #[msg("Protocol fee recipients are not set")]
+    ProtocolFeeRecipientsNotSet, // 0x17cc (6092)
+    #[msg("Protocol fee destination does not match the recipient")]
+    InvalidProtocolFeeDestination, // 0x17cd (6093)
+
+    #[msg("Parameter changes must be queued while the admin timelock is enabled")]
+    AdminTimelockEnabled, // 0x17ce (6094)
+    #[msg("Queued change cannot be executed before its execution timestamp")]
+    QueuedChangeNotExecutable, // 0x17cf (6095)
+    #[msg("Queued change target does not match the parameter")]
+    InvalidQueuedChangeTarget, // 0x17d0 (6096)
+
+    #[msg("Swaps are paused")]
+    SwapPaused, // 0x17d1 (6097)
+    #[msg("Liquidity increases are paused")]
+    IncreaseLiquidityPaused, // 0x17d2 (6098)
+    #[msg("Operations other than withdrawals are paused")]
+    OperationPaused, // 0x17d3 (6099)
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags, // 0x17d4 (6100)
+
+    #[msg("TickArray has initialized ticks")]
+    TickArrayNotEmpty, // 0x17d5 (6101)
+    #[msg("Tick array rent recipient is not set")]
+    TickArrayRentRecipientNotSet, // 0x17d6 (6102)
+
+    #[msg("Instruction does not support positions with a reward extension")]
+    RewardExtensionNotSupported, // 0x17d7 (6103)
+    #[msg("Reward extension accounts are required")]
+    RewardExtensionRequired, // 0x17d8 (6104)
+    #[msg("Invalid reward extension account")]
+    InvalidRewardExtensionAccount, // 0x17d9 (6105)
+    #[msg("Reward extension is already enabled")]
+    RewardExtensionAlreadyEnabled, // 0x17da (6106)
+    #[msg("Reward extension cannot be enabled while the pool has limit orders")]
+    RewardExtensionLimitOrdersExist, // 0x17db (6107)
+
+    #[msg("Reward schedule must end after it starts and after the current timestamp")]
+    InvalidRewardSchedule, // 0x17dc (6108)
+
+    #[msg("Emissions of the reward are not tracked since the pool was initialized")]
+    RewardNotTracked, // 0x17dd (6109)
+
+    #[msg("Invalid lock boost config")]
+    InvalidLockBoostConfig, // 0x17de (6110)
+    #[msg("Reward can only be boosted before it has been emitted")]
+    RewardBoostNotConfigurable, // 0x17df (6111)
+
+    #[msg("Solve tail is not allocated")]
+    SolveTailNotAllocated, // 0x17e0 (6112)
+
+    #[msg("Parameter changes cannot be queued while the admin timelock is disabled")]
+    AdminTimelockDisabled, // 0x17e1 (6113)
+    #[msg("Queued change was not executed within the execution window")]
+    QueuedChangeExpired, // 0x17e2 (6114)
+
+    #[msg("Protocol fees must be distributed while protocol fee recipients are set")]
+    ProtocolFeeRecipientsSet, // 0x17e3 (6115)
+
+    #[msg("Oracle account cannot grow by more than 10KiB per call")]
+    ObservationCardinalityIncreaseTooLarge, // 0x17e4 (6116)
+
+    #[msg("Boost of the position must be removed before it is unlocked")]
+    PositionBoosted, // 0x17e5 (6117)
+
+    #[msg("Solve is locked while a flash swap callback is in progress")]
+    SolveLocked, // 0x17e6 (6118)
+
+    #[msg("Admin timelock delay exceeds the maximum")]
+    AdminTimelockDelayMaxExceeded, // 0x17e7 (6119)
+
+    #[msg("Swap amount exceeds the deposited amount")]
+    ZapSwapAmountExceeded, // 0x17e8 (6120)
+
+    #[msg("Invalid position keeper price bounds")]
+    InvalidPositionKeeperPriceBounds, // 0x17e9 (6121)
+    #[msg("Price is out of the position keeper price bounds")]
+    PriceOutOfPositionKeeperBounds, // 0x17ea (6122)
 }
 
 impl From<TryFromIntError> for ErrorCode {
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::ParameterChangeLabel;
+
 #[event]
 pub struct PoolInitialized {
     pub solve: Pubkey,
@@ -52,4 +54,165 @@ pub struct Traded {
     pub output_transfer_fee: u64,
     pub lp_fee: u64,
     pub protocol_fee: u64,
+    // input token skimmed to the referral token account (transfer fee included)
+    pub referral_fee: u64,
+}
+
+#[event]
+pub struct ProtocolFeesDistributed {
+    pub solve: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct FlashLoanRepaid {
+    pub solve: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+}
+
+#[event]
+pub struct LimitOrderOpened {
+    pub solve: Pubkey,
+    pub limit_order: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower_index: i32,
+    pub a_to_b: bool,
+    pub liquidity: u128,
+    pub token_amount: u64,
+    pub token_transfer_fee: u64,
+}
+
+#[event]
+pub struct LimitOrderClosed {
+    pub solve: Pubkey,
+    pub limit_order: Pubkey,
+    pub filled: bool,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_a_transfer_fee: u64,
+    pub token_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct PositionRepositioned {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub old_tick_lower_index: i32,
+    pub old_tick_upper_index: i32,
+    pub new_tick_lower_index: i32,
+    pub new_tick_upper_index: i32,
+    pub old_liquidity: u128,
+    pub new_liquidity: u128,
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+    pub token_a_refund_amount: u64,
+    pub token_b_refund_amount: u64,
+    pub token_a_transfer_fee: u64,
+    pub token_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct PositionZappedIn {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub a_to_b: bool,
+    pub input_amount: u64,
+    pub input_transfer_fee: u64,
+    pub liquidity: u128,
+    pub token_a_refund_amount: u64,
+    pub token_b_refund_amount: u64,
+}
+
+#[event]
+pub struct PositionZappedOut {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub a_to_b: bool,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub token_a_transfer_fee: u64,
+    pub token_b_transfer_fee: u64,
+}
+
+#[event]
+pub struct FeesCompounded {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub authority: Pubkey,
+    pub liquidity: u128,
+    // fees owed before compounding
+    pub fee_a_amount: u64,
+    pub fee_b_amount: u64,
+    // fees owed after compounding
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+}
+
+#[event]
+pub struct PositionSplit {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub new_position: Pubkey,
+    pub liquidity: u128,
+    // fees owed moved to the new position
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+}
+
+#[event]
+pub struct PositionsMerged {
+    pub solve: Pubkey,
+    pub position: Pubkey,
+    pub closed_position: Pubkey,
+    // liquidity moved from the closed position
+    pub liquidity: u128,
+    // liquidity of the position after merging
+    pub merged_liquidity: u128,
+}
+
+#[event]
+pub struct ParameterChangeQueued {
+    pub solves_config: Pubkey,
+    pub target: Pubkey,
+    pub parameter_change: ParameterChangeLabel,
+    pub value: u64,
+    pub execution_timestamp: u64,
+}
+
+#[event]
+pub struct ParameterChangeExecuted {
+    pub solves_config: Pubkey,
+    pub target: Pubkey,
+    pub parameter_change: ParameterChangeLabel,
+    pub value: u64,
+}
+
+#[event]
+pub struct ParameterChangeCancelled {
+    pub solves_config: Pubkey,
+    pub target: Pubkey,
+    pub parameter_change: ParameterChangeLabel,
+    pub value: u64,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub solves_config: Pubkey,
+    // None for the config level pause flags
+    pub solve: Option<Pubkey>,
+    pub pause_flags: u8,
+}
+
+#[event]
+pub struct UnallocatedRewardWithdrawn {
+    pub solve: Pubkey,
+    pub reward_index: u8,
+    pub amount: u64,
 }
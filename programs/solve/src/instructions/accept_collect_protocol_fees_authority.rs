@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptCollectProtocolFeesAuthority<'info> {
+    #[account(mut)]
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_collect_protocol_fees_authority)]
+    pub pending_collect_protocol_fees_authority: Signer<'info>,
+}
+
+/// Accept the nominated collect protocol fees authority. Only the pending collect protocol fees authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptCollectProtocolFeesAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config
+        .update_collect_protocol_fees_authority(
+            ctx.accounts.pending_collect_protocol_fees_authority.key(),
+        );
+    ctx.accounts
+        .solves_config_extension
+        .update_pending_collect_protocol_fees_authority(Pubkey::default());
+    Ok(())
+}
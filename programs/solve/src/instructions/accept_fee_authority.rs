@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptFeeAuthority<'info> {
+    #[account(mut)]
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_fee_authority)]
+    pub pending_fee_authority: Signer<'info>,
+}
+
+/// Accept the nominated fee authority. Only the pending fee authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptFeeAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config
+        .update_fee_authority(ctx.accounts.pending_fee_authority.key());
+    ctx.accounts
+        .solves_config_extension
+        .update_pending_fee_authority(Pubkey::default());
+    Ok(())
+}
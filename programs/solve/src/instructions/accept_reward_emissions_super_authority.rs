@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptRewardEmissionsSuperAuthority<'info> {
+    #[account(mut)]
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_reward_emissions_super_authority)]
+    pub pending_reward_emissions_super_authority: Signer<'info>,
+}
+
+/// Accept the nominated reward emissions super authority. Only the pending reward emissions super authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptRewardEmissionsSuperAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config
+        .update_reward_emissions_super_authority(
+            ctx.accounts.pending_reward_emissions_super_authority.key(),
+        );
+    ctx.accounts
+        .solves_config_extension
+        .update_pending_reward_emissions_super_authority(Pubkey::default());
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AdaptiveFeeTier, SolvesConfig};
+
+#[derive(Accounts)]
+pub struct AcceptInitializePoolAuthority<'info> {
+    pub solves_config: Account<'info, SolvesConfig>,
+
+    #[account(mut, has_one = solves_config)]
+    pub adaptive_fee_tier: Account<'info, AdaptiveFeeTier>,
+
+    #[account(address = adaptive_fee_tier.pending_initialize_pool_authority)]
+    pub pending_initialize_pool_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptInitializePoolAuthority>) -> Result<()> {
+    ctx.accounts
+        .adaptive_fee_tier
+        .accept_initialize_pool_authority();
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod accept_delegated_fee_authority;
+pub mod accept_initialize_pool_authority;
 pub mod initialize_adaptive_fee_tier;
 pub mod initialize_pool_with_adaptive_fee;
 pub mod set_default_base_fee_rate;
@@ -6,6 +8,8 @@ pub mod set_fee_rate_by_delegated_fee_authority;
 pub mod set_initialize_pool_authority;
 pub mod set_preset_adaptive_fee_constants;
 
+pub use accept_delegated_fee_authority::*;
+pub use accept_initialize_pool_authority::*;
 pub use initialize_adaptive_fee_tier::*;
 pub use initialize_pool_with_adaptive_fee::*;
 pub use set_default_base_fee_rate::*;
@@ -19,6 +19,6 @@ pub struct SetDelegatedFeeAuthority<'info> {
 pub fn handler(ctx: Context<SetDelegatedFeeAuthority>) -> Result<()> {
     ctx.accounts
         .adaptive_fee_tier
-        .update_delegated_fee_authority(ctx.accounts.new_delegated_fee_authority.key());
+        .update_pending_delegated_fee_authority(ctx.accounts.new_delegated_fee_authority.key());
     Ok(())
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::state::{AdaptiveFeeTier, Solve};
+use crate::util::verify_admin_timelock_disabled;
 
 #[derive(Accounts)]
 pub struct SetFeeRateByDelegatedFeeAuthority<'info> {
@@ -17,8 +18,16 @@ pub struct SetFeeRateByDelegatedFeeAuthority<'info> {
 
     #[account(address = adaptive_fee_tier.delegated_fee_authority)]
     pub delegated_fee_authority: Signer<'info>,
+
+    // appended after the accounts of the instruction before the admin timelock was introduced
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
 }
 
+// While the admin timelock is enabled, fee rate changes can only be queued by the fee authority.
 pub fn handler(ctx: Context<SetFeeRateByDelegatedFeeAuthority>, fee_rate: u16) -> Result<()> {
+    verify_admin_timelock_disabled(&ctx.accounts.solves_config_extension)?;
+
     ctx.accounts.solve.update_fee_rate(fee_rate)
 }
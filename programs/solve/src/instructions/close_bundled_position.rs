@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::close_position_reward_extension;
 use crate::{state::*, util::verify_position_bundle_authority};
 
 #[derive(Accounts)]
@@ -35,7 +36,10 @@ pub struct CloseBundledPosition<'info> {
     pub receiver: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<CloseBundledPosition>, bundle_index: u16) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseBundledPosition<'info>>,
+    bundle_index: u16,
+) -> Result<()> {
     let position_bundle = &mut ctx.accounts.position_bundle;
 
     // Allow delegation
@@ -48,6 +52,12 @@ pub fn handler(ctx: Context<CloseBundledPosition>, bundle_index: u16) -> Result<
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
+    close_position_reward_extension(
+        &ctx.accounts.bundled_position.to_account_info(),
+        ctx.remaining_accounts,
+        &ctx.accounts.receiver,
+    )?;
+
     position_bundle.close_bundled_position(bundle_index)?;
 
     // Anchor will close the Position account
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::close_position_reward_extension;
 use crate::state::*;
 use crate::util::{burn_and_close_user_position_token, verify_position_authority};
 
@@ -32,7 +33,7 @@ pub struct ClosePosition<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClosePosition<'info>>) -> Result<()> {
     verify_position_authority(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
@@ -42,6 +43,12 @@ pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
+    close_position_reward_extension(
+        &ctx.accounts.position.to_account_info(),
+        ctx.remaining_accounts,
+        &ctx.accounts.receiver,
+    )?;
+
     burn_and_close_user_position_token(
         &ctx.accounts.position_authority,
         &ctx.accounts.receiver,
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority_interface;
+
+#[derive(Accounts)]
+pub struct ClosePositionKeeper<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint,
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        close = receiver,
+        seeds = [b"position_keeper".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub position_keeper: Account<'info, PositionKeeper>,
+}
+
+pub fn handler(ctx: Context<ClosePositionKeeper>) -> Result<()> {
+    // The current owner of the position can revoke the authorization even if it was given by the previous owner
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    Ok(())
+}
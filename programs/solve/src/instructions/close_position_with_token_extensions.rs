@@ -3,6 +3,7 @@ use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::close_position_reward_extension;
 use crate::state::*;
 use crate::util::{
     burn_and_close_user_position_token_2022, is_locked_position,
@@ -37,7 +38,9 @@ pub struct ClosePositionWithTokenExtensions<'info> {
     pub token_2022_program: Program<'info, Token2022>,
 }
 
-pub fn handler(ctx: Context<ClosePositionWithTokenExtensions>) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClosePositionWithTokenExtensions<'info>>,
+) -> Result<()> {
     verify_position_authority_interface(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
@@ -51,6 +54,12 @@ pub fn handler(ctx: Context<ClosePositionWithTokenExtensions>) -> Result<()> {
         return Err(ErrorCode::ClosePositionNotEmpty.into());
     }
 
+    close_position_reward_extension(
+        &ctx.accounts.position.to_account_info(),
+        ctx.remaining_accounts,
+        &ctx.accounts.receiver,
+    )?;
+
     burn_and_close_user_position_token_2022(
         &ctx.accounts.position_authority,
         &ctx.accounts.receiver,
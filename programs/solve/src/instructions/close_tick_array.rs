@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CloseTickArray<'info> {
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler, FixedTickArray or DynamicTickArray of the solve
+    pub tick_array: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler, TickArrayRewardExtension of the tick array, may be uninitialized
+    pub tick_array_reward_extension: UncheckedAccount<'info>,
+
+    #[account(mut, address = solves_config_extension.tick_array_rent_recipient)]
+    pub tick_array_rent_recipient: Signer<'info>,
+}
+
+/*
+  Closes a tick array without initialized ticks and refunds its rent to the tick array rent recipient.
+
+  The rent is NOT returned to the original funder of the tick array. Neither FixedTickArray nor DynamicTickArray
+  has room to record the funder without changing their account layout, which is shared with the existing clients.
+  The recipient is configured on the SolvesConfigExtension instead and must sign, so that arrays in use cannot be
+  closed by third parties. A closed tick array can be initialized again if needed.
+
+  The TickArrayRewardExtension of the tick array is closed with it. Its values are only meaningful for
+  initialized ticks and are overwritten when a tick is initialized, so it can be initialized again with zeros.
+*/
+pub fn handler(ctx: Context<CloseTickArray>) -> Result<()> {
+    close_empty_tick_array(
+        &ctx.accounts.solve.key(),
+        &ctx.accounts.tick_array,
+        &ctx.accounts.tick_array_reward_extension,
+        &ctx.accounts.tick_array_rent_recipient,
+    )
+}
+
+fn close_empty_tick_array<'info>(
+    solve: &Pubkey,
+    tick_array: &AccountInfo<'info>,
+    tick_array_reward_extension: &AccountInfo<'info>,
+    receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    let start_tick_index = {
+        let tick_array = load_tick_array(tick_array, solve)?;
+        // FixedTickArray: all ticks are checked
+        // DynamicTickArray: the tick bitmap is checked
+        if tick_array.has_initialized_ticks() {
+            return Err(ErrorCode::TickArrayNotEmpty.into());
+        }
+        tick_array.start_tick_index()
+    };
+
+    let (tick_array_reward_extension_address, _) = Pubkey::find_program_address(
+        &[
+            b"tick_array_reward_extension",
+            solve.as_ref(),
+            start_tick_index.to_string().as_bytes(),
+        ],
+        &crate::ID,
+    );
+    if tick_array_reward_extension.key() != tick_array_reward_extension_address {
+        return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+    }
+
+    close_account(tick_array, receiver)?;
+    if tick_array_reward_extension.owner == &crate::ID {
+        close_account(tick_array_reward_extension, receiver)?;
+    }
+
+    Ok(())
+}
+
+fn close_account(account: &AccountInfo, receiver: &AccountInfo) -> Result<()> {
+    let lamports = account.lamports();
+    account.sub_lamports(lamports)?;
+    receiver.add_lamports(lamports)?;
+
+    account.assign(&System::id());
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod close_tick_array_tests {
+    use super::*;
+    use crate::util::serialized_account::SerializedAccount;
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+    const START_TICK_INDEX: i32 = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    const TICK_ARRAY_LAMPORTS: u64 = 1_000_000;
+    const REWARD_EXTENSION_LAMPORTS: u64 = 2_000_000;
+    const RECEIVER_LAMPORTS: u64 = 3_000_000;
+
+    fn solve_data() -> Vec<u8> {
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Solve::LEN];
+        solve.try_serialize(&mut data.as_mut_slice()).unwrap();
+        data
+    }
+
+    fn reward_extension_address(solve: &Pubkey, start_tick_index: i32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"tick_array_reward_extension",
+                solve.as_ref(),
+                start_tick_index.to_string().as_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    fn fixed_tick_array_data(solve: &Account<Solve>, initialized: bool) -> Vec<u8> {
+        let mut data = vec![0u8; FixedTickArray::LEN];
+        data[0..8].copy_from_slice(&FixedTickArray::DISCRIMINATOR);
+        let tick_array: &mut FixedTickArray = bytemuck::from_bytes_mut(&mut data[8..]);
+        tick_array.initialize(solve, START_TICK_INDEX).unwrap();
+        if initialized {
+            tick_array
+                .update_tick(
+                    START_TICK_INDEX,
+                    TICK_SPACING,
+                    &TickUpdate {
+                        initialized: true,
+                        liquidity_gross: 1,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+        data
+    }
+
+    fn dynamic_tick_array_data(solve: &Account<Solve>) -> Vec<u8> {
+        let mut data = vec![0u8; DynamicTickArray::MIN_LEN];
+        data[0..8].copy_from_slice(&DynamicTickArray::DISCRIMINATOR);
+        DynamicTickArrayLoader::load_mut(&mut data[8..])
+            .initialize(solve, START_TICK_INDEX)
+            .unwrap();
+        data
+    }
+
+    fn migrated_tick_array_data(solve: &Account<Solve>, ticks: &[Tick]) -> Vec<u8> {
+        let mut data = vec![0u8; DynamicTickArray::MAX_LEN];
+        data[0..8].copy_from_slice(&DynamicTickArray::DISCRIMINATOR);
+        DynamicTickArrayLoader::load_mut(&mut data[8..])
+            .initialize_with_ticks(solve, START_TICK_INDEX, ticks)
+            .unwrap();
+        data
+    }
+
+    fn reward_extension_data(solve: &Account<Solve>) -> Vec<u8> {
+        let mut data = vec![0u8; TickArrayRewardExtension::LEN];
+        data[0..8].copy_from_slice(&TickArrayRewardExtension::discriminator());
+        let reward_extension: &mut TickArrayRewardExtension =
+            bytemuck::from_bytes_mut(&mut data[8..]);
+        reward_extension
+            .initialize(solve, START_TICK_INDEX)
+            .unwrap();
+        data
+    }
+
+    fn assert_closed(account_info: &AccountInfo) {
+        assert_eq!(account_info.lamports(), 0);
+        assert_eq!(account_info.owner, &System::id());
+        assert_eq!(account_info.data_len(), 0);
+    }
+
+    // Closes the tick array built from the solve and returns the lamports received by the receiver
+    fn close(
+        tick_array_data: impl FnOnce(&Account<Solve>) -> Vec<u8>,
+        reward_extension_initialized: bool,
+        reward_extension_key: Option<Pubkey>,
+    ) -> Result<u64> {
+        let solve_key = Pubkey::new_unique();
+        let mut solve_lamports = 0;
+        let mut solve_data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &solve_key,
+            false,
+            false,
+            &mut solve_lamports,
+            &mut solve_data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array = SerializedAccount::new(
+            Pubkey::new_unique(),
+            &tick_array_data(&solve),
+            TICK_ARRAY_LAMPORTS,
+            crate::ID,
+        );
+        let reward_extension_key = reward_extension_key
+            .unwrap_or_else(|| reward_extension_address(&solve_key, START_TICK_INDEX));
+        let mut reward_extension = if reward_extension_initialized {
+            SerializedAccount::new(
+                reward_extension_key,
+                &reward_extension_data(&solve),
+                REWARD_EXTENSION_LAMPORTS,
+                crate::ID,
+            )
+        } else {
+            SerializedAccount::new(reward_extension_key, &[], 0, System::id())
+        };
+        let mut receiver =
+            SerializedAccount::new(Pubkey::new_unique(), &[], RECEIVER_LAMPORTS, System::id());
+
+        let tick_array_info = tick_array.account_info();
+        let reward_extension_info = reward_extension.account_info();
+        let receiver_info = receiver.account_info();
+        close_empty_tick_array(
+            &solve_key,
+            &tick_array_info,
+            &reward_extension_info,
+            &receiver_info,
+        )?;
+
+        assert_closed(&tick_array_info);
+        if reward_extension_initialized {
+            assert_closed(&reward_extension_info);
+        } else {
+            assert_eq!(reward_extension_info.lamports(), 0);
+            assert_eq!(reward_extension_info.owner, &System::id());
+        }
+        Ok(receiver_info.lamports() - RECEIVER_LAMPORTS)
+    }
+
+    #[test]
+    fn test_close_empty_fixed_tick_array() {
+        let received = close(|solve| fixed_tick_array_data(solve, false), false, None).unwrap();
+        assert_eq!(received, TICK_ARRAY_LAMPORTS);
+    }
+
+    #[test]
+    fn test_close_empty_dynamic_tick_array() {
+        let received = close(dynamic_tick_array_data, false, None).unwrap();
+        assert_eq!(received, TICK_ARRAY_LAMPORTS);
+    }
+
+    #[test]
+    fn test_close_empty_migrated_tick_array() {
+        let received = close(
+            |solve| migrated_tick_array_data(solve, &[Tick::default(); TICK_ARRAY_SIZE_USIZE]),
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(received, TICK_ARRAY_LAMPORTS);
+    }
+
+    #[test]
+    fn test_close_tick_array_with_reward_extension() {
+        let received = close(dynamic_tick_array_data, true, None).unwrap();
+        assert_eq!(received, TICK_ARRAY_LAMPORTS + REWARD_EXTENSION_LAMPORTS);
+    }
+
+    #[test]
+    fn test_close_tick_array_not_empty() {
+        let result = close(|solve| fixed_tick_array_data(solve, true), true, None);
+        assert_eq!(result.unwrap_err(), ErrorCode::TickArrayNotEmpty.into());
+
+        let mut ticks = [Tick::default(); TICK_ARRAY_SIZE_USIZE];
+        ticks[TICK_ARRAY_SIZE_USIZE - 1].initialized = true;
+        ticks[TICK_ARRAY_SIZE_USIZE - 1].liquidity_gross = 1;
+        let result = close(|solve| migrated_tick_array_data(solve, &ticks), true, None);
+        assert_eq!(result.unwrap_err(), ErrorCode::TickArrayNotEmpty.into());
+    }
+
+    #[test]
+    fn test_close_tick_array_invalid_reward_extension() {
+        let result = close(dynamic_tick_array_data, true, Some(Pubkey::new_unique()));
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidRewardExtensionAccount.into()
+        );
+    }
+}
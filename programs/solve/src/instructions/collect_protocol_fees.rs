@@ -1,4 +1,7 @@
-use crate::{state::*, util::transfer_from_vault_to_owner};
+use crate::{
+    state::*,
+    util::{transfer_from_vault_to_owner, verify_protocol_fee_recipients_not_set},
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
@@ -26,9 +29,16 @@ pub struct CollectProtocolFees<'info> {
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
+    // remaining accounts
+    // - solves_config_extension (optional)
 }
 
 pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    verify_protocol_fee_recipients_not_set(
+        &ctx.accounts.solves_config.key(),
+        ctx.remaining_accounts.first(),
+    )?;
+
     let solve = &ctx.accounts.solve;
 
     transfer_from_vault_to_owner(
@@ -10,6 +10,7 @@ use crate::{
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct CollectReward<'info> {
+    #[account(mut)]
     pub solve: Box<Account<'info, Solve>>,
 
     pub position_authority: Signer<'info>,
@@ -63,6 +64,8 @@ pub fn handler(ctx: Context<CollectReward>, reward_index: u8) -> Result<()> {
 
     position.update_reward_owed(index, updated_amount_owed);
 
+    SolveTail::track_reward_collection(&ctx.accounts.solve, index, transfer_amount)?;
+
     transfer_from_vault_to_owner(
         &ctx.accounts.solve,
         &ctx.accounts.reward_vault,
@@ -5,11 +5,12 @@ use crate::events::*;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
 use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
-use crate::state::TickArraysMut;
+use crate::state::{SolveTail, TickArraysMut};
 use crate::util::{
-    is_locked_position, to_timestamp_u64, transfer_from_vault_to_owner,
+    is_locked_position, to_timestamp_u64, transfer_from_vault_to_owner, verify_pool_not_locked,
     verify_position_authority_interface,
 };
 
@@ -18,8 +19,8 @@ use super::increase_liquidity::ModifyLiquidity;
 /*
   Removes liquidity from an existing Solve Position.
 */
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
     token_min_b: u64,
@@ -33,6 +34,8 @@ pub fn handler(
         return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
     }
 
+    verify_pool_not_locked(&ctx.accounts.solve)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -40,6 +43,22 @@ pub fn handler(
     }
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // The accounts of the reward extension are passed as remaining accounts
+    let reward_extension_accounts = Some(ctx.remaining_accounts.to_vec());
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &reward_extension_accounts)?
+    {
+        reward_extension.update_for_decrease_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
 
     let mut tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
@@ -54,9 +73,11 @@ pub fn handler(
         lower_tick_array,
         upper_tick_array,
         liquidity_delta,
+        &reward_schedules,
         timestamp,
     )?;
 
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
     let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
     sync_modify_liquidity_values(
         &mut ctx.accounts.solve,
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::{load_oracle_with_observations, Oracle, Solve},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct GetTimeWeightedAverageTick<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(seeds = [b"oracle", solve.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+}
+
+pub fn handler(
+    ctx: Context<GetTimeWeightedAverageTick>,
+    seconds_ago_start: u32,
+    seconds_ago_end: u32,
+) -> Result<i32> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // AccountLoader::load checks the discriminator
+    drop(ctx.accounts.oracle.load()?);
+
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+    let data = oracle_account_info.try_borrow_data()?;
+    let (oracle, observations) = load_oracle_with_observations(&data)?;
+
+    let solve = &ctx.accounts.solve;
+    oracle.get_time_weighted_average_tick(
+        observations,
+        timestamp,
+        seconds_ago_start,
+        seconds_ago_end,
+        solve.tick_current_index,
+        solve.liquidity,
+    )
+}
@@ -7,11 +7,13 @@ use crate::events::*;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
 use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
 use crate::util::{
-    to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority_interface,
+    to_timestamp_u64, transfer_from_owner_to_vault, verify_pool_not_paused,
+    verify_position_authority_interface,
 };
 
 #[derive(Accounts)]
@@ -50,8 +52,8 @@ pub struct ModifyLiquidity<'info> {
     pub tick_array_upper: UncheckedAccount<'info>,
 }
 
-pub fn handler(
-    ctx: Context<ModifyLiquidity>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
     liquidity_amount: u128,
     token_max_a: u64,
     token_max_b: u64,
@@ -61,6 +63,8 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::IncreaseLiquidity)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -68,6 +72,22 @@ pub fn handler(
     }
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // The accounts of the reward extension are passed as remaining accounts
+    let reward_extension_accounts = Some(ctx.remaining_accounts.to_vec());
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &reward_extension_accounts)?
+    {
+        reward_extension.update_for_modify_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
 
     let tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
@@ -82,6 +102,7 @@ pub fn handler(
         lower_tick_array,
         upper_tick_array,
         liquidity_delta,
+        &reward_schedules,
         timestamp,
     )?;
 
@@ -102,6 +123,7 @@ pub fn handler(
         &ctx.accounts.solve.key(),
     )?;
 
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
     let (lower_tick_array, upper_tick_array) = tick_arrays.deref_mut();
     sync_modify_liquidity_values(
         &mut ctx.accounts.solve,
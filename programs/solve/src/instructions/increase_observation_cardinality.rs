@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+use crate::{
+    state::{
+        load_oracle_with_observations_mut, verify_oracle_size_increase, LimitOrderEntry, Oracle,
+        PausableOperation, Solve,
+    },
+    util::{to_timestamp_u64, verify_pool_not_paused},
+};
+
+#[derive(Accounts)]
+pub struct IncreaseObservationCardinality<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<IncreaseObservationCardinality>,
+    observation_cardinality_next: u16,
+) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let (limit_orders_offset, limit_order_capacity) = {
+        let mut oracle = ctx.accounts.oracle.load_mut()?;
+        let limit_orders_offset = oracle.limit_orders_offset();
+        oracle.update_observation_cardinality_next(observation_cardinality_next)?;
+        (limit_orders_offset, oracle.limit_order_capacity)
+    };
+
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+    let required_size = Oracle::space(observation_cardinality_next, limit_order_capacity);
+    verify_oracle_size_increase(oracle_account_info.data_len(), required_size)?;
+
+    // Rent for the new observation slots is paid by the funder
+    let required_lamports = Rent::get()?
+        .minimum_balance(required_size)
+        .saturating_sub(oracle_account_info.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.funder.key,
+                oracle_account_info.key,
+                required_lamports,
+            ),
+            &[
+                ctx.accounts.funder.to_account_info(),
+                oracle_account_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    oracle_account_info.realloc(required_size, true)?;
+
+    let mut data = oracle_account_info.try_borrow_mut_data()?;
+
+    // Limit orders are stored after the observation slots, so they are moved to the end of the grown buffer
+    let limit_orders_len = limit_order_capacity as usize * LimitOrderEntry::LEN;
+    if limit_orders_len > 0 {
+        let next_limit_orders_offset = required_size - limit_orders_len;
+        data.copy_within(
+            limit_orders_offset..limit_orders_offset + limit_orders_len,
+            next_limit_orders_offset,
+        );
+        data[limit_orders_offset..next_limit_orders_offset].fill(0);
+    }
+
+    // The first observation is written when the buffer is allocated for the first time
+    let (oracle, observations) = load_oracle_with_observations_mut(&mut data)?;
+    if !oracle.is_observation_enabled() {
+        oracle.initialize_observations(observations, timestamp)?;
+    }
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::cmp::max;
 use anchor_lang::{prelude::*, system_program, Discriminator};
 use solana_program::{program::invoke_signed, system_instruction};
 
-use crate::{state::*, ID};
+use crate::{state::*, util::verify_pool_not_paused, ID};
 
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
@@ -31,6 +31,8 @@ pub fn handler(
     start_tick_index: i32,
     idempotent: bool,
 ) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     if ctx.accounts.tick_array.owner == &system_program::ID {
         let current_balance = ctx.accounts.tick_array.lamports();
 
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Oracle, Solve, SolvesConfig};
+
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    pub solves_config: Account<'info, SolvesConfig>,
+
+    #[account(has_one = solves_config)]
+    pub solve: Account<'info, Solve>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"oracle", solve.key().as_ref()],
+        bump,
+        space = Oracle::LEN)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(address = solves_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOracle>) -> Result<()> {
+    // Pools initialized with AdaptiveFeeTier already have an Oracle account,
+    // so this is only reachable for pools initialized with FeeTier.
+    let mut oracle = ctx.accounts.oracle.load_init()?;
+    oracle.initialize_without_adaptive_fee(ctx.accounts.solve.key());
+    Ok(())
+}
@@ -41,6 +41,7 @@ pub struct InitializePositionBundle<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
+// Not paused: a position bundle does not belong to any pool, positions are opened in a pool separately.
 pub fn handler(ctx: Context<InitializePositionBundle>) -> Result<()> {
     let position_bundle_mint = &ctx.accounts.position_bundle_mint;
     let position_bundle = &mut ctx.accounts.position_bundle;
@@ -54,6 +54,7 @@ pub struct InitializePositionBundleWithMetadata<'info> {
     pub metadata_program: Program<'info, Metadata>,
 }
 
+// Not paused: a position bundle does not belong to any pool, positions are opened in a pool separately.
 pub fn handler(ctx: Context<InitializePositionBundleWithMetadata>) -> Result<()> {
     let position_bundle_mint = &ctx.accounts.position_bundle_mint;
     let position_bundle = &mut ctx.accounts.position_bundle;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::*;
+use crate::util::verify_position_authority_interface;
+
+#[derive(Accounts)]
+pub struct InitializePositionKeeper<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    pub position: Account<'info, Position>,
+
+    #[account(
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint,
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(init,
+        payer = funder,
+        space = PositionKeeper::LEN,
+        seeds = [b"position_keeper".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub position_keeper: Account<'info, PositionKeeper>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializePositionKeeper>,
+    keeper: Pubkey,
+    sqrt_price_lower_bound: u128,
+    sqrt_price_upper_bound: u128,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    ctx.accounts.position_keeper.initialize(
+        ctx.accounts.position.key(),
+        ctx.accounts.position_token_account.owner,
+        keeper,
+        sqrt_price_lower_bound,
+        sqrt_price_upper_bound,
+    )
+}
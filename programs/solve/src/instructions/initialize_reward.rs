@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
-use crate::state::Solve;
+use crate::state::{PausableOperation, Solve, SolveTail};
+use crate::util::verify_pool_not_paused;
 
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
@@ -32,11 +33,20 @@ pub struct InitializeReward<'info> {
 }
 
 pub fn handler(ctx: Context<InitializeReward>, reward_index: u8) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     let solve = &mut ctx.accounts.solve;
 
     solve.initialize_reward(
         reward_index as usize,
         ctx.accounts.reward_mint.key(),
         ctx.accounts.reward_vault.key(),
+    )?;
+
+    // The emissions of the reward are tracked from the start
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
     )
 }
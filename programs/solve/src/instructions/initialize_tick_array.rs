@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::state::*;
+use crate::util::verify_pool_not_paused;
 
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
@@ -22,6 +23,8 @@ pub struct InitializeTickArray<'info> {
 }
 
 pub fn handler(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     let mut tick_array = ctx.accounts.tick_array.load_init()?;
     tick_array.initialize(&ctx.accounts.solve, start_tick_index)
 }
@@ -5,7 +5,8 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 use crate::errors::ErrorCode;
 use crate::state::*;
 use crate::util::{
-    freeze_user_position_token_2022, is_locked_position, verify_position_authority_interface,
+    freeze_user_position_token_2022, is_locked_position, verify_pool_not_paused,
+    verify_position_authority_interface,
 };
 
 #[derive(Accounts)]
@@ -48,6 +49,8 @@ pub struct LockPosition<'info> {
 }
 
 pub fn handler(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     verify_position_authority_interface(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
@@ -81,6 +84,7 @@ pub fn handler(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
         ctx.accounts.position_token_account.owner,
         ctx.accounts.position.solve,
         Clock::get()?.unix_timestamp as u64,
+        ctx.accounts.position.liquidity,
         lock_type,
     )?;
 
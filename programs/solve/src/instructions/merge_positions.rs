@@ -0,0 +1,179 @@
+use std::ops::Deref;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::calculate_fee_and_reward_growths;
+use crate::state::*;
+use crate::util::{
+    burn_and_close_user_position_token_interface, is_locked_position, to_timestamp_u64,
+    verify_pool_and_config_not_paused, verify_position_authority_interface,
+    verify_position_without_reward_extension,
+};
+
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = solve)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        has_one = solve,
+        close = receiver,
+        seeds = [b"position".as_ref(), closed_position_mint.key().as_ref()],
+        bump,
+    )]
+    pub closed_position: Account<'info, Position>,
+
+    #[account(mut,
+        address = closed_position.position_mint,
+        owner = closed_position_token_program.key(),
+    )]
+    pub closed_position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = closed_position_token_account.mint == closed_position.position_mint,
+        constraint = closed_position_token_account.amount == 1
+    )]
+    pub closed_position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    pub closed_position_token_program: Interface<'info, TokenInterface>,
+}
+
+/*
+  Merges a position into another position with the same range and owner.
+  The liquidity and the fees and rewards owed are moved, then the position token of the
+  merged position is burned and its accounts are closed, except for legacy position mints which
+  the Token program cannot close. The ticks are left unchanged because the total liquidity in the
+  range does not change, so the reward extension of the pool is not affected unless a position has
+  a PositionRewardExtension.
+*/
+pub fn handler(ctx: Context<MergePositions>) -> Result<()> {
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Other,
+    )?;
+    verify_position_without_reward_extension(&ctx.accounts.position)?;
+    verify_position_without_reward_extension(&ctx.accounts.closed_position)?;
+
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+    verify_position_authority_interface(
+        &ctx.accounts.closed_position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if is_locked_position(&ctx.accounts.position_token_account)
+        || is_locked_position(&ctx.accounts.closed_position_token_account)
+    {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    if ctx.accounts.position.key() == ctx.accounts.closed_position.key()
+        || ctx.accounts.position_token_account.owner
+            != ctx.accounts.closed_position_token_account.owner
+        || ctx.accounts.position.tick_lower_index != ctx.accounts.closed_position.tick_lower_index
+        || ctx.accounts.position.tick_upper_index != ctx.accounts.closed_position.tick_upper_index
+    {
+        return Err(ErrorCode::PositionsNotMergeable.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Both positions share the tick arrays because they have the same range
+    let lower_tick_array =
+        load_tick_array(&ctx.accounts.tick_array_lower, &ctx.accounts.solve.key())?;
+    let upper_tick_array =
+        load_tick_array(&ctx.accounts.tick_array_upper, &ctx.accounts.solve.key())?;
+
+    // Checkpoint both positions (calculation for positions with zero liquidity results in an error)
+    if ctx.accounts.position.liquidity > 0 {
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            lower_tick_array.deref(),
+            upper_tick_array.deref(),
+            &reward_schedules,
+            timestamp,
+        )?;
+        SolveTail::track_reward_emissions(&ctx.accounts.solve, &reward_infos)?;
+        ctx.accounts.solve.update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+    }
+
+    if ctx.accounts.closed_position.liquidity > 0 {
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ctx.accounts.solve,
+            &ctx.accounts.closed_position,
+            lower_tick_array.deref(),
+            upper_tick_array.deref(),
+            &reward_schedules,
+            timestamp,
+        )?;
+        SolveTail::track_reward_emissions(&ctx.accounts.solve, &reward_infos)?;
+        ctx.accounts.solve.update_rewards(reward_infos, timestamp);
+        ctx.accounts.closed_position.update(&position_update);
+    }
+
+    drop(lower_tick_array);
+    drop(upper_tick_array);
+
+    let liquidity = ctx.accounts.closed_position.liquidity;
+    ctx.accounts.position.merge(&ctx.accounts.closed_position)?;
+
+    // The Token program cannot close mints, so a legacy position mint is left open as close_position does.
+    // Its supply is zero after the burn and it has no mint authority, so it cannot be used again.
+    // Token-2022 position mints are closed with the position as their close authority.
+    burn_and_close_user_position_token_interface(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.receiver,
+        &ctx.accounts.closed_position_mint,
+        &ctx.accounts.closed_position_token_account,
+        &ctx.accounts.closed_position_token_program,
+        &ctx.accounts.closed_position,
+        &[
+            b"position".as_ref(),
+            ctx.accounts.closed_position_mint.key().as_ref(),
+            &[ctx.bumps.closed_position],
+        ],
+    )?;
+
+    emit!(PositionsMerged {
+        solve: ctx.accounts.solve.key(),
+        position: ctx.accounts.position.key(),
+        closed_position: ctx.accounts.closed_position.key(),
+        liquidity,
+        merged_liquidity: ctx.accounts.position.liquidity,
+    });
+
+    Ok(())
+}
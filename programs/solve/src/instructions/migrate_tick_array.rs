@@ -0,0 +1,269 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::util::verify_pool_and_config_not_paused;
+
+#[derive(Accounts)]
+pub struct MigrateTickArray<'info> {
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler, FixedTickArray of the solve
+    pub tick_array: UncheckedAccount<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut, address = solves_config_extension.tick_array_rent_recipient)]
+    pub tick_array_rent_recipient: UncheckedAccount<'info>,
+}
+
+/*
+  Rewrites a FixedTickArray into the DynamicTickArray layout in place, preserving all initialized ticks.
+  The account is reallocated to the size of the DynamicTickArray and the excess rent is refunded to
+  the tick array rent recipient configured in the SolvesConfigExtension.
+
+  Tick arrays do not record who funded them, so the rent cannot be returned to the original funder on-chain.
+  Since the refund always goes to the configured recipient, anyone can invoke this instruction.
+
+  The positions opened before the migration did not transfer the rent for their ticks to the tick array.
+  The migrated tick array is marked, so that only its surplus over the rent-exempt minimum is refunded
+  to these positions when their ticks are uninitialized.
+*/
+pub fn handler(ctx: Context<MigrateTickArray>) -> Result<()> {
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension.to_account_info(),
+        PausableOperation::Other,
+    )?;
+
+    if ctx
+        .accounts
+        .solves_config_extension
+        .tick_array_rent_recipient
+        == Pubkey::default()
+    {
+        return Err(ErrorCode::TickArrayRentRecipientNotSet.into());
+    }
+
+    let excess_lamports = migrate_tick_array_account(
+        &ctx.accounts.solve,
+        &ctx.accounts.tick_array.to_account_info(),
+        &Rent::get()?,
+    )?;
+    ctx.accounts.tick_array.sub_lamports(excess_lamports)?;
+    ctx.accounts
+        .tick_array_rent_recipient
+        .add_lamports(excess_lamports)?;
+
+    Ok(())
+}
+
+// Rewrites the FixedTickArray into the DynamicTickArray layout and shrinks the account.
+// Returns the lamports in excess of the rent-exempt minimum of the new size.
+fn migrate_tick_array_account(
+    solve: &Account<Solve>,
+    tick_array_info: &AccountInfo,
+    rent: &Rent,
+) -> Result<u64> {
+    if tick_array_info.owner != &crate::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+
+    let (start_tick_index, ticks) = {
+        let data = tick_array_info.try_borrow_data()?;
+        if data.len() != FixedTickArray::LEN || data[0..8] != FixedTickArray::DISCRIMINATOR {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        let fixed_tick_array: &FixedTickArray = bytemuck::from_bytes(&data[8..]);
+        if fixed_tick_array.solve() != solve.key() {
+            return Err(ErrorCode::DifferentSolveTickArrayAccount.into());
+        }
+        (
+            fixed_tick_array.start_tick_index(),
+            fixed_tick_array.ticks_to_vec(),
+        )
+    };
+
+    let initialized_ticks = ticks.iter().filter(|tick| tick.initialized).count();
+    let required_size = DynamicTickArray::MIN_LEN + initialized_ticks * DynamicTickData::LEN;
+
+    // DynamicTickArrayLoader accesses up to MAX_LEN bytes, which exceeds FixedTickArray::LEN
+    tick_array_info.realloc(DynamicTickArray::MAX_LEN, false)?;
+    {
+        let mut data = tick_array_info.try_borrow_mut_data()?;
+        data.fill(0);
+        data[0..8].copy_from_slice(&DynamicTickArray::DISCRIMINATOR);
+        let tick_array = DynamicTickArrayLoader::load_mut(&mut data[8..]);
+        tick_array.initialize_with_ticks(solve, start_tick_index, &ticks)?;
+    }
+    tick_array_info.realloc(required_size, false)?;
+
+    let rent_exempt_lamports = rent.minimum_balance(required_size);
+    Ok(tick_array_info
+        .lamports()
+        .saturating_sub(rent_exempt_lamports))
+}
+
+#[cfg(test)]
+mod migrate_tick_array_tests {
+    use super::*;
+    use crate::util::serialized_account::SerializedAccount;
+
+    const TICK_SPACING: u16 = 64;
+    const START_TICK_INDEX: i32 = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+    const INITIALIZED_TICK_OFFSETS: [usize; 3] = [0, 10, TICK_ARRAY_SIZE_USIZE - 1];
+
+    fn solve_data() -> Vec<u8> {
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Solve::LEN];
+        solve.try_serialize(&mut data.as_mut_slice()).unwrap();
+        data
+    }
+
+    fn tick_index(tick_offset: usize) -> i32 {
+        START_TICK_INDEX + tick_offset as i32 * TICK_SPACING as i32
+    }
+
+    fn tick_update(tick_offset: usize) -> TickUpdate {
+        TickUpdate {
+            initialized: true,
+            liquidity_net: tick_offset as i128 + 1,
+            liquidity_gross: tick_offset as u128 + 1,
+            fee_growth_outside_a: tick_offset as u128 + 2,
+            fee_growth_outside_b: tick_offset as u128 + 3,
+            reward_growths_outside: [tick_offset as u128 + 4; NUM_REWARDS],
+        }
+    }
+
+    fn fixed_tick_array_data(solve: &Account<Solve>) -> Vec<u8> {
+        let mut data = vec![0u8; FixedTickArray::LEN];
+        data[0..8].copy_from_slice(&FixedTickArray::DISCRIMINATOR);
+        let tick_array: &mut FixedTickArray = bytemuck::from_bytes_mut(&mut data[8..]);
+        tick_array.initialize(solve, START_TICK_INDEX).unwrap();
+        for tick_offset in INITIALIZED_TICK_OFFSETS {
+            tick_array
+                .update_tick(
+                    tick_index(tick_offset),
+                    TICK_SPACING,
+                    &tick_update(tick_offset),
+                )
+                .unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn test_migrate_tick_array_account() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve_lamports = 0;
+        let mut solve_data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &solve_key,
+            false,
+            false,
+            &mut solve_lamports,
+            &mut solve_data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let rent = Rent::default();
+        let fixed_lamports = rent.minimum_balance(FixedTickArray::LEN);
+        let mut tick_array = SerializedAccount::new(
+            Pubkey::new_unique(),
+            &fixed_tick_array_data(&solve),
+            fixed_lamports,
+            crate::ID,
+        );
+        let tick_array_info = tick_array.account_info();
+
+        let excess_lamports = migrate_tick_array_account(&solve, &tick_array_info, &rent).unwrap();
+
+        // The account is shrunk to the size of the initialized ticks
+        let required_size =
+            DynamicTickArray::MIN_LEN + INITIALIZED_TICK_OFFSETS.len() * DynamicTickData::LEN;
+        assert_eq!(tick_array_info.data_len(), required_size);
+        assert_eq!(
+            excess_lamports,
+            fixed_lamports - rent.minimum_balance(required_size)
+        );
+
+        // All ticks are preserved
+        let loaded_tick_array = load_tick_array(&tick_array_info, &solve_key).unwrap();
+        assert!(loaded_tick_array.is_variable_size());
+        assert_eq!(loaded_tick_array.start_tick_index(), START_TICK_INDEX);
+        for tick_offset in 0..TICK_ARRAY_SIZE_USIZE {
+            let tick = loaded_tick_array
+                .get_tick(tick_index(tick_offset), TICK_SPACING)
+                .unwrap();
+            if INITIALIZED_TICK_OFFSETS.contains(&tick_offset) {
+                assert_eq!(TickUpdate::from(tick), tick_update(tick_offset));
+            } else {
+                assert_eq!(tick, Tick::default());
+            }
+        }
+        drop(loaded_tick_array);
+
+        let data = tick_array_info.try_borrow_data().unwrap();
+        assert!(DynamicTickArrayLoader::load(&data[8..]).is_migrated());
+    }
+
+    #[test]
+    fn test_migrate_tick_array_account_invalid_account() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve_lamports = 0;
+        let mut solve_data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &solve_key,
+            false,
+            false,
+            &mut solve_lamports,
+            &mut solve_data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+        let rent = Rent::default();
+        let data = fixed_tick_array_data(&solve);
+
+        // Owned by another program
+        let mut tick_array =
+            SerializedAccount::new(Pubkey::new_unique(), &data, 0, Pubkey::new_unique());
+        assert_eq!(
+            migrate_tick_array_account(&solve, &tick_array.account_info(), &rent).unwrap_err(),
+            anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into()
+        );
+
+        // Not a FixedTickArray
+        let mut dynamic_data = data.clone();
+        dynamic_data[0..8].copy_from_slice(&DynamicTickArray::DISCRIMINATOR);
+        let mut tick_array =
+            SerializedAccount::new(Pubkey::new_unique(), &dynamic_data, 0, crate::ID);
+        assert_eq!(
+            migrate_tick_array_account(&solve, &tick_array.account_info(), &rent).unwrap_err(),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into()
+        );
+
+        // FixedTickArray of another solve
+        let mut other_solve_data = data;
+        other_solve_data[FixedTickArray::LEN - 32..].copy_from_slice(Pubkey::new_unique().as_ref());
+        let mut tick_array =
+            SerializedAccount::new(Pubkey::new_unique(), &other_solve_data, 0, crate::ID);
+        assert_eq!(
+            migrate_tick_array_account(&solve, &tick_array.account_info(), &rent).unwrap_err(),
+            ErrorCode::DifferentSolveTickArrayAccount.into()
+        );
+    }
+}
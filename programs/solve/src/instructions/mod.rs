@@ -1,24 +1,35 @@
 #![allow(ambiguous_glob_reexports)]
 
+pub mod accept_collect_protocol_fees_authority;
+pub mod accept_fee_authority;
+pub mod accept_reward_emissions_super_authority;
 pub mod close_bundled_position;
 pub mod close_position;
+pub mod close_position_keeper;
 pub mod close_position_with_token_extensions;
+pub mod close_tick_array;
 pub mod collect_fees;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
 pub mod decrease_liquidity;
 pub mod delete_position_bundle;
+pub mod get_time_weighted_average_tick;
 pub mod idl_include;
 pub mod increase_liquidity;
+pub mod increase_observation_cardinality;
 pub mod initialize_config;
 pub mod initialize_dynamic_tick_array;
 pub mod initialize_fee_tier;
+pub mod initialize_oracle;
 pub mod initialize_pool;
 pub mod initialize_position_bundle;
 pub mod initialize_position_bundle_with_metadata;
+pub mod initialize_position_keeper;
 pub mod initialize_reward;
 pub mod initialize_tick_array;
 pub mod lock_position;
+pub mod merge_positions;
+pub mod migrate_tick_array;
 pub mod open_bundled_position;
 pub mod open_position;
 pub mod open_position_with_metadata;
@@ -34,30 +45,43 @@ pub mod set_reward_authority;
 pub mod set_reward_authority_by_super_authority;
 pub mod set_reward_emissions;
 pub mod set_reward_emissions_super_authority;
+pub mod split_position;
 pub mod swap;
 pub mod transfer_locked_position;
 pub mod two_hop_swap;
+pub mod unlock_position;
 pub mod update_fees_and_rewards;
 
+pub use accept_collect_protocol_fees_authority::*;
+pub use accept_fee_authority::*;
+pub use accept_reward_emissions_super_authority::*;
 pub use close_bundled_position::*;
 pub use close_position::*;
+pub use close_position_keeper::*;
 pub use close_position_with_token_extensions::*;
+pub use close_tick_array::*;
 pub use collect_fees::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
 
 pub use delete_position_bundle::*;
+pub use get_time_weighted_average_tick::*;
 pub use idl_include::*;
 pub use increase_liquidity::*;
+pub use increase_observation_cardinality::*;
 pub use initialize_config::*;
 pub use initialize_dynamic_tick_array::*;
 pub use initialize_fee_tier::*;
+pub use initialize_oracle::*;
 pub use initialize_pool::*;
 pub use initialize_position_bundle::*;
 pub use initialize_position_bundle_with_metadata::*;
+pub use initialize_position_keeper::*;
 pub use initialize_reward::*;
 pub use initialize_tick_array::*;
 pub use lock_position::*;
+pub use merge_positions::*;
+pub use migrate_tick_array::*;
 pub use open_bundled_position::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
@@ -73,9 +97,11 @@ pub use set_reward_authority::*;
 pub use set_reward_authority_by_super_authority::*;
 pub use set_reward_emissions::*;
 pub use set_reward_emissions_super_authority::*;
+pub use split_position::*;
 pub use swap::*;
 pub use transfer_locked_position::*;
 pub use two_hop_swap::*;
+pub use unlock_position::*;
 pub use update_fees_and_rewards::*;
 pub mod v2;
 pub use v2::*;
@@ -2,8 +2,9 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::{
-    manager::tick_array_manager::collect_rent_for_ticks_in_position, state::*,
-    util::verify_position_bundle_authority,
+    manager::tick_array_manager::collect_rent_for_ticks_in_position,
+    state::*,
+    util::{verify_pool_not_paused, verify_position_bundle_authority},
 };
 
 #[derive(Accounts)]
@@ -48,6 +49,8 @@ pub fn handler(
     tick_upper_index: i32,
 ) -> Result<()> {
     let solve = &ctx.accounts.solve;
+
+    verify_pool_not_paused(solve, PausableOperation::Other)?;
     let position_bundle = &mut ctx.accounts.position_bundle;
     let position = &mut ctx.accounts.bundled_position;
 
@@ -4,7 +4,10 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::manager::tick_array_manager::collect_rent_for_ticks_in_position;
 use crate::state;
-use crate::{state::*, util::mint_position_token_and_remove_authority};
+use crate::{
+    state::*,
+    util::{mint_position_token_and_remove_authority, verify_pool_not_paused},
+};
 
 #[derive(Accounts)]
 pub struct OpenPosition<'info> {
@@ -56,6 +59,8 @@ pub fn handler(
     tick_upper_index: i32,
 ) -> Result<()> {
     let solve = &ctx.accounts.solve;
+
+    verify_pool_not_paused(solve, PausableOperation::Other)?;
     let position_mint = &ctx.accounts.position_mint;
     let position = &mut ctx.accounts.position;
 
@@ -1,6 +1,6 @@
 use crate::manager::tick_array_manager::collect_rent_for_ticks_in_position;
 use crate::state::*;
-use crate::util::build_position_token_metadata;
+use crate::util::{build_position_token_metadata, verify_pool_not_paused};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_2022::spl_token_2022;
@@ -58,6 +58,8 @@ pub fn handler(
     with_token_metadata: bool,
 ) -> Result<()> {
     let solve = &ctx.accounts.solve;
+
+    verify_pool_not_paused(solve, PausableOperation::Other)?;
     let position_mint = &ctx.accounts.position_mint;
     let position = &mut ctx.accounts.position;
 
@@ -5,7 +5,7 @@ use solana_program::system_instruction;
 
 use crate::manager::tick_array_manager::get_tick_rent_amount;
 use crate::state::*;
-use crate::util::verify_position_authority_interface;
+use crate::util::{verify_pool_not_paused, verify_position_authority_interface};
 
 #[derive(Accounts)]
 pub struct ResetPositionRange<'info> {
@@ -35,6 +35,8 @@ pub fn handler(
     new_tick_lower_index: i32,
     new_tick_upper_index: i32,
 ) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     verify_position_authority_interface(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
@@ -53,7 +55,7 @@ pub fn handler(
     )
 }
 
-fn ensure_position_has_enough_rent_for_ticks<'info>(
+pub(crate) fn ensure_position_has_enough_rent_for_ticks<'info>(
     funder: &Signer<'info>,
     position: &Account<'info, Position>,
     system_program: &Program<'info, System>,
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::state::{FeeTier, SolvesConfig};
+use crate::util::verify_admin_timelock_disabled;
 
 #[derive(Accounts)]
 pub struct SetDefaultFeeRate<'info> {
@@ -11,12 +12,19 @@ pub struct SetDefaultFeeRate<'info> {
 
     #[account(address = solves_config.fee_authority)]
     pub fee_authority: Signer<'info>,
+
+    // appended after the accounts of the instruction before the admin timelock was introduced
+    #[account(seeds = [b"config_extension", solves_config.key().as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
 }
 
 /*
    Updates the default fee rate on a FeeTier object.
 */
 pub fn handler(ctx: Context<SetDefaultFeeRate>, default_fee_rate: u16) -> Result<()> {
+    verify_admin_timelock_disabled(&ctx.accounts.solves_config_extension)?;
+
     ctx.accounts
         .fee_tier
         .update_default_fee_rate(default_fee_rate)
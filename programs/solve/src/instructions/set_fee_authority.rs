@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::SolvesConfig;
+use crate::state::{SolvesConfig, SolvesConfigExtension};
 
 #[derive(Accounts)]
 pub struct SetFeeAuthority<'info> {
-    #[account(mut)]
     pub solves_config: Account<'info, SolvesConfig>,
 
     #[account(address = solves_config.fee_authority)]
@@ -12,12 +11,17 @@ pub struct SetFeeAuthority<'info> {
 
     /// CHECK: safe, the account that will be new authority can be arbitrary
     pub new_fee_authority: UncheckedAccount<'info>,
+
+    // appended after the accounts of the one-step version of this instruction
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
 }
 
-/// Set the fee authority. Only the current fee authority has permission to invoke this instruction.
+/// Nominate the new fee authority. Only the current fee authority has permission to invoke this instruction.
+/// The nominated key becomes the fee authority once it signs accept_fee_authority.
 pub fn handler(ctx: Context<SetFeeAuthority>) -> Result<()> {
     ctx.accounts
-        .solves_config
-        .update_fee_authority(ctx.accounts.new_fee_authority.key());
+        .solves_config_extension
+        .update_pending_fee_authority(ctx.accounts.new_fee_authority.key());
     Ok(())
 }
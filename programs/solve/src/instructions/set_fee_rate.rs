@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::state::{Solve, SolvesConfig};
+use crate::util::verify_admin_timelock_disabled;
 
 #[derive(Accounts)]
 pub struct SetFeeRate<'info> {
@@ -11,8 +12,15 @@ pub struct SetFeeRate<'info> {
 
     #[account(address = solves_config.fee_authority)]
     pub fee_authority: Signer<'info>,
+
+    // appended after the accounts of the instruction before the admin timelock was introduced
+    #[account(seeds = [b"config_extension", solves_config.key().as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
+    verify_admin_timelock_disabled(&ctx.accounts.solves_config_extension)?;
+
     ctx.accounts.solve.update_fee_rate(fee_rate)
 }
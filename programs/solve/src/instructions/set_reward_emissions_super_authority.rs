@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::SolvesConfig;
+use crate::state::{SolvesConfig, SolvesConfigExtension};
 
 #[derive(Accounts)]
 pub struct SetRewardEmissionsSuperAuthority<'info> {
-    #[account(mut)]
     pub solves_config: Account<'info, SolvesConfig>,
 
     #[account(address = solves_config.reward_emissions_super_authority)]
@@ -12,12 +11,16 @@ pub struct SetRewardEmissionsSuperAuthority<'info> {
 
     /// CHECK: safe, the account that will be new authority can be arbitrary
     pub new_reward_emissions_super_authority: UncheckedAccount<'info>,
+
+    // appended after the accounts of the one-step version of this instruction
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
 }
 
 pub fn handler(ctx: Context<SetRewardEmissionsSuperAuthority>) -> Result<()> {
     ctx.accounts
-        .solves_config
-        .update_reward_emissions_super_authority(
+        .solves_config_extension
+        .update_pending_reward_emissions_super_authority(
             ctx.accounts.new_reward_emissions_super_authority.key(),
         );
     Ok(())
@@ -0,0 +1,215 @@
+use std::ops::Deref;
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::nft::solve_nft_update_auth::ID as POSITION_NFT_UPDATE_AUTH;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::calculate_fee_and_reward_growths;
+use crate::manager::tick_array_manager::collect_rent_for_ticks_in_position;
+use crate::state::*;
+use crate::util::{
+    build_position_token_metadata, initialize_position_mint_2022,
+    initialize_position_token_account_2022, initialize_token_metadata_extension,
+    is_locked_position, mint_position_token_2022_and_remove_authority, to_timestamp_u64,
+    verify_pool_and_config_not_paused, verify_position_authority_interface,
+    verify_position_without_reward_extension,
+};
+
+#[derive(Accounts)]
+pub struct SplitPosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = solve)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: safe, the account that will be the owner of the new position can be arbitrary
+    pub new_position_owner: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"position".as_ref(), new_position_mint.key().as_ref()],
+      bump,
+    )]
+    pub new_position: Box<Account<'info, Position>>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub new_position_mint: Signer<'info>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub new_position_token_account: UncheckedAccount<'info>,
+
+    #[account(address = spl_token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: checked via account constraints
+    #[account(address = POSITION_NFT_UPDATE_AUTH)]
+    pub metadata_update_auth: UncheckedAccount<'info>,
+}
+
+/*
+  Moves part of the liquidity of a position into a new Token-2022 position with the same range.
+  Fees and rewards owed are split pro-rata to the liquidity. The ticks are left unchanged
+  because the total liquidity in the range does not change, so the reward extension of the pool
+  is not affected unless the position has a PositionRewardExtension.
+*/
+pub fn handler(
+    ctx: Context<SplitPosition>,
+    liquidity_amount: u128,
+    with_token_metadata: bool,
+) -> Result<()> {
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Other,
+    )?;
+    verify_position_without_reward_extension(&ctx.accounts.position)?;
+
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if is_locked_position(&ctx.accounts.position_token_account) {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Bring the fees and rewards owed up to date before splitting them
+    let lower_tick_array =
+        load_tick_array(&ctx.accounts.tick_array_lower, &ctx.accounts.solve.key())?;
+    let upper_tick_array =
+        load_tick_array(&ctx.accounts.tick_array_upper, &ctx.accounts.solve.key())?;
+    let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array.deref(),
+        upper_tick_array.deref(),
+        &reward_schedules,
+        timestamp,
+    )?;
+    drop(lower_tick_array);
+    drop(upper_tick_array);
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &reward_infos)?;
+    ctx.accounts.solve.update_rewards(reward_infos, timestamp);
+    ctx.accounts.position.update(&position_update);
+
+    let solve = &ctx.accounts.solve;
+    let position = &mut ctx.accounts.position;
+    let new_position_mint = &ctx.accounts.new_position_mint;
+    let new_position = &mut ctx.accounts.new_position;
+
+    let new_position_seeds = [
+        b"position".as_ref(),
+        new_position_mint.key.as_ref(),
+        &[ctx.bumps.new_position],
+    ];
+
+    collect_rent_for_ticks_in_position(
+        &ctx.accounts.funder,
+        new_position,
+        &ctx.accounts.system_program,
+    )?;
+
+    new_position.open_position(
+        solve,
+        new_position_mint.key(),
+        position.tick_lower_index,
+        position.tick_upper_index,
+    )?;
+
+    position.split(new_position, liquidity_amount)?;
+
+    initialize_position_mint_2022(
+        new_position_mint,
+        &ctx.accounts.funder,
+        new_position,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_2022_program,
+        with_token_metadata,
+    )?;
+
+    if with_token_metadata {
+        let (name, symbol, uri) =
+            build_position_token_metadata(new_position_mint, new_position, solve);
+
+        initialize_token_metadata_extension(
+            name,
+            symbol,
+            uri,
+            new_position_mint,
+            new_position,
+            &ctx.accounts.metadata_update_auth,
+            &ctx.accounts.funder,
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_2022_program,
+            &new_position_seeds,
+        )?;
+    }
+
+    initialize_position_token_account_2022(
+        &ctx.accounts.new_position_token_account,
+        new_position_mint,
+        &ctx.accounts.funder,
+        &ctx.accounts.new_position_owner,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.associated_token_program,
+    )?;
+
+    mint_position_token_2022_and_remove_authority(
+        new_position,
+        new_position_mint,
+        &ctx.accounts.new_position_token_account,
+        &ctx.accounts.token_2022_program,
+        &new_position_seeds,
+    )?;
+
+    emit!(PositionSplit {
+        solve: solve.key(),
+        position: position.key(),
+        new_position: new_position.key(),
+        liquidity: liquidity_amount,
+        fee_owed_a: new_position.fee_owed_a,
+        fee_owed_b: new_position.fee_owed_b,
+    });
+
+    Ok(())
+}
@@ -4,9 +4,15 @@ use anchor_spl::token::{self, Token, TokenAccount};
 use crate::{
     errors::ErrorCode,
     events::*,
-    manager::swap_manager::*,
-    state::{OracleAccessor, Solve},
-    util::{to_timestamp_u64, update_and_swap_solve, SparseSwapTickSequenceBuilder},
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+        swap_manager::*,
+    },
+    state::{OracleAccessor, PausableOperation, Solve, SolveTail},
+    util::{
+        to_timestamp_u64, update_and_swap_solve, verify_pool_not_paused,
+        SparseSwapTickSequenceBuilder,
+    },
 };
 
 #[derive(Accounts)]
@@ -44,16 +50,17 @@ pub struct Swap<'info> {
     #[account(seeds = [b"oracle", solve.key().as_ref()], bump)]
     /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
     pub oracle: UncheckedAccount<'info>,
-    // Special notes to support pools with AdaptiveFee:
-    // - For trades on pool using AdaptiveFee, pass oracle as writable accounts in the remaining accounts.
+    // Special notes to support pools with AdaptiveFee or price observations:
+    // - For trades on pool using AdaptiveFee or price observations, pass oracle as writable accounts in the remaining accounts.
     // - If you want to avoid using the remaining accounts, you can pass oracle as writable accounts directly.
 
     // remaining accounts
     // - [mut] oracle
+    // - reward extension accounts (SolveRewardExtension and TickArrayRewardExtension of crossed ticks)
 }
 
-pub fn handler(
-    ctx: Context<Swap>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit: u128,
@@ -79,7 +86,9 @@ pub fn handler(
     if !oracle_accessor.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve, PausableOperation::Swap)?;
     let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
 
     let swap_update = swap(
         solve,
@@ -88,6 +97,7 @@ pub fn handler(
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
+        &reward_schedules,
         timestamp,
         &adaptive_fee_info,
     )?;
@@ -104,7 +114,28 @@ pub fn handler(
         return Err(ErrorCode::AmountInAboveMaximum.into());
     }
 
+    // The reward extension accrues rewards with the liquidity before the swap
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load_from_remaining_accounts(solve, ctx.remaining_accounts)?
+    {
+        reward_extension.update_for_swap(
+            solve,
+            &swap_update.crossed_tick_indexes,
+            a_to_b,
+            timestamp,
+        )?;
+    }
+
     oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve, &swap_update.next_reward_infos)?;
+    oracle_accessor.record_observation(timestamp, solve.tick_current_index, solve.liquidity)?;
+    fill_limit_orders(
+        &oracle_accessor,
+        solve,
+        &mut swap_tick_sequence,
+        &swap_update,
+        a_to_b,
+    )?;
 
     let pre_sqrt_price = solve.sqrt_price;
     let (input_amount, output_amount) = if a_to_b {
@@ -138,6 +169,7 @@ pub fn handler(
         output_transfer_fee: 0,
         lp_fee,
         protocol_fee,
+        referral_fee: 0,
     });
 
     Ok(())
@@ -51,6 +51,7 @@ pub struct TransferLockedPosition<'info> {
     pub token_2022_program: Program<'info, Token2022>,
 }
 
+// Not paused: the transfer does not touch the pool, same as the transfer of an unlocked position token.
 pub fn handler(ctx: Context<TransferLockedPosition>) -> Result<()> {
     // Only allow the owner of the position to transfer this and not the delegate.
     // * Once a position is locked the delegate cannot be changed
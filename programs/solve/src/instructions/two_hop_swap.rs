@@ -4,9 +4,15 @@ use anchor_spl::token::{self, Token, TokenAccount};
 use crate::{
     errors::ErrorCode,
     events::*,
-    manager::swap_manager::*,
-    state::{OracleAccessor, Solve},
-    util::{to_timestamp_u64, update_and_swap_solve, SparseSwapTickSequenceBuilder},
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+        swap_manager::*,
+    },
+    state::{OracleAccessor, PausableOperation, Solve, SolveTail},
+    util::{
+        to_timestamp_u64, update_and_swap_solve, verify_pool_not_paused,
+        SparseSwapTickSequenceBuilder,
+    },
 };
 
 #[derive(Accounts)]
@@ -73,18 +79,19 @@ pub struct TwoHopSwap<'info> {
     #[account(seeds = [b"oracle", solve_two.key().as_ref()], bump)]
     /// CHECK: Oracle is currently unused and will be enabled on subsequent updates
     pub oracle_two: UncheckedAccount<'info>,
-    // Special notes to support pools with AdaptiveFee:
-    // - For trades on pools using AdaptiveFee, pass oracle_one and oracle_two as writable accounts in the remaining accounts.
+    // Special notes to support pools with AdaptiveFee or price observations:
+    // - For trades on pools using AdaptiveFee or price observations, pass oracle_one and oracle_two as writable accounts in the remaining accounts.
     // - If you want to avoid using the remaining accounts, you can pass oracle_one and oracle_two as writable accounts directly.
 
     // remaining accounts
     // - [mut] oracle_one
     // - [mut] oracle_two
+    // - reward extension accounts of solve_one and solve_two, each group starting with its SolveRewardExtension
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn handler(
-    ctx: Context<TwoHopSwap>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, TwoHopSwap<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     amount_specified_is_input: bool,
@@ -147,14 +154,18 @@ pub fn handler(
     if !oracle_accessor_one.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve_one, PausableOperation::Swap)?;
     let adaptive_fee_info_one = oracle_accessor_one.get_adaptive_fee_info()?;
+    let reward_schedules_one = SolveTail::load(&solve_one.to_account_info())?.reward_schedules;
 
     let oracle_accessor_two =
         OracleAccessor::new(solve_two, ctx.accounts.oracle_two.to_account_info())?;
     if !oracle_accessor_two.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve_two, PausableOperation::Swap)?;
     let adaptive_fee_info_two = oracle_accessor_two.get_adaptive_fee_info()?;
+    let reward_schedules_two = SolveTail::load(&solve_two.to_account_info())?.reward_schedules;
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
@@ -169,6 +180,7 @@ pub fn handler(
             sqrt_price_limit_one,
             amount_specified_is_input, // true
             a_to_b_one,
+            &reward_schedules_one,
             timestamp,
             &adaptive_fee_info_one,
         )?;
@@ -187,6 +199,7 @@ pub fn handler(
             sqrt_price_limit_two,
             amount_specified_is_input, // true
             a_to_b_two,
+            &reward_schedules_two,
             timestamp,
             &adaptive_fee_info_two,
         )?;
@@ -202,6 +215,7 @@ pub fn handler(
             sqrt_price_limit_two,
             amount_specified_is_input, // false
             a_to_b_two,
+            &reward_schedules_two,
             timestamp,
             &adaptive_fee_info_two,
         )?;
@@ -220,6 +234,7 @@ pub fn handler(
             sqrt_price_limit_one,
             amount_specified_is_input, // false
             a_to_b_one,
+            &reward_schedules_one,
             timestamp,
             &adaptive_fee_info_one,
         )?;
@@ -267,9 +282,57 @@ pub fn handler(
         }
     }
 
+    // The reward extensions accrue rewards with the liquidity before the swaps
+    if let Some(mut reward_extension_one) =
+        RewardExtensionAccounts::load_from_remaining_accounts(solve_one, ctx.remaining_accounts)?
+    {
+        reward_extension_one.update_for_swap(
+            solve_one,
+            &swap_update_one.crossed_tick_indexes,
+            a_to_b_one,
+            timestamp,
+        )?;
+    }
+    if let Some(mut reward_extension_two) =
+        RewardExtensionAccounts::load_from_remaining_accounts(solve_two, ctx.remaining_accounts)?
+    {
+        reward_extension_two.update_for_swap(
+            solve_two,
+            &swap_update_two.crossed_tick_indexes,
+            a_to_b_two,
+            timestamp,
+        )?;
+    }
+
     oracle_accessor_one.update_adaptive_fee_variables(&swap_update_one.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve_one, &swap_update_one.next_reward_infos)?;
+    oracle_accessor_one.record_observation(
+        timestamp,
+        solve_one.tick_current_index,
+        solve_one.liquidity,
+    )?;
+    fill_limit_orders(
+        &oracle_accessor_one,
+        solve_one,
+        &mut swap_tick_sequence_one,
+        &swap_update_one,
+        a_to_b_one,
+    )?;
 
     oracle_accessor_two.update_adaptive_fee_variables(&swap_update_two.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve_two, &swap_update_two.next_reward_infos)?;
+    oracle_accessor_two.record_observation(
+        timestamp,
+        solve_two.tick_current_index,
+        solve_two.liquidity,
+    )?;
+    fill_limit_orders(
+        &oracle_accessor_two,
+        solve_two,
+        &mut swap_tick_sequence_two,
+        &swap_update_two,
+        a_to_b_two,
+    )?;
 
     let pre_sqrt_price_one = solve_one.sqrt_price;
     let (input_amount_one, output_amount_one) = if a_to_b_one {
@@ -326,6 +389,7 @@ pub fn handler(
         output_transfer_fee: 0,
         lp_fee: lp_fee_one,
         protocol_fee: protocol_fee_one,
+        referral_fee: 0,
     });
 
     emit!(Traded {
@@ -339,6 +403,7 @@ pub fn handler(
         output_transfer_fee: 0,
         lp_fee: lp_fee_two,
         protocol_fee: protocol_fee_two,
+        referral_fee: 0,
     });
 
     Ok(())
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::verify_position_not_boosted;
+use crate::state::*;
+use crate::util::{
+    is_locked_position, to_timestamp_u64, unfreeze_user_position_token_2022, validate_owner,
+};
+
+#[derive(Accounts)]
+pub struct UnlockPosition<'info> {
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+        bump,
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(address = position.position_mint, owner = token_2022_program.key())]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.mint == position.position_mint,
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut,
+        seeds = [b"lock_config".as_ref(), position.key().as_ref()],
+        bump,
+        has_one = position,
+        close = receiver,
+    )]
+    pub lock_config: Box<Account<'info, LockConfig>>,
+
+    #[account(address = token_2022::ID)]
+    pub token_2022_program: Program<'info, Token2022>,
+    // remaining accounts
+    // - PositionRewardExtension, if the position has one
+}
+
+pub fn handler(ctx: Context<UnlockPosition>) -> Result<()> {
+    // Only the owner of the position can unlock it, the delegate is not allowed.
+    validate_owner(
+        &ctx.accounts.position_token_account.owner,
+        &ctx.accounts.position_authority.to_account_info(),
+    )?;
+
+    if !is_locked_position(&ctx.accounts.position_token_account) {
+        unreachable!("Position has to be locked for this instruction");
+    }
+
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    if !ctx.accounts.lock_config.is_unlockable(timestamp) {
+        return Err(ErrorCode::PositionNotUnlockable.into());
+    }
+
+    verify_position_not_boosted(
+        &ctx.accounts.position.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
+
+    unfreeze_user_position_token_2022(
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_2022_program,
+        &ctx.accounts.position,
+        &[
+            b"position".as_ref(),
+            ctx.accounts.position_mint.key().as_ref(),
+            &[ctx.bumps.position],
+        ],
+    )?;
+
+    Ok(())
+}
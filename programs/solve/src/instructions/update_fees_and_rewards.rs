@@ -3,7 +3,9 @@ use std::ops::Deref;
 use anchor_lang::prelude::*;
 
 use crate::{
-    manager::liquidity_manager::calculate_fee_and_reward_growths, state::*, util::to_timestamp_u64,
+    manager::liquidity_manager::calculate_fee_and_reward_growths,
+    state::*,
+    util::{to_timestamp_u64, verify_pool_not_locked},
 };
 
 #[derive(Accounts)]
@@ -21,6 +23,10 @@ pub struct UpdateFeesAndRewards<'info> {
 }
 
 pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
+    verify_pool_not_locked(&ctx.accounts.solve)?;
+
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
     let solve = &mut ctx.accounts.solve;
     let position = &mut ctx.accounts.position;
     let clock = Clock::get()?;
@@ -34,9 +40,11 @@ pub fn handler(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
         position,
         lower_tick_array.deref(),
         upper_tick_array.deref(),
+        &reward_schedules,
         timestamp,
     )?;
 
+    SolveTail::track_reward_emissions(solve, &reward_infos)?;
     solve.update_rewards(reward_infos, timestamp);
     position.update(&position_update);
 
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptConfigExtensionAuthority<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_config_extension_authority)]
+    pub pending_config_extension_authority: Signer<'info>,
+}
+
+/// Accept the nominated config extension authority. Only the pending config extension authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptConfigExtensionAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .accept_config_extension_authority();
+    Ok(())
+}
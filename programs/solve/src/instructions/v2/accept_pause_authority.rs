@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptPauseAuthority<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_pause_authority)]
+    pub pending_pause_authority: Signer<'info>,
+}
+
+/// Accept the nominated pause authority. Only the pending pause authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptPauseAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .accept_pause_authority();
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct AcceptTokenBadgeAuthority<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pending_token_badge_authority)]
+    pub pending_token_badge_authority: Signer<'info>,
+}
+
+/// Accept the nominated token badge authority. Only the pending token badge authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<AcceptTokenBadgeAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .accept_token_badge_authority();
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(address = solves_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = solves_config, close = receiver)]
+    pub queued_change: Account<'info, QueuedChange>,
+}
+
+pub fn handler(ctx: Context<CancelParameterChange>) -> Result<()> {
+    emit!(ParameterChangeCancelled {
+        solves_config: ctx.accounts.solves_config.key(),
+        target: ctx.accounts.queued_change.target,
+        parameter_change: ctx.accounts.queued_change.parameter_change,
+        value: ctx.accounts.queued_change.value,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::limit_order_manager::calculate_filled_limit_order_amounts;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::tick_array_manager::{
+    update_tick_array_accounts, TickArrayRentTransfer, TickArraySizeUpdate, TickArrayUpdate,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, verify_pool_not_locked,
+    AccountsType, RemainingAccountsInfo,
+};
+use crate::util::{to_timestamp_u64, v2::transfer_from_vault_to_owner_v2};
+
+#[derive(Accounts)]
+pub struct CloseLimitOrder<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: safe, for receiving rent only
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(mut, has_one = solve, has_one = owner, close = receiver)]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+/*
+  Closes a limit order.
+  A filled order pays out the bought token, an order not filled yet is cancelled and pays out its liquidity.
+  Fees earned until the order was filled (or cancelled) are paid out together.
+  The deposit held by the LimitOrder account is refunded to the receiver with its rent.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseLimitOrder<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_pool_not_locked(&ctx.accounts.solve)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let limit_order_key = ctx.accounts.limit_order.key();
+    let oracle_account_info = ctx.accounts.oracle.to_account_info();
+    let entry = {
+        let data = oracle_account_info.try_borrow_data()?;
+        let (oracle, limit_orders) = load_oracle_with_limit_orders(&data)?;
+        limit_orders[oracle.find_limit_order(limit_orders, limit_order_key)?]
+    };
+
+    let tick_spacing = ctx.accounts.solve.tick_spacing;
+    let tick_lower_index = entry.tick_lower_index;
+    let tick_upper_index = entry.tick_upper_index(tick_spacing);
+    let filled = entry.is_filled();
+
+    let (delta_a, delta_b, fee_owed_a, fee_owed_b, reward_infos) = if filled {
+        // The liquidity has been removed from the ticks by the swap which filled the order.
+        let (delta_a, delta_b) = calculate_filled_limit_order_amounts(&entry, tick_spacing)?;
+
+        let mut tick_arrays = TickArraysMut::load(
+            &ctx.accounts.tick_array,
+            &ctx.accounts.tick_array,
+            &ctx.accounts.solve.key(),
+        )?;
+        let (tick_array, _) = tick_arrays.deref_mut();
+        let tick_array_lower_update =
+            clear_filled_limit_order_tick(tick_array, tick_lower_index, tick_spacing)?;
+        let tick_array_upper_update =
+            clear_filled_limit_order_tick(tick_array, tick_upper_index, tick_spacing)?;
+
+        // Need to drop the tick arrays so we can potentially resize them
+        drop(tick_arrays);
+
+        update_tick_array_accounts(
+            &ctx.accounts.limit_order,
+            ctx.accounts.tick_array.to_account_info(),
+            ctx.accounts.tick_array.to_account_info(),
+            &tick_array_lower_update,
+            &tick_array_upper_update,
+        )?;
+
+        (
+            delta_a,
+            delta_b,
+            entry.fee_owed_a,
+            entry.fee_owed_b,
+            entry.reward_infos(),
+        )
+    } else {
+        // An order crossed only by swaps that could not fill it is settled here at the current price,
+        // so that it holds the bought token in the same way as a filled order.
+        let liquidity_delta = convert_to_liquidity_delta(entry.liquidity, false)?;
+        let mut position = Position {
+            solve: ctx.accounts.solve.key(),
+            liquidity: entry.liquidity,
+            tick_lower_index,
+            tick_upper_index,
+            fee_growth_checkpoint_a: entry.fee_growth_checkpoint_a,
+            fee_owed_a: entry.fee_owed_a,
+            fee_growth_checkpoint_b: entry.fee_growth_checkpoint_b,
+            fee_owed_b: entry.fee_owed_b,
+            reward_infos: entry.reward_infos(),
+            ..Default::default()
+        };
+
+        let mut tick_arrays = TickArraysMut::load(
+            &ctx.accounts.tick_array,
+            &ctx.accounts.tick_array,
+            &ctx.accounts.solve.key(),
+        )?;
+
+        let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+        let update = calculate_modify_liquidity(
+            &ctx.accounts.solve,
+            &position,
+            lower_tick_array,
+            upper_tick_array,
+            liquidity_delta,
+            &reward_schedules,
+            timestamp,
+        )?;
+
+        SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+        let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+        sync_modify_liquidity_values(
+            &mut ctx.accounts.solve,
+            &mut position,
+            lower_tick_array_mut,
+            upper_tick_array_mut,
+            &update,
+            timestamp,
+        )?;
+
+        // Need to drop the tick arrays so we can potentially resize them
+        drop(tick_arrays);
+
+        update_tick_array_accounts(
+            &ctx.accounts.limit_order,
+            ctx.accounts.tick_array.to_account_info(),
+            ctx.accounts.tick_array.to_account_info(),
+            &update.tick_array_lower_update,
+            &update.tick_array_upper_update,
+        )?;
+
+        let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+            ctx.accounts.solve.tick_current_index,
+            ctx.accounts.solve.sqrt_price,
+            &position,
+            liquidity_delta,
+        )?;
+
+        (
+            delta_a,
+            delta_b,
+            position.fee_owed_a,
+            position.fee_owed_b,
+            position.reward_infos,
+        )
+    };
+
+    // The rewards emitted to the liquidity of the order are released, so that the reward authority
+    // can withdraw them with withdraw_unallocated_reward
+    for (index, reward_info) in reward_infos.iter().enumerate() {
+        SolveTail::track_reward_collection(&ctx.accounts.solve, index, reward_info.amount_owed)?;
+    }
+
+    {
+        let mut data = oracle_account_info.try_borrow_mut_data()?;
+        let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data)?;
+        let index = oracle.find_limit_order(limit_orders, limit_order_key)?;
+        oracle.remove_limit_order(limit_orders, index);
+    }
+
+    let amount_a = delta_a
+        .checked_add(fee_owed_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let amount_b = delta_b
+        .checked_add(fee_owed_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+    let transfer_fee_excluded_amount_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, amount_a)?;
+    let transfer_fee_excluded_amount_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, amount_b)?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        amount_a,
+        transfer_memo::TRANSFER_MEMO_CLOSE_LIMIT_ORDER.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        amount_b,
+        transfer_memo::TRANSFER_MEMO_CLOSE_LIMIT_ORDER.as_bytes(),
+    )?;
+
+    emit!(LimitOrderClosed {
+        solve: ctx.accounts.solve.key(),
+        limit_order: limit_order_key,
+        filled,
+        token_a_amount: amount_a,
+        token_b_amount: amount_b,
+        token_a_transfer_fee: transfer_fee_excluded_amount_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_excluded_amount_b.transfer_fee,
+    });
+
+    Ok(())
+}
+
+// A swap keeps the ticks of a filled order initialized even if no liquidity remains (see next_tick_fill_limit_order_update).
+// Such a tick is cleared here unless another position has been opened on it in the meantime.
+fn clear_filled_limit_order_tick(
+    tick_array: &mut dyn TickArrayType,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<TickArrayUpdate> {
+    let is_variable_size = tick_array.is_variable_size();
+    let tick = tick_array.get_tick(tick_index, tick_spacing)?;
+
+    let mut size_update = TickArraySizeUpdate::None;
+    if tick.initialized && tick.liquidity_gross == 0 {
+        tick_array.update_tick(tick_index, tick_spacing, &TickUpdate::default())?;
+        if is_variable_size {
+            size_update = TickArraySizeUpdate::Decrease;
+        }
+    }
+
+    // The rent for the tick is returned to the limit order in the same way as positions
+    let transfer_rent = if is_variable_size {
+        TickArrayRentTransfer::TransferToPosition
+    } else {
+        TickArrayRentTransfer::None
+    };
+
+    Ok(TickArrayUpdate {
+        transfer_rent,
+        size_update,
+    })
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use super::collect_reward::calculate_collect_reward;
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface},
+};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct CollectExtensionReward<'info> {
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(has_one = solve)]
+    pub solve_reward_extension: Box<Account<'info, SolveRewardExtension>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(has_one = solve)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, has_one = position)]
+    pub position_reward_extension: Box<Account<'info, PositionRewardExtension>>,
+
+    #[account(mut,
+        constraint = reward_owner_account.mint == solve_reward_extension.reward_infos[reward_index as usize].mint
+    )]
+    pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = solve_reward_extension.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of reward_mint
+}
+
+/// Collects all harvestable tokens for a specified reward of the reward extension.
+///
+/// Harvestable tokens are tracked by `update_extension_rewards` and the instructions modifying
+/// the liquidity of the position. As for `collect_reward_v2`, if the reward vault does not have
+/// enough tokens, the unharvested amount remains tracked.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectExtensionReward<'info>>,
+    reward_index: u8,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookReward],
+    )?;
+
+    let index = reward_index as usize;
+
+    let position_reward_extension = &mut ctx.accounts.position_reward_extension;
+    let (transfer_amount, updated_amount_owed) = calculate_collect_reward(
+        position_reward_extension.reward_infos[index],
+        ctx.accounts.reward_vault.amount,
+    );
+
+    position_reward_extension.update_reward_owed(index, updated_amount_owed);
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_owner_account,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        transfer_amount,
+        transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+    )
+}
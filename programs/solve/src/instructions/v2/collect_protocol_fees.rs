@@ -1,4 +1,7 @@
-use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::util::{
+    parse_remaining_accounts, verify_protocol_fee_recipients_not_set, AccountsType,
+    RemainingAccountsInfo,
+};
 use crate::{constants::transfer_memo, state::*, util::v2::transfer_from_vault_to_owner_v2};
 use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
@@ -39,6 +42,7 @@ pub struct CollectProtocolFeesV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - solves_config_extension (optional)
 }
 
 pub fn handler<'info>(
@@ -51,7 +55,19 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::ConfigExtension,
+        ],
+    )?;
+
+    verify_protocol_fee_recipients_not_set(
+        &ctx.accounts.solves_config.key(),
+        remaining_accounts
+            .config_extension
+            .as_ref()
+            .and_then(|accounts| accounts.first()),
     )?;
 
     transfer_from_vault_to_owner_v2(
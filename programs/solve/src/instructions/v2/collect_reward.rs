@@ -12,6 +12,7 @@ use crate::{
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
 pub struct CollectRewardV2<'info> {
+    #[account(mut)]
     pub solve: Box<Account<'info, Solve>>,
 
     pub position_authority: Signer<'info>,
@@ -82,6 +83,8 @@ pub fn handler<'info>(
 
     position.update_reward_owed(index, updated_amount_owed);
 
+    SolveTail::track_reward_collection(&ctx.accounts.solve, index, transfer_amount)?;
+
     transfer_from_vault_to_owner_v2(
         &ctx.accounts.solve,
         &ctx.accounts.reward_mint,
@@ -96,7 +99,10 @@ pub fn handler<'info>(
 }
 
 // TODO: refactor (remove (dup))
-fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
+pub fn calculate_collect_reward(
+    position_reward: PositionRewardInfo,
+    vault_amount: u64,
+) -> (u64, u64) {
     let amount_owed = position_reward.amount_owed;
     let (transfer_amount, updated_amount_owed) = if amount_owed > vault_amount {
         (vault_amount, amount_owed - vault_amount)
@@ -106,4 +112,3 @@ fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u
 
     (transfer_amount, updated_amount_owed)
 }
-
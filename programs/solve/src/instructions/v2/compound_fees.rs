@@ -0,0 +1,491 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::{
+    calculate_fee_and_reward_growths, calculate_liquidity_from_token_amounts,
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, to_timestamp_u64, verify_pool_and_config_not_paused,
+    verify_position_authority_interface, AccountsType, RemainingAccountsInfo,
+    SparseSwapTickSequenceBuilder,
+};
+
+use super::zap_in::swap_deposited_amount;
+
+#[derive(Accounts)]
+pub struct CompoundFeesV2<'info> {
+    // position authority or the keeper authorized by position_keeper
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut, has_one = solve)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"position_keeper".as_ref(), position.key().as_ref()],
+        bump,
+    )]
+    pub position_keeper: Option<Account<'info, PositionKeeper>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+    // remaining accounts
+    // - supplemental TickArray accounts for the swap
+    // - reward extension accounts (SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension
+    //   of the position ticks and the crossed ticks)
+}
+
+/*
+  Converts the fees owed to a position into additional liquidity in the same range.
+  The excess side is optionally swapped through the same pool, the swap amount is quoted by the caller.
+  The fees never leave the vaults, and the dust that cannot be added as liquidity is kept as fees owed.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompoundFeesV2<'info>>,
+    swap_amount: u64,
+    a_to_b: bool,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    liquidity_min: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let keeper = ctx
+        .accounts
+        .position_keeper
+        .as_ref()
+        .filter(|position_keeper| {
+            position_keeper.is_authorized(
+                ctx.accounts.authority.key,
+                &ctx.accounts.position_token_account.owner,
+            )
+        });
+    // A keeper can only compound within the price bounds set by the position owner
+    let sqrt_price_limit = match keeper {
+        Some(position_keeper) => keeper_sqrt_price_limit(
+            position_keeper,
+            ctx.accounts.solve.sqrt_price,
+            sqrt_price_limit,
+            a_to_b,
+        )?,
+        None => {
+            verify_position_authority_interface(
+                &ctx.accounts.position_token_account,
+                &ctx.accounts.authority,
+            )?;
+            sqrt_price_limit
+        }
+    };
+
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::IncreaseLiquidity,
+    )?;
+    if swap_amount > 0 {
+        verify_pool_and_config_not_paused(
+            &ctx.accounts.solve,
+            &ctx.accounts.solves_config_extension,
+            PausableOperation::Swap,
+        )?;
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::SupplementalTickArrays,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+    let mut reward_extension =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?;
+
+    // Update the fees owed (calculation for positions with zero liquidity results in an error)
+    if ctx.accounts.position.liquidity > 0 {
+        let tick_arrays = TickArraysMut::load(
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            &ctx.accounts.solve.key(),
+        )?;
+        let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            lower_tick_array,
+            upper_tick_array,
+            &reward_schedules,
+            timestamp,
+        )?;
+        drop(tick_arrays);
+
+        SolveTail::track_reward_emissions(&ctx.accounts.solve, &reward_infos)?;
+        ctx.accounts.solve.update_rewards(reward_infos, timestamp);
+        ctx.accounts.position.update(&position_update);
+    }
+
+    let fee_a = ctx.accounts.position.fee_owed_a;
+    let fee_b = ctx.accounts.position.fee_owed_b;
+    let mut amount_a = fee_a;
+    let mut amount_b = fee_b;
+
+    // The fees are already in the vaults, so the swap only updates the pool state
+    if swap_amount > 0 {
+        let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+            vec![
+                ctx.accounts.tick_array_lower.to_account_info(),
+                ctx.accounts.tick_array_upper.to_account_info(),
+            ],
+            remaining_accounts.supplemental_tick_arrays,
+        );
+
+        let (swapped_amount, other_amount) = if a_to_b {
+            (amount_a, amount_b)
+        } else {
+            (amount_b, amount_a)
+        };
+        let (swapped_amount_a, swapped_amount_b) = swap_deposited_amount(
+            &mut ctx.accounts.solve,
+            ctx.accounts.oracle.to_account_info(),
+            &swap_tick_sequence_builder,
+            reward_extension.as_mut(),
+            swapped_amount,
+            swap_amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            a_to_b,
+            timestamp,
+        )?;
+
+        if a_to_b {
+            amount_a = swapped_amount_a;
+            amount_b = swapped_amount_b
+                .checked_add(other_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+        } else {
+            amount_a = swapped_amount_a
+                .checked_add(other_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+            amount_b = swapped_amount_b;
+        }
+    }
+
+    let liquidity_amount = calculate_liquidity_from_token_amounts(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        amount_a,
+        amount_b,
+    )?;
+    if liquidity_amount < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimum.into());
+    }
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    // The reward extension is updated with the solve, position and ticks before the modification
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_for_modify_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    let (deposited_a, deposited_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    // The compounded fees are replaced by the dust that could not be added as liquidity.
+    // Fees earned by the position on the swap above are kept as fees owed.
+    let dust_a = amount_a
+        .checked_sub(deposited_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let dust_b = amount_b
+        .checked_sub(deposited_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let fee_owed_a = (ctx.accounts.position.fee_owed_a - fee_a)
+        .checked_add(dust_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let fee_owed_b = (ctx.accounts.position.fee_owed_b - fee_b)
+        .checked_add(dust_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    ctx.accounts.position.fee_owed_a = fee_owed_a;
+    ctx.accounts.position.fee_owed_b = fee_owed_b;
+
+    emit!(FeesCompounded {
+        solve: ctx.accounts.solve.key(),
+        position: ctx.accounts.position.key(),
+        authority: ctx.accounts.authority.key(),
+        liquidity: liquidity_amount,
+        fee_a_amount: fee_a,
+        fee_b_amount: fee_b,
+        fee_owed_a,
+        fee_owed_b,
+    });
+
+    Ok(())
+}
+
+// Verifies that the pool price is within the price bounds of the keeper, and bounds the swap
+// so that it cannot move the price out of them.
+fn keeper_sqrt_price_limit(
+    position_keeper: &PositionKeeper,
+    sqrt_price: u128,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+) -> Result<u128> {
+    position_keeper.verify_sqrt_price(sqrt_price)?;
+    Ok(position_keeper.bound_sqrt_price_limit(sqrt_price_limit, a_to_b))
+}
+
+#[cfg(test)]
+mod compound_fees_tests {
+    use super::*;
+    use crate::math::{sqrt_price_from_tick_index, NO_EXPLICIT_SQRT_PRICE_LIMIT};
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+    const TICK_CURRENT_INDEX: i32 = 2_048;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>, owner: Pubkey) -> Self {
+            Self {
+                key,
+                owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn position_keeper(lower_tick_index: i32, upper_tick_index: i32) -> PositionKeeper {
+        let mut position_keeper = PositionKeeper {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            keeper: Pubkey::default(),
+            sqrt_price_lower_bound: 0,
+            sqrt_price_upper_bound: 0,
+        };
+        position_keeper
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                sqrt_price_from_tick_index(lower_tick_index),
+                sqrt_price_from_tick_index(upper_tick_index),
+            )
+            .unwrap();
+        position_keeper
+    }
+
+    // Swaps the fees of token A as a keeper would, with the limit bounded by position_keeper
+    fn keeper_swap(
+        position_keeper: &PositionKeeper,
+        fee_amount: u64,
+        swap_amount: u64,
+        sqrt_price_limit: u128,
+    ) -> Result<(u64, u64, Solve)> {
+        let solve_key = Pubkey::new_unique();
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            fee_rate: 3_000,
+            liquidity: 1_000_000_000_000,
+            sqrt_price: sqrt_price_from_tick_index(TICK_CURRENT_INDEX),
+            tick_current_index: TICK_CURRENT_INDEX,
+            ..Default::default()
+        };
+        let mut solve_data = vec![0u8; Solve::LEN];
+        solve.try_serialize(&mut solve_data.as_mut_slice()).unwrap();
+
+        // A fixed tick array starting at tick 0 without initialized ticks
+        let mut tick_array_data = vec![0u8; FixedTickArray::LEN];
+        tick_array_data[0..8].copy_from_slice(&FixedTickArray::discriminator());
+        tick_array_data[9956..9988].copy_from_slice(&solve_key.to_bytes());
+
+        let mut solve_account = TestAccount::new(solve_key, solve_data, crate::ID);
+        let mut oracle_account = TestAccount::new(Pubkey::new_unique(), vec![], System::id());
+        let mut tick_array_account =
+            TestAccount::new(Pubkey::new_unique(), tick_array_data, crate::ID);
+
+        let solve_info = solve_account.account_info(true);
+        let mut solve: Account<Solve> = Account::try_from(&solve_info)?;
+        let sqrt_price_limit =
+            keeper_sqrt_price_limit(position_keeper, solve.sqrt_price, sqrt_price_limit, true)?;
+        let swap_tick_sequence_builder =
+            SparseSwapTickSequenceBuilder::new(vec![tick_array_account.account_info(true)], None);
+        let (amount_a, amount_b) = swap_deposited_amount(
+            &mut solve,
+            oracle_account.account_info(false),
+            &swap_tick_sequence_builder,
+            None,
+            fee_amount,
+            swap_amount,
+            0,
+            sqrt_price_limit,
+            true,
+            1_000,
+        )?;
+        Ok((amount_a, amount_b, solve.into_inner()))
+    }
+
+    #[test]
+    fn test_keeper_swap_within_bounds() {
+        let position_keeper = position_keeper(TICK_CURRENT_INDEX - 100, TICK_CURRENT_INDEX + 100);
+        let (amount_a, amount_b, solve) =
+            keeper_swap(&position_keeper, 1_000, 500, NO_EXPLICIT_SQRT_PRICE_LIMIT).unwrap();
+        assert_eq!(amount_a, 500);
+        assert!(amount_b > 0);
+        assert!(solve.sqrt_price < sqrt_price_from_tick_index(TICK_CURRENT_INDEX));
+        assert!(solve.sqrt_price > position_keeper.sqrt_price_lower_bound);
+    }
+
+    #[test]
+    fn test_keeper_swap_stops_at_lower_bound() {
+        // The swap cannot move the price out of the bounds, the rest of the fees is kept
+        let position_keeper = position_keeper(TICK_CURRENT_INDEX - 10, TICK_CURRENT_INDEX + 10);
+        let (amount_a, amount_b, solve) = keeper_swap(
+            &position_keeper,
+            u64::MAX >> 8,
+            u64::MAX >> 9,
+            NO_EXPLICIT_SQRT_PRICE_LIMIT,
+        )
+        .unwrap();
+        assert_eq!(solve.sqrt_price, position_keeper.sqrt_price_lower_bound);
+        assert!(amount_a > (u64::MAX >> 9));
+        assert!(amount_b > 0);
+    }
+
+    #[test]
+    fn test_keeper_swap_tighter_limit() {
+        // A limit tighter than the bounds is kept
+        let position_keeper = position_keeper(TICK_CURRENT_INDEX - 100, TICK_CURRENT_INDEX + 100);
+        let sqrt_price_limit = sqrt_price_from_tick_index(TICK_CURRENT_INDEX - 10);
+        let (_, _, solve) = keeper_swap(
+            &position_keeper,
+            u64::MAX >> 8,
+            u64::MAX >> 9,
+            sqrt_price_limit,
+        )
+        .unwrap();
+        assert_eq!(solve.sqrt_price, sqrt_price_limit);
+    }
+
+    #[test]
+    fn test_keeper_price_out_of_bounds() {
+        for (lower_tick_index, upper_tick_index) in [
+            (TICK_CURRENT_INDEX + 1, TICK_CURRENT_INDEX + 100),
+            (TICK_CURRENT_INDEX - 100, TICK_CURRENT_INDEX - 1),
+        ] {
+            let position_keeper = position_keeper(lower_tick_index, upper_tick_index);
+            let result = keeper_swap(&position_keeper, 1_000, 0, NO_EXPLICIT_SQRT_PRICE_LIMIT);
+            assert_eq!(
+                result
+                    .map(|(amount_a, amount_b, _)| (amount_a, amount_b))
+                    .unwrap_err(),
+                ErrorCode::PriceOutOfPositionKeeperBounds.into()
+            );
+        }
+    }
+}
@@ -6,15 +6,17 @@ use crate::events::*;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
 use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
-use crate::state::TickArraysMut;
+use crate::state::{SolveTail, TickArraysMut};
 use crate::util::{
     calculate_transfer_fee_excluded_amount, is_locked_position, parse_remaining_accounts,
     AccountsType, RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_position_authority_interface,
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_locked_position_decrease,
+    verify_pool_not_locked, verify_position_authority_interface,
 };
 
 use super::increase_liquidity::ModifyLiquidityV2;
@@ -34,9 +36,7 @@ pub fn handler<'info>(
         &ctx.accounts.position_authority,
     )?;
 
-    if is_locked_position(&ctx.accounts.position_token_account) {
-        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
-    }
+    verify_pool_not_locked(&ctx.accounts.solve)?;
 
     let clock = Clock::get()?;
 
@@ -48,11 +48,40 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::LockConfig,
+            AccountsType::RewardExtension,
+        ],
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    if is_locked_position(&ctx.accounts.position_token_account) {
+        verify_locked_position_decrease(
+            &remaining_accounts.lock_config,
+            &ctx.accounts.position,
+            liquidity_amount,
+            timestamp,
+        )?;
+    }
+
+    // The reward extension is updated with the solve, position and ticks before the modification
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?
+    {
+        reward_extension.update_for_decrease_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
 
     let mut tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
@@ -67,9 +96,11 @@ pub fn handler<'info>(
         lower_tick_array,
         upper_tick_array,
         liquidity_delta,
+        &reward_schedules,
         timestamp,
     )?;
 
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
     let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
     sync_modify_liquidity_values(
         &mut ctx.accounts.solve,
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::util::{
+    get_remaining_accounts_slice, parse_remaining_accounts, AccountsType, RemainingAccountsInfo,
+};
+use crate::{constants::transfer_memo, state::*, util::v2::transfer_from_vault_to_owner_v2};
+
+#[derive(Accounts)]
+pub struct DistributeProtocolFeesV2<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(has_one = solves_config)]
+    pub solves_config_extension: Box<Account<'info, SolvesConfigExtension>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = solve.token_vault_a)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = solve.token_vault_b)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - token accounts of the recipients, for token_mint_a in the order of the distribution table
+    //   followed by those for token_mint_b
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+/*
+  Distributes the protocol fees accrued in a Solve to the recipients of the fee distribution table
+  of the SolvesConfigExtension. Anyone can invoke this instruction.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeProtocolFeesV2<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let solves_config_extension = &ctx.accounts.solves_config_extension;
+    let protocol_fee_recipients = solves_config_extension.get_protocol_fee_recipients();
+    if protocol_fee_recipients.is_empty() {
+        return Err(ErrorCode::ProtocolFeeRecipientsNotSet.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::ProtocolFeeDestinations,
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+        ],
+    )?;
+    let recipients_len = protocol_fee_recipients.len();
+    let token_destination_infos = match get_remaining_accounts_slice(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        AccountsType::ProtocolFeeDestinations,
+    ) {
+        Some(token_destination_infos) if token_destination_infos.len() == recipients_len * 2 => {
+            token_destination_infos
+        }
+        _ => return Err(ErrorCode::InvalidProtocolFeeDestination.into()),
+    };
+
+    let solve = &ctx.accounts.solve;
+    let protocol_fee_owed_a = solve.protocol_fee_owed_a;
+    let protocol_fee_owed_b = solve.protocol_fee_owed_b;
+    let amounts_a = solves_config_extension.split_protocol_fee(protocol_fee_owed_a);
+    let amounts_b = solves_config_extension.split_protocol_fee(protocol_fee_owed_b);
+
+    for (i, protocol_fee_recipient) in protocol_fee_recipients.iter().enumerate() {
+        let token_destination_a =
+            InterfaceAccount::<TokenAccount>::try_from(&token_destination_infos[i])?;
+        let token_destination_b = InterfaceAccount::<TokenAccount>::try_from(
+            &token_destination_infos[recipients_len + i],
+        )?;
+        if token_destination_a.mint != solve.token_mint_a
+            || token_destination_a.owner != protocol_fee_recipient.recipient
+            || token_destination_b.mint != solve.token_mint_b
+            || token_destination_b.owner != protocol_fee_recipient.recipient
+        {
+            return Err(ErrorCode::InvalidProtocolFeeDestination.into());
+        }
+
+        if amounts_a[i] > 0 {
+            transfer_from_vault_to_owner_v2(
+                solve,
+                &ctx.accounts.token_mint_a,
+                &ctx.accounts.token_vault_a,
+                &token_destination_a,
+                &ctx.accounts.token_program_a,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_a,
+                amounts_a[i],
+                transfer_memo::TRANSFER_MEMO_DISTRIBUTE_PROTOCOL_FEES.as_bytes(),
+            )?;
+        }
+
+        if amounts_b[i] > 0 {
+            transfer_from_vault_to_owner_v2(
+                solve,
+                &ctx.accounts.token_mint_b,
+                &ctx.accounts.token_vault_b,
+                &token_destination_b,
+                &ctx.accounts.token_program_b,
+                &ctx.accounts.memo_program,
+                &remaining_accounts.transfer_hook_b,
+                amounts_b[i],
+                transfer_memo::TRANSFER_MEMO_DISTRIBUTE_PROTOCOL_FEES.as_bytes(),
+            )?;
+        }
+    }
+
+    ctx.accounts.solve.reset_protocol_fees_owed();
+
+    emit!(ProtocolFeesDistributed {
+        solve: ctx.accounts.solve.key(),
+        amount_a: protocol_fee_owed_a,
+        amount_b: protocol_fee_owed_b,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(mut)]
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = solves_config, close = receiver)]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    // required for pool changes only
+    #[account(mut, has_one = solves_config)]
+    pub solve: Option<Box<Account<'info, Solve>>>,
+
+    // required for fee tier changes only
+    #[account(mut, has_one = solves_config)]
+    pub fee_tier: Option<Account<'info, FeeTier>>,
+}
+
+pub fn handler(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let queued_change = &ctx.accounts.queued_change;
+    queued_change.verify_executable(
+        timestamp,
+        ctx.accounts.solves_config_extension.admin_timelock_delay,
+    )?;
+
+    let target = queued_change.target;
+    let parameter_change = queued_change.parameter_change;
+    let value = queued_change.value;
+
+    if parameter_change.is_pool_change() {
+        let solve = match ctx.accounts.solve.as_mut() {
+            Some(solve) if solve.key() == target => solve,
+            _ => return Err(ErrorCode::InvalidQueuedChangeTarget.into()),
+        };
+        match parameter_change {
+            ParameterChangeLabel::FeeRate => solve.update_fee_rate(value as u16)?,
+            ParameterChangeLabel::ProtocolFeeRate => {
+                solve.update_protocol_fee_rate(value as u16)?
+            }
+            _ => unreachable!(),
+        }
+    } else if parameter_change.is_fee_tier_change() {
+        let fee_tier = match ctx.accounts.fee_tier.as_mut() {
+            Some(fee_tier) if fee_tier.key() == target => fee_tier,
+            _ => return Err(ErrorCode::InvalidQueuedChangeTarget.into()),
+        };
+        match parameter_change {
+            ParameterChangeLabel::DefaultFeeRate => {
+                fee_tier.update_default_fee_rate(value as u16)?
+            }
+            _ => unreachable!(),
+        }
+    } else {
+        match parameter_change {
+            ParameterChangeLabel::DefaultProtocolFeeRate => ctx
+                .accounts
+                .solves_config
+                .update_default_protocol_fee_rate(value as u16)?,
+            ParameterChangeLabel::AdminTimelockDelay => ctx
+                .accounts
+                .solves_config_extension
+                .update_admin_timelock_delay(value)?,
+            _ => unreachable!(),
+        }
+    }
+
+    emit!(ParameterChangeExecuted {
+        solves_config: ctx.accounts.solves_config.key(),
+        target,
+        parameter_change,
+        value,
+    });
+
+    Ok(())
+}
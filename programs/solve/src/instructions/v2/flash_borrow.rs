@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    manager::flash_loan_manager::calculate_flash_loan_update,
+    state::{PausableOperation, Solve},
+    util::{
+        transfer_from_vault_to_owner_v2, verify_flash_repay_follows,
+        verify_pool_and_config_not_paused,
+    },
+};
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    // must be the first account (see util::flash_loan)
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+
+    /// CHECK: checked by address constraint
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+    amount_a: u64,
+    amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Other,
+    )?;
+
+    if amount_a == 0 && amount_b == 0 {
+        return Err(ErrorCode::ZeroTradableAmount.into());
+    }
+
+    // The loan must be repaid later in this transaction
+    verify_flash_repay_follows(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.solve.key(),
+        amount_a,
+        amount_b,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    // The fees are accrued when the loan is taken, so that they are split by the liquidity in range
+    // at this point. Liquidity added before the loan is repaid does not earn a share of them.
+    let update = calculate_flash_loan_update(&ctx.accounts.solve, amount_a, amount_b)?;
+    ctx.accounts.solve.update_after_flash_loan(
+        update.next_fee_growth_global_a,
+        update.next_fee_growth_global_b,
+        update.protocol_fee_a,
+        update.protocol_fee_b,
+    );
+
+    let solve = &ctx.accounts.solve;
+    if amount_a > 0 {
+        transfer_from_vault_to_owner_v2(
+            solve,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            amount_a,
+            transfer_memo::TRANSFER_MEMO_FLASH_BORROW.as_bytes(),
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_from_vault_to_owner_v2(
+            solve,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            amount_b,
+            transfer_memo::TRANSFER_MEMO_FLASH_BORROW.as_bytes(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    errors::ErrorCode,
+    events::*,
+    manager::flash_loan_manager::calculate_flash_loan_update,
+    state::Solve,
+    util::{
+        calculate_transfer_fee_included_amount, transfer_from_owner_to_vault_v2,
+        verify_flash_borrow_precedes,
+    },
+};
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    // must be the first account (see util::flash_loan)
+    pub solve: Box<Account<'info, Solve>>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+
+    /// CHECK: checked by address constraint
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashRepay<'info>>,
+    amount_a: u64,
+    amount_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    if amount_a == 0 && amount_b == 0 {
+        return Err(ErrorCode::ZeroTradableAmount.into());
+    }
+
+    verify_flash_borrow_precedes(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.solve.key(),
+        amount_a,
+        amount_b,
+    )?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    // The fees have been accrued by flash_borrow, only the repayment is collected here
+    let update = calculate_flash_loan_update(&ctx.accounts.solve, amount_a, amount_b)?;
+
+    // The vaults must receive the borrowed amount plus the fee, so the transfer fee is paid on top
+    let repay_amount_a = amount_a
+        .checked_add(update.fee_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let repay_amount_b = amount_b
+        .checked_add(update.fee_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let transfer_fee_included_amount_a =
+        calculate_transfer_fee_included_amount(&ctx.accounts.token_mint_a, repay_amount_a)?;
+    let transfer_fee_included_amount_b =
+        calculate_transfer_fee_included_amount(&ctx.accounts.token_mint_b, repay_amount_b)?;
+
+    if transfer_fee_included_amount_a.amount > 0 {
+        transfer_from_owner_to_vault_v2(
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            transfer_fee_included_amount_a.amount,
+        )?;
+    }
+
+    if transfer_fee_included_amount_b.amount > 0 {
+        transfer_from_owner_to_vault_v2(
+            &ctx.accounts.token_authority,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            transfer_fee_included_amount_b.amount,
+        )?;
+    }
+
+    emit!(FlashLoanRepaid {
+        solve: ctx.accounts.solve.key(),
+        amount_a,
+        amount_b,
+        fee_a: update.fee_a,
+        fee_b: update.fee_b,
+        protocol_fee_a: update.protocol_fee_a,
+        protocol_fee_b: update.protocol_fee_b,
+    });
+
+    Ok(())
+}
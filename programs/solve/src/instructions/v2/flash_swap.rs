@@ -0,0 +1,381 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::*,
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+    },
+    state::*,
+    util::{
+        calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+        transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2,
+        verify_pool_and_config_not_paused, AccountsType, RemainingAccountsInfo,
+        SparseSwapTickSequenceBuilder,
+    },
+};
+
+#[derive(Accounts)]
+pub struct FlashSwapV2<'info> {
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked in the handler
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+
+    /// CHECK: any program except this program, invoked after the output tokens are sent
+    #[account(executable, constraint = callback_program.key() != crate::ID @ ErrorCode::InvalidFlashSwapCallbackProgram)]
+    pub callback_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - supplemental TickArray accounts
+    // - accounts for callback program
+    // - reward extension accounts (SolveRewardExtension and TickArrayRewardExtension of crossed ticks)
+}
+
+/// Swap result appended to the callback data when invoking the callback program.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FlashSwapCallback {
+    pub solve: Pubkey,
+    pub a_to_b: bool,
+    // transfer fee included
+    pub input_amount: u64,
+    // transfer fee included
+    pub output_amount: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FlashSwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+    callback_data: Vec<u8>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // The tail holds the lock taken while the callback program is invoked
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let solve = &mut ctx.accounts.solve;
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::FlashSwapCallback,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+
+    let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+        vec![
+            ctx.accounts.tick_array_0.to_account_info(),
+            ctx.accounts.tick_array_1.to_account_info(),
+            ctx.accounts.tick_array_2.to_account_info(),
+        ],
+        remaining_accounts.supplemental_tick_arrays,
+    );
+    let mut swap_tick_sequence = swap_tick_sequence_builder.try_build(solve, a_to_b)?;
+
+    let oracle_accessor = OracleAccessor::new(solve, ctx.accounts.oracle.to_account_info())?;
+    if !oracle_accessor.is_trade_enabled(timestamp)? {
+        return Err(ErrorCode::TradeIsNotEnabled.into());
+    }
+    verify_pool_and_config_not_paused(
+        solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Swap,
+    )?;
+    let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
+
+    let swap_update = swap_with_transfer_fee_extension(
+        solve,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        &reward_schedules,
+        timestamp,
+        &adaptive_fee_info,
+    )?;
+
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let (token_mint_input, token_mint_output) = if a_to_b {
+        (&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b)
+    } else {
+        (&ctx.accounts.token_mint_b, &ctx.accounts.token_mint_a)
+    };
+    let input_transfer_fee_excluded =
+        calculate_transfer_fee_excluded_amount(token_mint_input, input_amount)?;
+    let output_transfer_fee_excluded =
+        calculate_transfer_fee_excluded_amount(token_mint_output, output_amount)?;
+
+    if amount_specified_is_input {
+        if output_transfer_fee_excluded.amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else if input_amount > other_amount_threshold {
+        return Err(ErrorCode::AmountInAboveMaximum.into());
+    }
+
+    // The reward extension accrues rewards with the liquidity before the swap
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(solve, &remaining_accounts.reward_extension)?
+    {
+        reward_extension.update_for_swap(
+            solve,
+            &swap_update.crossed_tick_indexes,
+            a_to_b,
+            timestamp,
+        )?;
+    }
+
+    oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve, &swap_update.next_reward_infos)?;
+    oracle_accessor.record_observation(timestamp, solve.tick_current_index, solve.liquidity)?;
+    fill_limit_orders(
+        &oracle_accessor,
+        solve,
+        &mut swap_tick_sequence,
+        &swap_update,
+        a_to_b,
+    )?;
+    // release the tick arrays and the oracle before the callback program is invoked
+    drop(swap_tick_sequence);
+    drop(oracle_accessor);
+
+    let pre_sqrt_price = solve.sqrt_price;
+    solve.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        a_to_b,
+        timestamp,
+    );
+
+    // The callback program may invoke this program again. The Solve is stored and locked before any
+    // token is transferred, so that reentrant instructions see the state after the swap and cannot
+    // modify it until the input tokens have been received.
+    solve.exit(&crate::ID)?;
+    set_solve_locked(solve, true)?;
+
+    // Send the output tokens first
+    let (
+        token_owner_account_output,
+        token_vault_output,
+        token_program_output,
+        transfer_hook_output,
+    ) = if a_to_b {
+        (
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    } else {
+        (
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    };
+    transfer_from_vault_to_owner_v2(
+        solve,
+        token_mint_output,
+        token_vault_output,
+        token_owner_account_output,
+        token_program_output,
+        &ctx.accounts.memo_program,
+        transfer_hook_output,
+        output_amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    // Invoke the callback program with the swap result
+    let callback = FlashSwapCallback {
+        solve: solve.key(),
+        a_to_b,
+        input_amount,
+        output_amount,
+        input_transfer_fee: input_transfer_fee_excluded.transfer_fee,
+        output_transfer_fee: output_transfer_fee_excluded.transfer_fee,
+    };
+    invoke_flash_swap_callback(
+        &ctx.accounts.callback_program,
+        &remaining_accounts.flash_swap_callback,
+        callback_data,
+        &callback,
+    )?;
+
+    // The input tokens are transferred by this instruction, the callback program must have funded
+    // the input token account of the token authority
+    let (token_owner_account_input, token_vault_input, token_program_input, transfer_hook_input) =
+        if a_to_b {
+            (
+                &mut ctx.accounts.token_owner_account_a,
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_program_a,
+                &remaining_accounts.transfer_hook_a,
+            )
+        } else {
+            (
+                &mut ctx.accounts.token_owner_account_b,
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_program_b,
+                &remaining_accounts.transfer_hook_b,
+            )
+        };
+    token_owner_account_input.reload()?;
+    if token_owner_account_input.amount < input_amount {
+        return Err(ErrorCode::FlashSwapNotPaid.into());
+    }
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.token_authority,
+        token_mint_input,
+        token_owner_account_input,
+        token_vault_input,
+        token_program_input,
+        &ctx.accounts.memo_program,
+        transfer_hook_input,
+        input_amount,
+    )?;
+
+    // Authority instructions invoked during the callback may have modified the stored Solve
+    solve.reload()?;
+    set_solve_locked(solve, false)?;
+
+    emit!(Traded {
+        solve: solve.key(),
+        a_to_b,
+        pre_sqrt_price,
+        post_sqrt_price: solve.sqrt_price,
+        input_amount,
+        output_amount,
+        input_transfer_fee: callback.input_transfer_fee,
+        output_transfer_fee: callback.output_transfer_fee,
+        lp_fee: swap_update.lp_fee,
+        protocol_fee: swap_update.next_protocol_fee,
+        referral_fee: 0,
+    });
+
+    Ok(())
+}
+
+fn set_solve_locked(solve: &Account<Solve>, locked: bool) -> Result<()> {
+    let solve_account_info = solve.to_account_info();
+    let mut solve_tail = SolveTail::load(&solve_account_info)?;
+    solve_tail.locked = locked;
+    solve_tail.store(&solve_account_info)
+}
+
+fn invoke_flash_swap_callback<'info>(
+    callback_program: &UncheckedAccount<'info>,
+    callback_accounts: &Option<Vec<AccountInfo<'info>>>,
+    callback_data: Vec<u8>,
+    callback: &FlashSwapCallback,
+) -> Result<()> {
+    let callback_accounts = callback_accounts.clone().unwrap_or_default();
+
+    // caller specified data (e.g. instruction discriminator) followed by the swap result
+    let mut data = callback_data;
+    callback.serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+
+    let mut account_infos = callback_accounts;
+    account_infos.push(callback_program.to_account_info());
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ use crate::events::*;
 use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
 use crate::manager::tick_array_manager::update_tick_array_accounts;
 use crate::math::convert_to_liquidity_delta;
 use crate::state::*;
@@ -15,7 +16,8 @@ use crate::util::{
     RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_position_authority_interface,
+    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_pool_not_paused,
+    verify_position_authority_interface,
 };
 
 #[derive(Accounts)]
@@ -64,6 +66,7 @@ pub struct ModifyLiquidityV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - reward extension accounts (SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension of the position ticks)
 }
 
 pub fn handler<'info>(
@@ -78,6 +81,8 @@ pub fn handler<'info>(
         &ctx.accounts.position_authority,
     )?;
 
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::IncreaseLiquidity)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -88,11 +93,30 @@ pub fn handler<'info>(
     let remaining_accounts = parse_remaining_accounts(
         ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::RewardExtension,
+        ],
     )?;
 
     let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // The reward extension is updated with the solve, position and ticks before the modification
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?
+    {
+        reward_extension.update_for_modify_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
 
     let tick_arrays = TickArraysMut::load(
         &ctx.accounts.tick_array_lower,
@@ -107,6 +131,7 @@ pub fn handler<'info>(
         lower_tick_array,
         upper_tick_array,
         liquidity_delta,
+        &reward_schedules,
         timestamp,
     )?;
 
@@ -127,6 +152,7 @@ pub fn handler<'info>(
         &ctx.accounts.solve.key(),
     )?;
 
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
     let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
     sync_modify_liquidity_values(
         &mut ctx.accounts.solve,
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::manager::liquidity_manager::calculate_liquidity_from_token_amounts;
+use crate::state::PausableOperation;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, verify_pool_not_paused, RemainingAccountsInfo,
+};
+
+use super::increase_liquidity::ModifyLiquidityV2;
+
+/*
+  Adds the maximum liquidity that can be funded with token_max_a and token_max_b.
+  The liquidity is calculated on-chain at the current sqrt_price, so it does not race with price changes.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+    token_max_a: u64,
+    token_max_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Checked again by increase_liquidity, but a paused pool must fail before the liquidity is calculated
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::IncreaseLiquidity)?;
+
+    // token_max_a and token_max_b are transfer fee included amounts,
+    // the liquidity is funded by the amounts received by the vaults
+    let transfer_fee_excluded_max_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, token_max_a)?;
+    let transfer_fee_excluded_max_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, token_max_b)?;
+
+    let liquidity_amount = calculate_liquidity_from_token_amounts(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        transfer_fee_excluded_max_a.amount,
+        transfer_fee_excluded_max_b.amount,
+    )?;
+
+    super::increase_liquidity::handler(
+        ctx,
+        liquidity_amount,
+        token_max_a,
+        token_max_b,
+        remaining_accounts_info,
+    )
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    state::{PausableOperation, Solve, SolveRewardExtension},
+    util::{initialize_vault_token_account, verify_pool_not_paused, verify_supported_token_mint},
+};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct InitializeExtensionReward<'info> {
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut, has_one = solve)]
+    pub solve_reward_extension: Box<Account<'info, SolveRewardExtension>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(seeds = [b"token_badge", solve.solves_config.as_ref(), reward_mint.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub reward_token_badge: UncheckedAccount<'info>,
+
+    /// CHECK: initialized in the handler
+    #[account(mut)]
+    pub reward_vault: Signer<'info>,
+
+    #[account(address = *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<InitializeExtensionReward>, reward_index: u8) -> Result<()> {
+    let solve = &ctx.accounts.solve;
+
+    verify_pool_not_paused(solve, PausableOperation::Other)?;
+
+    // Don't allow initializing a reward with an unsupported token mint
+    verify_supported_token_mint(
+        &ctx.accounts.reward_mint,
+        solve.solves_config,
+        &ctx.accounts.reward_token_badge,
+    )?;
+
+    // The vault is owned by the solve, as the vaults of the other rewards
+    initialize_vault_token_account(
+        solve,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.funder,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.system_program,
+    )?;
+
+    ctx.accounts.solve_reward_extension.initialize_reward(
+        reward_index as usize,
+        ctx.accounts.reward_mint.key(),
+        ctx.accounts.reward_vault.key(),
+    )
+}
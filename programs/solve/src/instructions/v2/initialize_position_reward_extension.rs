@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+use crate::state::{
+    PausableOperation, Position, PositionRewardExtension, Solve, SolveRewardExtension,
+};
+use crate::util::verify_pool_not_paused;
+
+#[derive(Accounts)]
+pub struct InitializePositionRewardExtension<'info> {
+    #[account(mut, has_one = solve)]
+    pub position: Account<'info, Position>,
+
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(has_one = solve)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"position_reward_extension", position.key().as_ref()],
+      bump,
+      space = PositionRewardExtension::LEN)]
+    pub position_reward_extension: Account<'info, PositionRewardExtension>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// The checkpoints start from zero like the reward growths. A position that held its liquidity
+/// since the reward extension was enabled is owed the rewards accrued inside its range since then.
+/// Liquidity removed before the PositionRewardExtension exists only loses its share of the rewards.
+///
+/// The position is marked with one extra byte, so that it cannot be closed without its extension.
+pub fn handler(ctx: Context<InitializePositionRewardExtension>) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
+    ctx.accounts
+        .position_reward_extension
+        .initialize(ctx.accounts.position.key());
+
+    let position_account_info = ctx.accounts.position.to_account_info();
+    let required_size = Position::LEN + Position::REWARD_EXTENSION_MARKER_LEN;
+    let required_lamports = Rent::get()?
+        .minimum_balance(required_size)
+        .saturating_sub(position_account_info.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.funder.key,
+                position_account_info.key,
+                required_lamports,
+            ),
+            &[
+                ctx.accounts.funder.to_account_info(),
+                position_account_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+    position_account_info.realloc(required_size, true)?;
+    Ok(())
+}
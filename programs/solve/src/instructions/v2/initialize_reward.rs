@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use crate::{
-    state::Solve,
-    util::{initialize_vault_token_account, verify_supported_token_mint},
+    state::{PausableOperation, Solve, SolveTail},
+    util::{initialize_vault_token_account, verify_pool_not_paused, verify_supported_token_mint},
 };
 
 #[derive(Accounts)]
@@ -35,6 +35,8 @@ pub struct InitializeRewardV2<'info> {
 }
 
 pub fn handler(ctx: Context<InitializeRewardV2>, reward_index: u8) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
     let solve = &mut ctx.accounts.solve;
 
     // Don't allow initializing a reward with an unsupported token mint
@@ -57,5 +59,12 @@ pub fn handler(ctx: Context<InitializeRewardV2>, reward_index: u8) -> Result<()>
         reward_index as usize,
         ctx.accounts.reward_mint.key(),
         ctx.accounts.reward_vault.key(),
+    )?;
+
+    // The emissions of the reward are tracked from the start
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
     )
 }
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{OracleAccessor, PausableOperation, Solve, SolveRewardExtension, SolveTail};
+use crate::util::{to_timestamp_u64, verify_pool_not_paused};
+
+#[derive(Accounts)]
+pub struct InitializeRewardExtension<'info> {
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = funder,
+        seeds = [b"reward_extension", solve.key().as_ref()],
+        bump,
+        space = SolveRewardExtension::LEN
+    )]
+    pub solve_reward_extension: Box<Account<'info, SolveRewardExtension>>,
+
+    #[account(address = solve.reward_infos[0].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Enables the reward extension for the pool. Once enabled, it cannot be disabled.
+/// The flag is kept in the tail of the Solve account, which is allocated if needed.
+///
+/// Limit orders do not track the rewards of the reward extension,
+/// so the pool must not have any limit order when the reward extension is enabled.
+pub fn handler(ctx: Context<InitializeRewardExtension>) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
+    let oracle_accessor =
+        OracleAccessor::new(&ctx.accounts.solve, ctx.accounts.oracle.to_account_info())?;
+    if oracle_accessor.get_limit_order_count()? != 0 {
+        return Err(ErrorCode::RewardExtensionLimitOrdersExist.into());
+    }
+
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    let solve_account_info = ctx.accounts.solve.to_account_info();
+    let mut solve_tail = SolveTail::load(&solve_account_info)?;
+    if solve_tail.reward_extension_enabled {
+        return Err(ErrorCode::RewardExtensionAlreadyEnabled.into());
+    }
+    solve_tail.reward_extension_enabled = true;
+    solve_tail.store(&solve_account_info)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts
+        .solve_reward_extension
+        .initialize(&ctx.accounts.solve, timestamp)
+}
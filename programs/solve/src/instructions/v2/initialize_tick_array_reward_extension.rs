@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PausableOperation, Solve, SolveRewardExtension, TickArrayRewardExtension};
+use crate::util::verify_pool_not_paused;
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArrayRewardExtension<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(has_one = solve)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [b"tick_array_reward_extension", solve.key().as_ref(), start_tick_index.to_string().as_bytes()],
+      bump,
+      space = TickArrayRewardExtension::LEN)]
+    pub tick_array_reward_extension: AccountLoader<'info, TickArrayRewardExtension>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Swaps crossing a tick and liquidity changes initializing a tick require the account covering
+/// that tick once the reward extension is enabled. The ticks covered by a new account have not been
+/// crossed or initialized since then, so their reward growths outside are zero and the account is
+/// initialized with zeros, whenever it is created.
+pub fn handler(
+    ctx: Context<InitializeTickArrayRewardExtension>,
+    start_tick_index: i32,
+) -> Result<()> {
+    verify_pool_not_paused(&ctx.accounts.solve, PausableOperation::Other)?;
+
+    let mut tick_array_reward_extension = ctx.accounts.tick_array_reward_extension.load_init()?;
+    tick_array_reward_extension.initialize(&ctx.accounts.solve, start_tick_index)
+}
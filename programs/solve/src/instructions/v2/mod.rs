@@ -1,34 +1,110 @@
 #![allow(ambiguous_glob_reexports)]
 
+pub mod accept_config_extension_authority;
+pub mod accept_pause_authority;
+pub mod accept_token_badge_authority;
+pub mod cancel_parameter_change;
+pub mod close_limit_order;
+pub mod collect_extension_reward;
 pub mod collect_fees;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
+pub mod compound_fees;
 pub mod decrease_liquidity;
+pub mod distribute_protocol_fees;
+pub mod execute_parameter_change;
+pub mod flash_borrow;
+pub mod flash_repay;
+pub mod flash_swap;
 pub mod increase_liquidity;
+pub mod increase_liquidity_by_token_amounts;
+pub mod initialize_extension_reward;
 pub mod initialize_pool;
+pub mod initialize_position_reward_extension;
 pub mod initialize_reward;
+pub mod initialize_reward_extension;
+pub mod initialize_tick_array_reward_extension;
+pub mod open_limit_order;
+pub mod queue_parameter_change;
+pub mod quote_swap;
+pub mod reposition_position;
+pub mod route_swap;
+pub mod set_admin_timelock_delay;
+pub mod set_config_pause_flags;
+pub mod set_extension_reward_authority;
+pub mod set_extension_reward_boosted;
+pub mod set_extension_reward_emissions;
+pub mod set_lock_boost_config;
+pub mod set_pause_authority;
+pub mod set_pool_pause_flags;
 pub mod set_reward_emissions;
+pub mod set_reward_schedule;
 pub mod swap;
+pub mod sync_pool_pause_flags;
 pub mod two_hop_swap;
+pub mod update_extension_rewards;
+pub mod update_position_boost;
+pub mod withdraw_unallocated_reward;
+pub mod zap_in;
+pub mod zap_out;
 
 pub mod delete_token_badge;
 pub mod initialize_config_extension;
 pub mod initialize_token_badge;
 pub mod set_config_extension_authority;
+pub mod set_protocol_fee_recipients;
+pub mod set_tick_array_rent_recipient;
 pub mod set_token_badge_authority;
 
+pub use accept_config_extension_authority::*;
+pub use accept_pause_authority::*;
+pub use accept_token_badge_authority::*;
+pub use cancel_parameter_change::*;
+pub use close_limit_order::*;
+pub use collect_extension_reward::*;
 pub use collect_fees::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use compound_fees::*;
+pub use distribute_protocol_fees::*;
+pub use execute_parameter_change::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use flash_swap::*;
 pub use increase_liquidity::*;
+pub use initialize_extension_reward::*;
 pub use initialize_pool::*;
+pub use initialize_position_reward_extension::*;
 pub use initialize_reward::*;
+pub use initialize_reward_extension::*;
+pub use initialize_tick_array_reward_extension::*;
+pub use open_limit_order::*;
+pub use queue_parameter_change::*;
+pub use quote_swap::*;
+pub use reposition_position::*;
+pub use route_swap::*;
+pub use set_admin_timelock_delay::*;
+pub use set_config_pause_flags::*;
+pub use set_extension_reward_authority::*;
+pub use set_extension_reward_boosted::*;
+pub use set_extension_reward_emissions::*;
+pub use set_lock_boost_config::*;
+pub use set_pause_authority::*;
+pub use set_pool_pause_flags::*;
 pub use set_reward_emissions::*;
+pub use set_reward_schedule::*;
 pub use swap::*;
+pub use sync_pool_pause_flags::*;
 pub use two_hop_swap::*;
+pub use update_extension_rewards::*;
+pub use update_position_boost::*;
+pub use withdraw_unallocated_reward::*;
+pub use zap_in::*;
 
 pub use delete_token_badge::*;
 pub use initialize_config_extension::*;
 pub use initialize_token_badge::*;
 pub use set_config_extension_authority::*;
+pub use set_protocol_fee_recipients::*;
+pub use set_tick_array_rent_recipient::*;
 pub use set_token_badge_authority::*;
@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::{program::invoke, system_instruction};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::limit_order_manager::collect_limit_order_deposit;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::manager::tick_array_manager::{
+    collect_rent_for_ticks_in_limit_order, update_tick_array_accounts,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_included_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::util::{
+    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_pool_and_config_not_paused,
+};
+
+#[derive(Accounts)]
+pub struct OpenLimitOrder<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    pub oracle: AccountLoader<'info, Oracle>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+
+    #[account(init, payer = funder, space = LimitOrder::LEN)]
+    pub limit_order: Account<'info, LimitOrder>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - reward extension accounts (SolveRewardExtension and TickArrayRewardExtension of the order ticks)
+}
+
+/*
+  Deposits a single token into a one tick spacing range on the far side of the current price.
+  The order is filled by the swap which crosses the far tick of the range.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenLimitOrder<'info>>,
+    tick_lower_index: i32,
+    a_to_b: bool,
+    liquidity_amount: u128,
+    token_max: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::IncreaseLiquidity,
+    )?;
+
+    let tick_spacing = ctx.accounts.solve.tick_spacing;
+    validate_limit_order_tick_index(tick_lower_index, tick_spacing)?;
+
+    // The order must hold only the token it sells
+    let tick_upper_index = tick_lower_index + tick_spacing as i32;
+    let tick_current_index = ctx.accounts.solve.tick_current_index;
+    if (a_to_b && tick_current_index >= tick_lower_index)
+        || (!a_to_b && tick_current_index < tick_upper_index)
+    {
+        return Err(ErrorCode::InvalidLimitOrderRange.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+
+    reserve_limit_order_slot(
+        &ctx.accounts.oracle,
+        &ctx.accounts.funder,
+        &ctx.accounts.system_program,
+    )?;
+
+    collect_rent_for_ticks_in_limit_order(
+        &ctx.accounts.funder,
+        &ctx.accounts.limit_order,
+        &ctx.accounts.system_program,
+    )?;
+    collect_limit_order_deposit(
+        &ctx.accounts.funder,
+        &ctx.accounts.limit_order,
+        &ctx.accounts.system_program,
+    )?;
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // The liquidity of the order is managed with a position that only lives in this instruction
+    let mut position = Position {
+        solve: ctx.accounts.solve.key(),
+        tick_lower_index,
+        tick_upper_index,
+        ..Default::default()
+    };
+
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?
+    {
+        reward_extension.update_for_open_limit_order(
+            &ctx.accounts.solve,
+            &ctx.accounts.tick_array,
+            tick_lower_index,
+            tick_upper_index,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array,
+        &ctx.accounts.tick_array,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.limit_order,
+        ctx.accounts.tick_array.to_account_info(),
+        ctx.accounts.tick_array.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array,
+        &ctx.accounts.tick_array,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &position,
+        liquidity_delta,
+    )?;
+
+    let (token_mint, token_owner_account, token_vault, token_program, transfer_hook, delta) =
+        if a_to_b {
+            (
+                &ctx.accounts.token_mint_a,
+                &ctx.accounts.token_owner_account_a,
+                &ctx.accounts.token_vault_a,
+                &ctx.accounts.token_program_a,
+                &remaining_accounts.transfer_hook_a,
+                delta_a,
+            )
+        } else {
+            (
+                &ctx.accounts.token_mint_b,
+                &ctx.accounts.token_owner_account_b,
+                &ctx.accounts.token_vault_b,
+                &ctx.accounts.token_program_b,
+                &remaining_accounts.transfer_hook_b,
+                delta_b,
+            )
+        };
+
+    // token_max should be applied to the transfer fee included amount
+    let transfer_fee_included_delta = calculate_transfer_fee_included_amount(token_mint, delta)?;
+    if transfer_fee_included_delta.amount > token_max {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    }
+
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.owner,
+        token_mint,
+        token_owner_account,
+        token_vault,
+        token_program,
+        &ctx.accounts.memo_program,
+        transfer_hook,
+        transfer_fee_included_delta.amount,
+    )?;
+
+    let limit_order_key = ctx.accounts.limit_order.key();
+    {
+        let oracle_account_info = ctx.accounts.oracle.to_account_info();
+        let mut data = oracle_account_info.try_borrow_mut_data()?;
+        let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data)?;
+        let mut entry = LimitOrderEntry {
+            limit_order: limit_order_key,
+            tick_lower_index,
+            a_to_b: a_to_b as u8,
+            filled: 0,
+            liquidity: liquidity_amount,
+            fee_growth_checkpoint_a: position.fee_growth_checkpoint_a,
+            fee_growth_checkpoint_b: position.fee_growth_checkpoint_b,
+            ..Default::default()
+        };
+        entry.update_reward_infos(&position.reward_infos);
+        oracle.add_limit_order(limit_orders, entry)?;
+    }
+
+    ctx.accounts.limit_order.open_limit_order(
+        ctx.accounts.solve.key(),
+        ctx.accounts.owner.key(),
+        tick_lower_index,
+        a_to_b,
+        liquidity_amount,
+    );
+
+    emit!(LimitOrderOpened {
+        solve: ctx.accounts.solve.key(),
+        limit_order: limit_order_key,
+        owner: ctx.accounts.owner.key(),
+        tick_lower_index,
+        a_to_b,
+        liquidity: liquidity_amount,
+        token_amount: transfer_fee_included_delta.amount,
+        token_transfer_fee: transfer_fee_included_delta.transfer_fee,
+    });
+
+    Ok(())
+}
+
+// Grows the limit order slots of the Oracle account if all slots are in use.
+// Rent for the new slot is paid by the funder.
+fn reserve_limit_order_slot<'info>(
+    oracle: &AccountLoader<'info, Oracle>,
+    funder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let (observation_cardinality_next, limit_order_capacity) = {
+        let mut oracle = oracle.load_mut()?;
+        if oracle.limit_order_count < oracle.limit_order_capacity {
+            return Ok(());
+        }
+        if oracle.limit_order_capacity >= MAX_LIMIT_ORDERS {
+            return Err(ErrorCode::LimitOrderCapacityExceeded.into());
+        }
+        oracle.limit_order_capacity += 1;
+        (
+            oracle.observation_cardinality_next,
+            oracle.limit_order_capacity,
+        )
+    };
+
+    let oracle_account_info = oracle.to_account_info();
+    let required_size = Oracle::space(observation_cardinality_next, limit_order_capacity);
+
+    let required_lamports = Rent::get()?
+        .minimum_balance(required_size)
+        .saturating_sub(oracle_account_info.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(funder.key, oracle_account_info.key, required_lamports),
+            &[
+                funder.to_account_info(),
+                oracle_account_info.clone(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    oracle_account_info.realloc(required_size, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+#[instruction(parameter_change: ParameterChange)]
+pub struct QueueParameterChange<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// CHECK: the pool, fee tier or config depending on the parameter, checked in the handler
+    pub target: UncheckedAccount<'info>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [
+        b"queued_change",
+        solves_config.key().as_ref(),
+        target.key().as_ref(),
+        parameter_change.seed().as_ref(),
+      ],
+      bump,
+      space = QueuedChange::LEN)]
+    pub queued_change: Account<'info, QueuedChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Queues a parameter change that can be executed after the admin timelock delay.
+  The delay in effect at the execution applies.
+*/
+pub fn handler(
+    ctx: Context<QueueParameterChange>,
+    parameter_change: ParameterChange,
+) -> Result<()> {
+    let solves_config = ctx.accounts.solves_config.key();
+    let target = &ctx.accounts.target;
+
+    let label = parameter_change.label();
+    let is_valid_target = if label.is_pool_change() {
+        target.owner == &crate::ID
+            && Solve::try_deserialize(&mut target.try_borrow_data()?.as_ref())
+                .is_ok_and(|solve| solve.solves_config == solves_config)
+    } else if label.is_fee_tier_change() {
+        target.owner == &crate::ID
+            && FeeTier::try_deserialize(&mut target.try_borrow_data()?.as_ref())
+                .is_ok_and(|fee_tier| fee_tier.solves_config == solves_config)
+    } else {
+        target.key() == solves_config
+    };
+    if !is_valid_target {
+        return Err(ErrorCode::InvalidQueuedChangeTarget.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let queued_change = &mut ctx.accounts.queued_change;
+    queued_change.initialize(
+        solves_config,
+        target.key(),
+        parameter_change,
+        timestamp,
+        ctx.accounts.solves_config_extension.admin_timelock_delay,
+    )?;
+
+    emit!(ParameterChangeQueued {
+        solves_config,
+        target: queued_change.target,
+        parameter_change: queued_change.parameter_change,
+        value: queued_change.value,
+        execution_timestamp: queued_change.execution_timestamp,
+    });
+
+    Ok(())
+}
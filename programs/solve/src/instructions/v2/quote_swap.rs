@@ -0,0 +1,393 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::swap_with_transfer_fee_extension;
+use crate::{
+    errors::ErrorCode,
+    state::{OracleAccessor, Solve, SolveTail},
+    util::{
+        calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+        AccountsType, RemainingAccountsInfo, SparseSwapTickSequenceBuilder,
+    },
+};
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_0: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_1: UncheckedAccount<'info>,
+
+    /// CHECK: checked in the handler
+    pub tick_array_2: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: UncheckedAccount<'info>,
+    // remaining accounts
+    // - supplemental TickArray accounts
+}
+
+/// Result of quote_swap. Amounts are what swap_v2 would transfer with the same parameters.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    // transfer fee included
+    pub amount_a: u64,
+    // transfer fee included
+    pub amount_b: u64,
+    pub input_transfer_fee: u64,
+    pub output_transfer_fee: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub next_liquidity: u128,
+    pub next_tick_index: i32,
+    pub next_sqrt_price: u128,
+    pub next_fee_growth_global: u128,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, QuoteSwap<'info>>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<SwapQuote> {
+    let solve = &ctx.accounts.solve;
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::SupplementalTickArrays],
+    )?;
+
+    let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+        vec![
+            ctx.accounts.tick_array_0.to_account_info(),
+            ctx.accounts.tick_array_1.to_account_info(),
+            ctx.accounts.tick_array_2.to_account_info(),
+        ],
+        remaining_accounts.supplemental_tick_arrays,
+    );
+
+    quote(
+        solve,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_mint_b,
+        &swap_tick_sequence_builder,
+        ctx.accounts.oracle.to_account_info(),
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn quote<'info>(
+    solve: &Account<'info, Solve>,
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+    swap_tick_sequence_builder: &SparseSwapTickSequenceBuilder<'info>,
+    oracle: AccountInfo<'info>,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<SwapQuote> {
+    let mut swap_tick_sequence = swap_tick_sequence_builder.try_build_read_only(solve, a_to_b)?;
+
+    let oracle_accessor = OracleAccessor::new(solve, oracle)?;
+    if !oracle_accessor.is_trade_enabled(timestamp)? {
+        return Err(ErrorCode::TradeIsNotEnabled.into());
+    }
+    let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
+
+    // Solve, Oracle and TickArray accounts are not updated
+    let swap_update = swap_with_transfer_fee_extension(
+        solve,
+        token_mint_a,
+        token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        &reward_schedules,
+        timestamp,
+        &adaptive_fee_info,
+    )?;
+
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    let (token_mint_input, token_mint_output) = if a_to_b {
+        (token_mint_a, token_mint_b)
+    } else {
+        (token_mint_b, token_mint_a)
+    };
+    let input_transfer_fee =
+        calculate_transfer_fee_excluded_amount(token_mint_input, input_amount)?.transfer_fee;
+    let output_transfer_fee =
+        calculate_transfer_fee_excluded_amount(token_mint_output, output_amount)?.transfer_fee;
+
+    Ok(SwapQuote {
+        amount_a: swap_update.amount_a,
+        amount_b: swap_update.amount_b,
+        input_transfer_fee,
+        output_transfer_fee,
+        lp_fee: swap_update.lp_fee,
+        protocol_fee: swap_update.next_protocol_fee,
+        next_liquidity: swap_update.next_liquidity,
+        next_tick_index: swap_update.next_tick_index,
+        next_sqrt_price: swap_update.next_sqrt_price,
+        next_fee_growth_global: swap_update.next_fee_growth_global,
+    })
+}
+
+#[cfg(test)]
+mod quote_swap_tests {
+    use super::*;
+    use crate::manager::swap_manager::PostSwapUpdate;
+    use crate::math::sqrt_price_from_tick_index;
+    use crate::state::{FixedTickArray, TickArrayType, TickUpdate};
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+    const TICK_CURRENT_INDEX: i32 = 2_048;
+    const INITIALIZED_TICK_INDEX: i32 = 1_024;
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+    const LIQUIDITY_NET: i128 = 1_000;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>, owner: Pubkey) -> Self {
+            Self {
+                key,
+                owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    struct TestAccounts {
+        solve: TestAccount,
+        token_mint_a: TestAccount,
+        token_mint_b: TestAccount,
+        tick_array: TestAccount,
+        oracle: TestAccount,
+    }
+
+    impl TestAccounts {
+        fn new() -> Self {
+            let solve_key = Pubkey::new_unique();
+            let solve = Solve {
+                tick_spacing: TICK_SPACING,
+                fee_rate: 3_000,
+                protocol_fee_rate: 300,
+                liquidity: LIQUIDITY,
+                sqrt_price: sqrt_price_from_tick_index(TICK_CURRENT_INDEX),
+                tick_current_index: TICK_CURRENT_INDEX,
+                ..Default::default()
+            };
+            let mut solve_data = vec![0u8; Solve::LEN];
+            solve.try_serialize(&mut solve_data.as_mut_slice()).unwrap();
+
+            // A fixed tick array starting at tick 0 with a single initialized tick below the current tick
+            let mut tick_array_data = vec![0u8; FixedTickArray::LEN];
+            tick_array_data[0..8].copy_from_slice(&FixedTickArray::discriminator());
+            tick_array_data[9956..9988].copy_from_slice(&solve_key.to_bytes());
+            let tick_array: &mut FixedTickArray =
+                bytemuck::from_bytes_mut(&mut tick_array_data[8..]);
+            tick_array
+                .update_tick(
+                    INITIALIZED_TICK_INDEX,
+                    TICK_SPACING,
+                    &TickUpdate {
+                        initialized: true,
+                        liquidity_net: LIQUIDITY_NET,
+                        liquidity_gross: LIQUIDITY_NET as u128,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+            Self {
+                solve: TestAccount::new(solve_key, solve_data, crate::ID),
+                token_mint_a: mint_account(),
+                token_mint_b: mint_account(),
+                tick_array: TestAccount::new(Pubkey::new_unique(), tick_array_data, crate::ID),
+                oracle: TestAccount::new(Pubkey::new_unique(), vec![], System::id()),
+            }
+        }
+
+        // Quotes the swap with read-only tick arrays
+        fn quote(
+            &mut self,
+            amount: u64,
+            sqrt_price_limit: u128,
+            amount_specified_is_input: bool,
+        ) -> Result<SwapQuote> {
+            let solve_info = self.solve.account_info(false);
+            let solve: Account<Solve> = Account::try_from(&solve_info)?;
+            let token_mint_a_info = self.token_mint_a.account_info(false);
+            let token_mint_a = InterfaceAccount::<Mint>::try_from(&token_mint_a_info)?;
+            let token_mint_b_info = self.token_mint_b.account_info(false);
+            let token_mint_b = InterfaceAccount::<Mint>::try_from(&token_mint_b_info)?;
+            let swap_tick_sequence_builder =
+                SparseSwapTickSequenceBuilder::new(vec![self.tick_array.account_info(false)], None);
+            quote(
+                &solve,
+                &token_mint_a,
+                &token_mint_b,
+                &swap_tick_sequence_builder,
+                self.oracle.account_info(false),
+                amount,
+                sqrt_price_limit,
+                amount_specified_is_input,
+                true,
+                1_000,
+            )
+        }
+
+        // Executes the swap as swap_v2 does, updating the tick arrays
+        fn swap(
+            &mut self,
+            amount: u64,
+            sqrt_price_limit: u128,
+            amount_specified_is_input: bool,
+        ) -> Result<Box<PostSwapUpdate>> {
+            let solve_info = self.solve.account_info(true);
+            let solve: Account<Solve> = Account::try_from(&solve_info)?;
+            let token_mint_a_info = self.token_mint_a.account_info(false);
+            let token_mint_a = InterfaceAccount::<Mint>::try_from(&token_mint_a_info)?;
+            let token_mint_b_info = self.token_mint_b.account_info(false);
+            let token_mint_b = InterfaceAccount::<Mint>::try_from(&token_mint_b_info)?;
+            let swap_tick_sequence_builder =
+                SparseSwapTickSequenceBuilder::new(vec![self.tick_array.account_info(true)], None);
+            let mut swap_tick_sequence = swap_tick_sequence_builder.try_build(&solve, true)?;
+            swap_with_transfer_fee_extension(
+                &solve,
+                &token_mint_a,
+                &token_mint_b,
+                &mut swap_tick_sequence,
+                amount,
+                sqrt_price_limit,
+                amount_specified_is_input,
+                true,
+                &SolveTail::default().reward_schedules,
+                1_000,
+                &None,
+            )
+        }
+    }
+
+    fn mint_account() -> TestAccount {
+        let mint = anchor_spl::token::spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; anchor_spl::token::spl_token::state::Mint::LEN];
+        mint.pack_into_slice(&mut data);
+        TestAccount::new(Pubkey::new_unique(), data, anchor_spl::token::ID)
+    }
+
+    #[test]
+    fn test_quote_swap_matches_swap() {
+        let mut test_accounts = TestAccounts::new();
+        let solve_data = test_accounts.solve.data.clone();
+        let tick_array_data = test_accounts.tick_array.data.clone();
+
+        // The swap crosses the initialized tick
+        let sqrt_price_limit = sqrt_price_from_tick_index(512);
+        let quote = test_accounts
+            .quote(60_000_000_000, sqrt_price_limit, true)
+            .unwrap();
+        assert!(quote.next_tick_index < INITIALIZED_TICK_INDEX);
+        assert_eq!(quote.next_liquidity, LIQUIDITY - LIQUIDITY_NET as u128);
+        assert_eq!(quote.amount_a, 60_000_000_000);
+        assert!(quote.amount_b > 0);
+        assert!(quote.lp_fee > 0);
+        assert!(quote.protocol_fee > 0);
+        assert_eq!(quote.input_transfer_fee, 0);
+        assert_eq!(quote.output_transfer_fee, 0);
+
+        // The accounts are not updated
+        assert_eq!(test_accounts.solve.data, solve_data);
+        assert_eq!(test_accounts.tick_array.data, tick_array_data);
+
+        // The quote is what the swap would do
+        let swap_update = test_accounts
+            .swap(60_000_000_000, sqrt_price_limit, true)
+            .unwrap();
+        assert_eq!(quote.amount_a, swap_update.amount_a);
+        assert_eq!(quote.amount_b, swap_update.amount_b);
+        assert_eq!(quote.lp_fee, swap_update.lp_fee);
+        assert_eq!(quote.protocol_fee, swap_update.next_protocol_fee);
+        assert_eq!(quote.next_liquidity, swap_update.next_liquidity);
+        assert_eq!(quote.next_tick_index, swap_update.next_tick_index);
+        assert_eq!(quote.next_sqrt_price, swap_update.next_sqrt_price);
+        assert_eq!(
+            quote.next_fee_growth_global,
+            swap_update.next_fee_growth_global
+        );
+        // The swap updates the crossed tick
+        assert_ne!(test_accounts.tick_array.data, tick_array_data);
+    }
+
+    #[test]
+    fn test_quote_swap_exact_out() {
+        let mut test_accounts = TestAccounts::new();
+        let quote = test_accounts
+            .quote(1_000_000, sqrt_price_from_tick_index(512), false)
+            .unwrap();
+        assert_eq!(quote.amount_b, 1_000_000);
+        assert!(quote.amount_a > 0);
+        assert_eq!(quote.next_liquidity, LIQUIDITY);
+    }
+
+    #[test]
+    fn test_quote_swap_zero_amount() {
+        let mut test_accounts = TestAccounts::new();
+        let result = test_accounts.quote(0, sqrt_price_from_tick_index(512), true);
+        assert_eq!(result.unwrap_err(), ErrorCode::ZeroTradableAmount.into());
+    }
+}
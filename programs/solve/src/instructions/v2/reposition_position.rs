@@ -0,0 +1,432 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::reset_position_range::ensure_position_has_enough_rent_for_ticks;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_from_token_amounts, calculate_liquidity_token_deltas,
+    calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, is_locked_position, parse_remaining_accounts,
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_pool_and_config_not_paused,
+    verify_position_authority_interface, AccountsType, RemainingAccountsInfo,
+    SparseSwapTickSequenceBuilder,
+};
+
+use super::swap::swap_within_vaults;
+
+#[derive(Accounts)]
+pub struct RepositionPositionV2<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut, has_one = solve)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub new_tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub new_tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - supplemental TickArray accounts for the swap
+    // - reward extension accounts (SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension
+    //   of the old and new position ticks and the crossed ticks)
+}
+
+/// Swap executed between the withdrawal and the redeposit of a reposition.
+/// The input is taken from the withdrawn tokens, so no token is transferred for the swap.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RepositionSwap {
+    pub a_to_b: bool,
+    // exact input amount
+    pub amount: u64,
+    // minimum output amount
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+}
+
+/*
+  Moves the liquidity of a position to a new range in a single instruction.
+  The liquidity is withdrawn, fees are collected, the range is moved, the imbalance is optionally
+  swapped through the same pool, and the maximum liquidity is redeposited.
+  Tokens that could not be redeposited are returned to the owner.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, RepositionPositionV2<'info>>,
+    new_tick_lower_index: i32,
+    new_tick_upper_index: i32,
+    liquidity_min: u128,
+    swap_params: Option<RepositionSwap>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::IncreaseLiquidity,
+    )?;
+    if swap_params.is_some() {
+        verify_pool_and_config_not_paused(
+            &ctx.accounts.solve,
+            &ctx.accounts.solves_config_extension,
+            PausableOperation::Swap,
+        )?;
+    }
+
+    if is_locked_position(&ctx.accounts.position_token_account) {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    let old_liquidity = ctx.accounts.position.liquidity;
+    if old_liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+    let mut reward_extension =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?;
+
+    let old_tick_lower_index = ctx.accounts.position.tick_lower_index;
+    let old_tick_upper_index = ctx.accounts.position.tick_upper_index;
+
+    // Withdraw all liquidity from the current range
+    let liquidity_delta = convert_to_liquidity_delta(old_liquidity, false)?;
+
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_for_decrease_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let (withdrawn_a, withdrawn_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    // Collect fees into the tokens to be redeposited
+    let fee_a = ctx.accounts.position.fee_owed_a;
+    let fee_b = ctx.accounts.position.fee_owed_b;
+    ctx.accounts.position.reset_fees_owed();
+
+    let mut amount_a = withdrawn_a
+        .checked_add(fee_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    let mut amount_b = withdrawn_b
+        .checked_add(fee_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+    // The withdrawn tokens stay in the vaults, so the swap only updates the pool state
+    if let Some(swap_params) = swap_params {
+        let a_to_b = swap_params.a_to_b;
+        let available_input_amount = if a_to_b { amount_a } else { amount_b };
+        if swap_params.amount > available_input_amount {
+            return Err(ErrorCode::RepositionSwapAmountExceeded.into());
+        }
+
+        let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+            vec![
+                ctx.accounts.tick_array_lower.to_account_info(),
+                ctx.accounts.tick_array_upper.to_account_info(),
+                ctx.accounts.new_tick_array_lower.to_account_info(),
+                ctx.accounts.new_tick_array_upper.to_account_info(),
+            ],
+            remaining_accounts.supplemental_tick_arrays,
+        );
+        let (input_amount, output_amount) = swap_within_vaults(
+            &mut ctx.accounts.solve,
+            ctx.accounts.oracle.to_account_info(),
+            &swap_tick_sequence_builder,
+            reward_extension.as_mut(),
+            swap_params.amount,
+            swap_params.other_amount_threshold,
+            swap_params.sqrt_price_limit,
+            a_to_b,
+            timestamp,
+        )?;
+
+        if a_to_b {
+            amount_a -= input_amount;
+            amount_b = amount_b
+                .checked_add(output_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+        } else {
+            amount_b -= input_amount;
+            amount_a = amount_a
+                .checked_add(output_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+        }
+    }
+
+    // Move the range
+    ensure_position_has_enough_rent_for_ticks(
+        &ctx.accounts.funder,
+        &ctx.accounts.position,
+        &ctx.accounts.system_program,
+    )?;
+
+    ctx.accounts.position.reposition_range(
+        &ctx.accounts.solve,
+        new_tick_lower_index,
+        new_tick_upper_index,
+    )?;
+
+    // Redeposit the maximum liquidity into the new range
+    let new_liquidity = calculate_liquidity_from_token_amounts(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        amount_a,
+        amount_b,
+    )?;
+    if new_liquidity < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimum.into());
+    }
+
+    let liquidity_delta = convert_to_liquidity_delta(new_liquidity, true)?;
+
+    // The position has no liquidity at this point, so only its extension checkpoints are moved
+    // to the new range
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_for_modify_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.new_tick_array_lower,
+            &ctx.accounts.new_tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let tick_arrays = TickArraysMut::load(
+        &ctx.accounts.new_tick_array_lower,
+        &ctx.accounts.new_tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.new_tick_array_lower.to_account_info(),
+        ctx.accounts.new_tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.new_tick_array_lower,
+        &ctx.accounts.new_tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    let (deposited_a, deposited_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    // Return the tokens that could not be redeposited
+    let refund_a = amount_a
+        .checked_sub(deposited_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let refund_b = amount_b
+        .checked_sub(deposited_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+
+    let transfer_fee_excluded_refund_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, refund_a)?;
+    let transfer_fee_excluded_refund_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, refund_b)?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        refund_a,
+        transfer_memo::TRANSFER_MEMO_REPOSITION_POSITION.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        refund_b,
+        transfer_memo::TRANSFER_MEMO_REPOSITION_POSITION.as_bytes(),
+    )?;
+
+    emit!(PositionRepositioned {
+        solve: ctx.accounts.solve.key(),
+        position: ctx.accounts.position.key(),
+        old_tick_lower_index,
+        old_tick_upper_index,
+        new_tick_lower_index,
+        new_tick_upper_index,
+        old_liquidity,
+        new_liquidity,
+        fee_a_amount: fee_a,
+        fee_b_amount: fee_b,
+        token_a_refund_amount: refund_a,
+        token_b_refund_amount: refund_b,
+        token_a_transfer_fee: transfer_fee_excluded_refund_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_excluded_refund_b.transfer_fee,
+    });
+
+    Ok(())
+}
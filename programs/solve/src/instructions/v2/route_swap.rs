@@ -0,0 +1,842 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::*,
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+        swap_manager::PostSwapUpdate,
+    },
+    state::{
+        AdaptiveFeeInfo, OracleAccessor, PausableOperation, RewardSchedule, Solve, SolveTail,
+        NUM_REWARDS,
+    },
+    util::{
+        calculate_transfer_fee_excluded_amount, to_timestamp_u64, transfer_from_owner_to_vault_v2,
+        transfer_from_vault_to_owner_v2, verify_pool_and_config_not_paused,
+        SparseSwapTickSequenceBuilder, SwapTickSequence, MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN,
+    },
+};
+
+pub const MAX_ROUTE_SWAP_HOPS: usize = 4;
+
+// solve, token_mint_output, token_program_output, token_vault_input, token_vault_output,
+// tick_array_0, tick_array_1, tick_array_2, oracle, solves_config_extension
+pub const ROUTE_SWAP_HOP_ACCOUNTS_LEN: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RouteSwapHop {
+    pub a_to_b: bool,
+    pub sqrt_price_limit: u128,
+    // number of supplemental TickArray accounts that follow the hop accounts
+    pub supplemental_tick_arrays_len: u8,
+    // number of transfer hook accounts for the output mint of this hop
+    pub transfer_hook_output_len: u8,
+}
+
+#[derive(Accounts)]
+pub struct RouteSwapV2<'info> {
+    pub token_authority: Signer<'info>,
+
+    pub token_mint_input: InterfaceAccount<'info, Mint>,
+    #[account(address = *token_mint_input.to_account_info().owner)]
+    pub token_program_input: Interface<'info, TokenInterface>,
+
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    // the mint is checked against the output mint of the last hop in the handler
+    #[account(mut)]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_input
+    // - for each hop:
+    //   - [mut] solve
+    //   - token_mint_output
+    //   - token_program_output
+    //   - [mut] token_vault_input
+    //   - [mut] token_vault_output
+    //   - [mut] tick_array_0
+    //   - [mut] tick_array_1
+    //   - [mut] tick_array_2
+    //   - [mut] oracle
+    //   - solves_config_extension
+    //   - supplemental TickArray accounts
+    //   - accounts for transfer hook program of token_mint_output
+    // - reward extension accounts of the hop pools, each group starting with its SolveRewardExtension
+}
+
+struct RouteSwapHopAccounts<'info> {
+    solve: Box<Account<'info, Solve>>,
+    token_mint_output: InterfaceAccount<'info, Mint>,
+    token_program_output: Interface<'info, TokenInterface>,
+    token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+    tick_arrays: Vec<AccountInfo<'info>>,
+    supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
+    oracle: AccountInfo<'info>,
+    solves_config_extension: AccountInfo<'info>,
+    transfer_hook_output: Option<Vec<AccountInfo<'info>>>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RouteSwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    transfer_hook_input_len: u8,
+    hops: Vec<RouteSwapHop>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    if hops.is_empty() || hops.len() > MAX_ROUTE_SWAP_HOPS {
+        return Err(ErrorCode::InvalidRouteLength.into());
+    }
+
+    // Process remaining accounts
+    let mut remaining_accounts: &'info [AccountInfo<'info>] = ctx.remaining_accounts;
+    let transfer_hook_input =
+        take_optional_accounts(&mut remaining_accounts, transfer_hook_input_len)?;
+
+    let mut hop_accounts = parse_route_accounts(
+        &mut remaining_accounts,
+        &hops,
+        ctx.accounts.token_mint_input.key(),
+    )?;
+
+    let last_hop = hop_accounts.last().unwrap();
+    if ctx.accounts.token_owner_account_output.mint != last_hop.token_mint_output.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintTokenMint.into());
+    }
+
+    let swap_tick_sequence_builders: Vec<SparseSwapTickSequenceBuilder> = hop_accounts
+        .iter()
+        .map(|accounts| {
+            SparseSwapTickSequenceBuilder::new(
+                accounts.tick_arrays.clone(),
+                accounts.supplemental_tick_arrays.clone(),
+            )
+        })
+        .collect();
+    let mut swap_tick_sequences = Vec::with_capacity(hops.len());
+    for (builder, (accounts, hop)) in swap_tick_sequence_builders
+        .iter()
+        .zip(hop_accounts.iter().zip(hops.iter()))
+    {
+        swap_tick_sequences.push(builder.try_build(&accounts.solve, hop.a_to_b)?);
+    }
+
+    let mut oracle_accessors = Vec::with_capacity(hops.len());
+    let mut adaptive_fee_infos = Vec::with_capacity(hops.len());
+    let mut reward_schedules = Vec::with_capacity(hops.len());
+    for accounts in hop_accounts.iter() {
+        let oracle_accessor = OracleAccessor::new(&accounts.solve, accounts.oracle.clone())?;
+        if !oracle_accessor.is_trade_enabled(timestamp)? {
+            return Err(ErrorCode::TradeIsNotEnabled.into());
+        }
+        verify_pool_and_config_not_paused(
+            &accounts.solve,
+            &accounts.solves_config_extension,
+            PausableOperation::Swap,
+        )?;
+        adaptive_fee_infos.push(oracle_accessor.get_adaptive_fee_info()?);
+        reward_schedules.push(SolveTail::load(&accounts.solve.to_account_info())?.reward_schedules);
+        oracle_accessors.push(oracle_accessor);
+    }
+
+    let swap_updates = {
+        // The input mint of each hop followed by the output mint of the last hop
+        let mut token_mints: Vec<&InterfaceAccount<Mint>> = vec![&ctx.accounts.token_mint_input];
+        token_mints.extend(
+            hop_accounts
+                .iter()
+                .map(|accounts| &accounts.token_mint_output),
+        );
+        let solves: Vec<&Solve> = hop_accounts
+            .iter()
+            .map(|accounts| -> &Solve { &accounts.solve })
+            .collect();
+        compute_route_swap_updates(
+            &hops,
+            &solves,
+            &token_mints,
+            &mut swap_tick_sequences,
+            &reward_schedules,
+            &adaptive_fee_infos,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            timestamp,
+        )?
+    };
+
+    let last = hops.len() - 1;
+    let mut events: Vec<Traded> = Vec::with_capacity(hops.len());
+    for (i, hop) in hops.iter().enumerate() {
+        let swap_update = &swap_updates[i];
+        let hop_input_amount = input_amount(swap_update, hop.a_to_b);
+        let hop_output_amount = output_amount(swap_update, hop.a_to_b);
+        let input_transfer_fee = calculate_transfer_fee_excluded_amount(
+            hop_input_mint(&ctx.accounts.token_mint_input, &hop_accounts, i),
+            hop_input_amount,
+        )?
+        .transfer_fee;
+        let output_transfer_fee = calculate_transfer_fee_excluded_amount(
+            &hop_accounts[i].token_mint_output,
+            hop_output_amount,
+        )?
+        .transfer_fee;
+
+        oracle_accessors[i].update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+
+        let solve = &mut hop_accounts[i].solve;
+
+        // The reward extension accrues rewards with the liquidity before the swap
+        if let Some(mut reward_extension) =
+            RewardExtensionAccounts::load_from_remaining_accounts(solve, remaining_accounts)?
+        {
+            reward_extension.update_for_swap(
+                solve,
+                &swap_update.crossed_tick_indexes,
+                hop.a_to_b,
+                timestamp,
+            )?;
+        }
+
+        SolveTail::track_reward_emissions(solve, &swap_update.next_reward_infos)?;
+        oracle_accessors[i].record_observation(
+            timestamp,
+            solve.tick_current_index,
+            solve.liquidity,
+        )?;
+        fill_limit_orders(
+            &oracle_accessors[i],
+            solve,
+            &mut swap_tick_sequences[i],
+            swap_update,
+            hop.a_to_b,
+        )?;
+
+        let pre_sqrt_price = solve.sqrt_price;
+        solve.update_after_swap(
+            swap_update.next_liquidity,
+            swap_update.next_tick_index,
+            swap_update.next_sqrt_price,
+            swap_update.next_fee_growth_global,
+            swap_update.next_reward_infos,
+            swap_update.next_protocol_fee,
+            hop.a_to_b,
+            timestamp,
+        );
+
+        events.push(Traded {
+            solve: solve.key(),
+            a_to_b: hop.a_to_b,
+            pre_sqrt_price,
+            post_sqrt_price: solve.sqrt_price,
+            input_amount: hop_input_amount,
+            output_amount: hop_output_amount,
+            input_transfer_fee,
+            output_transfer_fee,
+            lp_fee: swap_update.lp_fee,
+            protocol_fee: swap_update.next_protocol_fee,
+            referral_fee: 0,
+        });
+    }
+
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        &hop_accounts[0].token_vault_input,
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &transfer_hook_input,
+        input_amount(&swap_updates[0], hops[0].a_to_b),
+    )?;
+
+    // Transfer from pool to pool, and from the last pool to the owner
+    for (i, hop) in hops.iter().enumerate() {
+        let accounts = &hop_accounts[i];
+        let destination = if i == last {
+            &ctx.accounts.token_owner_account_output
+        } else {
+            &hop_accounts[i + 1].token_vault_input
+        };
+        transfer_from_vault_to_owner_v2(
+            &accounts.solve,
+            &accounts.token_mint_output,
+            &accounts.token_vault_output,
+            destination,
+            &accounts.token_program_output,
+            &ctx.accounts.memo_program,
+            &accounts.transfer_hook_output,
+            output_amount(&swap_updates[i], hop.a_to_b),
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+    }
+
+    // Solve accounts are not part of the Accounts struct, so they must be persisted manually
+    for accounts in hop_accounts.iter() {
+        accounts.solve.exit(&crate::ID)?;
+    }
+
+    for event in events {
+        emit!(event);
+    }
+
+    Ok(())
+}
+
+fn parse_route_accounts<'info>(
+    remaining_accounts: &mut &'info [AccountInfo<'info>],
+    hops: &[RouteSwapHop],
+    token_mint_input: Pubkey,
+) -> Result<Vec<RouteSwapHopAccounts<'info>>> {
+    let mut hop_accounts: Vec<RouteSwapHopAccounts<'info>> = Vec::with_capacity(hops.len());
+    for (i, hop) in hops.iter().enumerate() {
+        let input_mint = match hop_accounts.last() {
+            Some(prev) => prev.token_mint_output.key(),
+            None => token_mint_input,
+        };
+        let accounts = parse_hop_accounts(remaining_accounts, hop, input_mint, i == 0)?;
+
+        // Don't allow swaps on the same solve twice
+        if hop_accounts
+            .iter()
+            .any(|prev| prev.solve.key() == accounts.solve.key())
+        {
+            return Err(ErrorCode::DuplicateRoutePool.into());
+        }
+
+        hop_accounts.push(accounts);
+    }
+    Ok(hop_accounts)
+}
+
+// Computes the swap of each hop and checks the slippage of the route, without updating any account.
+// Swap updates stay boxed to keep them off the stack.
+#[allow(clippy::too_many_arguments, clippy::vec_box)]
+fn compute_route_swap_updates(
+    hops: &[RouteSwapHop],
+    solves: &[&Solve],
+    token_mints: &[&InterfaceAccount<Mint>],
+    swap_tick_sequences: &mut [SwapTickSequence],
+    reward_schedules: &[[RewardSchedule; NUM_REWARDS]],
+    adaptive_fee_infos: &[Option<AdaptiveFeeInfo>],
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    timestamp: u64,
+) -> Result<Vec<Box<PostSwapUpdate>>> {
+    let mut swap_updates: Vec<Box<PostSwapUpdate>> = Vec::with_capacity(hops.len());
+    if amount_specified_is_input {
+        // exact-in: the swap calculations occur from the first hop to the last hop
+        // and the output of each hop is the input of the next hop.
+        // We use vault to vault transfer, so transfer fee will be collected once.
+        let mut hop_amount = amount;
+        for (i, hop) in hops.iter().enumerate() {
+            let (token_mint_a, token_mint_b) =
+                order_mints(token_mints[i], token_mints[i + 1], hop.a_to_b);
+            let swap_update = swap_with_transfer_fee_extension(
+                solves[i],
+                token_mint_a,
+                token_mint_b,
+                &mut swap_tick_sequences[i],
+                hop_amount,
+                hop.sqrt_price_limit,
+                amount_specified_is_input, // true
+                hop.a_to_b,
+                &reward_schedules[i],
+                timestamp,
+                &adaptive_fee_infos[i],
+            )?;
+            hop_amount = output_amount(&swap_update, hop.a_to_b);
+            swap_updates.push(swap_update);
+        }
+    } else {
+        // exact-out: the swap calculations occur from the last hop to the first hop,
+        // but the actual swaps occur from the first hop to the last hop (to ensure that the intermediate token exists in the vault)
+        let mut hop_amount = amount;
+        for (i, hop) in hops.iter().enumerate().rev() {
+            let (token_mint_a, token_mint_b) =
+                order_mints(token_mints[i], token_mints[i + 1], hop.a_to_b);
+            let swap_update = swap_with_transfer_fee_extension(
+                solves[i],
+                token_mint_a,
+                token_mint_b,
+                &mut swap_tick_sequences[i],
+                hop_amount,
+                hop.sqrt_price_limit,
+                amount_specified_is_input, // false
+                hop.a_to_b,
+                &reward_schedules[i],
+                timestamp,
+                &adaptive_fee_infos[i],
+            )?;
+            // The output of the previous hop is the input of this hop
+            hop_amount = calculate_transfer_fee_excluded_amount(
+                token_mints[i],
+                input_amount(&swap_update, hop.a_to_b),
+            )?
+            .amount;
+            swap_updates.push(swap_update);
+        }
+        swap_updates.reverse();
+    }
+
+    // All output token of each hop should be consumed by the next hop
+    for i in 1..hops.len() {
+        if output_amount(&swap_updates[i - 1], hops[i - 1].a_to_b)
+            != input_amount(&swap_updates[i], hops[i].a_to_b)
+        {
+            return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
+        }
+    }
+
+    let last = hops.len() - 1;
+    if amount_specified_is_input {
+        // The slippage we care about is the output of the last hop.
+        let route_output_amount = calculate_transfer_fee_excluded_amount(
+            token_mints[last + 1],
+            output_amount(&swap_updates[last], hops[last].a_to_b),
+        )?
+        .amount;
+        if route_output_amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else {
+        // The slippage we care about is the input of the first hop.
+        let route_input_amount = input_amount(&swap_updates[0], hops[0].a_to_b);
+        if route_input_amount > other_amount_threshold {
+            return Err(ErrorCode::AmountInAboveMaximum.into());
+        }
+    }
+
+    Ok(swap_updates)
+}
+
+fn parse_hop_accounts<'info>(
+    remaining_accounts: &mut &'info [AccountInfo<'info>],
+    hop: &RouteSwapHop,
+    input_mint: Pubkey,
+    is_first_hop: bool,
+) -> Result<RouteSwapHopAccounts<'info>> {
+    let accounts = take_accounts(remaining_accounts, ROUTE_SWAP_HOP_ACCOUNTS_LEN)?;
+
+    if !accounts[0].is_writable {
+        return Err(anchor_lang::error::ErrorCode::ConstraintMut.into());
+    }
+    let solve = Box::new(Account::<Solve>::try_from(&accounts[0])?);
+    if solve.input_token_mint(hop.a_to_b) != input_mint {
+        return Err(if is_first_hop {
+            anchor_lang::error::ErrorCode::ConstraintAddress.into()
+        } else {
+            ErrorCode::InvalidIntermediaryMint.into()
+        });
+    }
+
+    let token_mint_output = InterfaceAccount::<Mint>::try_from(&accounts[1])?;
+    if token_mint_output.key() != solve.output_token_mint(hop.a_to_b) {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    let token_program_output = Interface::<TokenInterface>::try_from(&accounts[2])?;
+    if token_program_output.key() != *accounts[1].owner {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    let token_vault_input = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?);
+    if token_vault_input.key() != solve.input_token_vault(hop.a_to_b) {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+    let token_vault_output = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[4])?);
+    if token_vault_output.key() != solve.output_token_vault(hop.a_to_b) {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    let tick_arrays = accounts[5..8].to_vec();
+
+    let (oracle_address, _) =
+        Pubkey::find_program_address(&[b"oracle", solve.key().as_ref()], &crate::ID);
+    if accounts[8].key() != oracle_address {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+    let oracle = accounts[8].clone();
+
+    let (config_extension_address, _) = Pubkey::find_program_address(
+        &[b"config_extension", solve.solves_config.as_ref()],
+        &crate::ID,
+    );
+    if accounts[9].key() != config_extension_address {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+    let solves_config_extension = accounts[9].clone();
+
+    if hop.supplemental_tick_arrays_len as usize > MAX_SUPPLEMENTAL_TICK_ARRAYS_LEN {
+        return Err(ErrorCode::TooManySupplementalTickArrays.into());
+    }
+    let supplemental_tick_arrays =
+        take_optional_accounts(remaining_accounts, hop.supplemental_tick_arrays_len)?;
+    let transfer_hook_output =
+        take_optional_accounts(remaining_accounts, hop.transfer_hook_output_len)?;
+
+    Ok(RouteSwapHopAccounts {
+        solve,
+        token_mint_output,
+        token_program_output,
+        token_vault_input,
+        token_vault_output,
+        tick_arrays,
+        supplemental_tick_arrays,
+        oracle,
+        solves_config_extension,
+        transfer_hook_output,
+    })
+}
+
+fn take_accounts<'info>(
+    remaining_accounts: &mut &'info [AccountInfo<'info>],
+    len: usize,
+) -> Result<&'info [AccountInfo<'info>]> {
+    if remaining_accounts.len() < len {
+        return Err(ErrorCode::RemainingAccountsInsufficient.into());
+    }
+    let (accounts, rest) = remaining_accounts.split_at(len);
+    *remaining_accounts = rest;
+    Ok(accounts)
+}
+
+fn take_optional_accounts<'info>(
+    remaining_accounts: &mut &'info [AccountInfo<'info>],
+    len: u8,
+) -> Result<Option<Vec<AccountInfo<'info>>>> {
+    if len == 0 {
+        return Ok(None);
+    }
+    Ok(Some(
+        take_accounts(remaining_accounts, len as usize)?.to_vec(),
+    ))
+}
+
+fn hop_input_mint<'a, 'info>(
+    token_mint_input: &'a InterfaceAccount<'info, Mint>,
+    hop_accounts: &'a [RouteSwapHopAccounts<'info>],
+    hop_index: usize,
+) -> &'a InterfaceAccount<'info, Mint> {
+    if hop_index == 0 {
+        token_mint_input
+    } else {
+        &hop_accounts[hop_index - 1].token_mint_output
+    }
+}
+
+fn order_mints<'a, 'info>(
+    input_mint: &'a InterfaceAccount<'info, Mint>,
+    output_mint: &'a InterfaceAccount<'info, Mint>,
+    a_to_b: bool,
+) -> (
+    &'a InterfaceAccount<'info, Mint>,
+    &'a InterfaceAccount<'info, Mint>,
+) {
+    if a_to_b {
+        (input_mint, output_mint)
+    } else {
+        (output_mint, input_mint)
+    }
+}
+
+fn input_amount(swap_update: &PostSwapUpdate, a_to_b: bool) -> u64 {
+    if a_to_b {
+        swap_update.amount_a
+    } else {
+        swap_update.amount_b
+    }
+}
+
+fn output_amount(swap_update: &PostSwapUpdate, a_to_b: bool) -> u64 {
+    if a_to_b {
+        swap_update.amount_b
+    } else {
+        swap_update.amount_a
+    }
+}
+
+#[cfg(test)]
+mod route_swap_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+    use crate::state::FixedTickArray;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_lang::Discriminator;
+    use anchor_spl::token::spl_token;
+
+    const TICK_SPACING: u16 = 64;
+    const TICK_CURRENT_INDEX: i32 = 2_048;
+
+    // The route accounts are borrowed for 'info, so they are leaked to live for the whole test
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        is_writable: bool,
+        executable: bool,
+    ) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            is_writable,
+            Box::leak(Box::new(1_000_000)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            executable,
+            0,
+        )
+    }
+
+    fn solve_account_info(solve_key: Pubkey, solve: Solve) -> AccountInfo<'static> {
+        let mut data = vec![0u8; Solve::LEN];
+        solve.try_serialize(&mut data.as_mut_slice()).unwrap();
+        account_info(solve_key, crate::ID, data, true, false)
+    }
+
+    fn new_solve(token_mint_a: Pubkey, token_mint_b: Pubkey) -> Solve {
+        Solve {
+            solves_config: Pubkey::new_unique(),
+            tick_spacing: TICK_SPACING,
+            fee_rate: 3_000,
+            liquidity: 1_000_000_000_000,
+            sqrt_price: sqrt_price_from_tick_index(TICK_CURRENT_INDEX),
+            tick_current_index: TICK_CURRENT_INDEX,
+            token_mint_a,
+            token_vault_a: Pubkey::new_unique(),
+            token_mint_b,
+            token_vault_b: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    // A fixed tick array starting at tick 0 without initialized ticks
+    fn tick_array_account_info(solve_key: Pubkey) -> AccountInfo<'static> {
+        let mut data = vec![0u8; FixedTickArray::LEN];
+        data[0..8].copy_from_slice(&FixedTickArray::discriminator());
+        data[9956..9988].copy_from_slice(&solve_key.to_bytes());
+        account_info(Pubkey::new_unique(), crate::ID, data, true, false)
+    }
+
+    fn mint_account_info(key: Pubkey) -> AccountInfo<'static> {
+        let mint = spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        mint.pack_into_slice(&mut data);
+        account_info(key, spl_token::ID, data, false, false)
+    }
+
+    fn token_account_info(key: Pubkey, mint: Pubkey) -> AccountInfo<'static> {
+        let token_account = spl_token::state::Account {
+            mint,
+            owner: Pubkey::new_unique(),
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        token_account.pack_into_slice(&mut data);
+        account_info(key, spl_token::ID, data, true, false)
+    }
+
+    fn hop(a_to_b: bool) -> RouteSwapHop {
+        RouteSwapHop {
+            a_to_b,
+            sqrt_price_limit: 0,
+            supplemental_tick_arrays_len: 0,
+            transfer_hook_output_len: 0,
+        }
+    }
+
+    // The ROUTE_SWAP_HOP_ACCOUNTS_LEN accounts of a hop
+    fn hop_account_infos(
+        solve_key: Pubkey,
+        solve: &Solve,
+        a_to_b: bool,
+    ) -> Vec<AccountInfo<'static>> {
+        let (token_mint_output, token_vault_input, token_vault_output) = if a_to_b {
+            (solve.token_mint_b, solve.token_vault_a, solve.token_vault_b)
+        } else {
+            (solve.token_mint_a, solve.token_vault_b, solve.token_vault_a)
+        };
+        let token_mint_input = solve.input_token_mint(a_to_b);
+        let (oracle, _) =
+            Pubkey::find_program_address(&[b"oracle", solve_key.as_ref()], &crate::ID);
+        let (solves_config_extension, _) = Pubkey::find_program_address(
+            &[b"config_extension", solve.solves_config.as_ref()],
+            &crate::ID,
+        );
+        vec![
+            solve_account_info(solve_key, solve.clone()),
+            mint_account_info(token_mint_output),
+            account_info(spl_token::ID, Pubkey::default(), vec![], false, true),
+            token_account_info(token_vault_input, token_mint_input),
+            token_account_info(token_vault_output, token_mint_output),
+            tick_array_account_info(solve_key),
+            tick_array_account_info(solve_key),
+            tick_array_account_info(solve_key),
+            account_info(oracle, System::id(), vec![], true, false),
+            account_info(solves_config_extension, crate::ID, vec![], false, false),
+        ]
+    }
+
+    #[test]
+    fn test_parse_route_accounts() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let (solve_ab_key, solve_ab) = (Pubkey::new_unique(), new_solve(mint_a, mint_b));
+        let (solve_cb_key, solve_cb) = (Pubkey::new_unique(), new_solve(mint_c, mint_b));
+
+        // A -> B -> C
+        let mut account_infos = hop_account_infos(solve_ab_key, &solve_ab, true);
+        account_infos.extend(hop_account_infos(solve_cb_key, &solve_cb, false));
+        let mut remaining_accounts: &'static [AccountInfo<'static>] =
+            Box::leak(account_infos.into_boxed_slice());
+        let hop_accounts =
+            parse_route_accounts(&mut remaining_accounts, &[hop(true), hop(false)], mint_a)
+                .unwrap();
+        assert!(remaining_accounts.is_empty());
+        assert_eq!(hop_accounts[0].solve.key(), solve_ab_key);
+        assert_eq!(hop_accounts[0].token_mint_output.key(), mint_b);
+        assert_eq!(hop_accounts[1].solve.key(), solve_cb_key);
+        assert_eq!(hop_accounts[1].token_mint_output.key(), mint_c);
+    }
+
+    #[test]
+    fn test_parse_route_accounts_duplicate_pool() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (solve_key, solve) = (Pubkey::new_unique(), new_solve(mint_a, mint_b));
+
+        // A -> B -> A on the same pool
+        let mut account_infos = hop_account_infos(solve_key, &solve, true);
+        account_infos.extend(hop_account_infos(solve_key, &solve, false));
+        let mut remaining_accounts: &'static [AccountInfo<'static>] =
+            Box::leak(account_infos.into_boxed_slice());
+        let result =
+            parse_route_accounts(&mut remaining_accounts, &[hop(true), hop(false)], mint_a);
+        assert_eq!(
+            result.map(|_| ()).unwrap_err(),
+            ErrorCode::DuplicateRoutePool.into()
+        );
+    }
+
+    // Computes a route of a_to_b swaps through num_hops pools
+    #[allow(clippy::vec_box)]
+    fn compute_route(
+        num_hops: usize,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+    ) -> Result<Vec<Box<PostSwapUpdate>>> {
+        let hops: Vec<RouteSwapHop> = (0..num_hops).map(|_| hop(true)).collect();
+        let mut solve_accounts = Vec::with_capacity(num_hops);
+        let mut builders = Vec::with_capacity(num_hops);
+        for _ in 0..num_hops {
+            let solve_key = Pubkey::new_unique();
+            let solve = new_solve(Pubkey::new_unique(), Pubkey::new_unique());
+            let solve_info: &'static AccountInfo<'static> =
+                Box::leak(Box::new(solve_account_info(solve_key, solve)));
+            solve_accounts.push(Account::<Solve>::try_from(solve_info)?);
+            builders.push(SparseSwapTickSequenceBuilder::new(
+                vec![tick_array_account_info(solve_key)],
+                None,
+            ));
+        }
+        let mint_infos: Vec<&'static AccountInfo<'static>> = (0..=num_hops)
+            .map(|_| &*Box::leak(Box::new(mint_account_info(Pubkey::new_unique()))))
+            .collect();
+        let mints = mint_infos
+            .into_iter()
+            .map(InterfaceAccount::<Mint>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut swap_tick_sequences = Vec::with_capacity(num_hops);
+        for (builder, solve) in builders.iter().zip(solve_accounts.iter()) {
+            swap_tick_sequences.push(builder.try_build(solve, true)?);
+        }
+        let solves: Vec<&Solve> = solve_accounts
+            .iter()
+            .map(|solve| -> &Solve { solve })
+            .collect();
+        let token_mints: Vec<&InterfaceAccount<Mint>> = mints.iter().collect();
+        compute_route_swap_updates(
+            &hops,
+            &solves,
+            &token_mints,
+            &mut swap_tick_sequences,
+            &vec![SolveTail::default().reward_schedules; num_hops],
+            &vec![None; num_hops],
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            1_000,
+        )
+    }
+
+    #[test]
+    fn test_compute_route_exact_in() {
+        for num_hops in 1..=MAX_ROUTE_SWAP_HOPS {
+            let swap_updates = compute_route(num_hops, 1_000_000, 0, true).unwrap();
+            assert_eq!(swap_updates.len(), num_hops);
+            assert_eq!(swap_updates[0].amount_a, 1_000_000);
+            // The output of each hop is the input of the next hop
+            for i in 1..num_hops {
+                assert_eq!(swap_updates[i].amount_a, swap_updates[i - 1].amount_b);
+            }
+            assert!(swap_updates[num_hops - 1].amount_b > 0);
+        }
+    }
+
+    #[test]
+    fn test_compute_route_exact_in_threshold() {
+        let swap_updates = compute_route(3, 1_000_000, 0, true).unwrap();
+        let output_amount = swap_updates[2].amount_b;
+        assert!(compute_route(3, 1_000_000, output_amount, true).is_ok());
+        assert_eq!(
+            compute_route(3, 1_000_000, output_amount + 1, true).unwrap_err(),
+            ErrorCode::AmountOutBelowMinimum.into()
+        );
+    }
+
+    #[test]
+    fn test_compute_route_exact_out() {
+        for num_hops in 1..=MAX_ROUTE_SWAP_HOPS {
+            let swap_updates = compute_route(num_hops, 1_000_000, u64::MAX, false).unwrap();
+            assert_eq!(swap_updates.len(), num_hops);
+            assert_eq!(swap_updates[num_hops - 1].amount_b, 1_000_000);
+            // The input of each hop is the output of the previous hop
+            for i in 1..num_hops {
+                assert_eq!(swap_updates[i].amount_a, swap_updates[i - 1].amount_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_route_exact_out_threshold() {
+        let swap_updates = compute_route(3, 1_000_000, u64::MAX, false).unwrap();
+        let input_amount = swap_updates[0].amount_a;
+        assert!(compute_route(3, 1_000_000, input_amount, false).is_ok());
+        assert_eq!(
+            compute_route(3, 1_000_000, input_amount - 1, false).unwrap_err(),
+            ErrorCode::AmountInAboveMaximum.into()
+        );
+    }
+}
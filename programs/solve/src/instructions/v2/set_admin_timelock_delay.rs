@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetAdminTimelockDelay<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+/// Set the admin timelock delay. Only the fee authority has permission to invoke this instruction.
+/// Once the timelock is enabled, the delay can only be changed through the queue.
+pub fn handler(ctx: Context<SetAdminTimelockDelay>, admin_timelock_delay: u64) -> Result<()> {
+    let solves_config_extension = &mut ctx.accounts.solves_config_extension;
+    if solves_config_extension.is_admin_timelock_enabled() {
+        return Err(ErrorCode::AdminTimelockEnabled.into());
+    }
+    solves_config_extension.update_admin_timelock_delay(admin_timelock_delay)
+}
@@ -16,10 +16,13 @@ pub struct SetConfigExtensionAuthority<'info> {
     pub new_config_extension_authority: UncheckedAccount<'info>,
 }
 
-/// Set the config extension authority. Only the current config extension authority has permission to invoke this instruction.
+/// Nominate the new config extension authority. Only the current config extension authority has permission to invoke this instruction.
+/// The nominated key becomes the config extension authority once it signs accept_config_extension_authority.
 pub fn handler(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
     ctx.accounts
         .solves_config_extension
-        .update_config_extension_authority(ctx.accounts.new_config_extension_authority.key());
+        .update_pending_config_extension_authority(
+            ctx.accounts.new_config_extension_authority.key(),
+        );
     Ok(())
 }
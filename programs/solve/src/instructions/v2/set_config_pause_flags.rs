@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetConfigPauseFlags<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.pause_authority)]
+    pub pause_authority: Signer<'info>,
+}
+
+/// Set the pause flags for all pools in the config. Only the pause authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetConfigPauseFlags>, pause_flags: u8) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .update_pause_flags(pause_flags)?;
+
+    emit!(PauseFlagsUpdated {
+        solves_config: ctx.accounts.solves_config.key(),
+        solve: None,
+        pause_flags,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::SolveRewardExtension;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetExtensionRewardAuthority<'info> {
+    #[account(mut)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_reward_authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetExtensionRewardAuthority>, reward_index: u8) -> Result<()> {
+    ctx.accounts.solve_reward_extension.update_reward_authority(
+        reward_index as usize,
+        ctx.accounts.new_reward_authority.key(),
+    )
+}
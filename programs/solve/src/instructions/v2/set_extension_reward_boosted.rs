@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::SolveRewardExtension;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetExtensionRewardBoosted<'info> {
+    #[account(mut)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetExtensionRewardBoosted>,
+    reward_index: u8,
+    boosted: bool,
+) -> Result<()> {
+    ctx.accounts
+        .solve_reward_extension
+        .update_reward_boosted(reward_index as usize, boosted)
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::solve_manager::next_solve_extension_reward_infos;
+use crate::math::checked_mul_shift_right;
+use crate::state::{Solve, SolveRewardExtension};
+use crate::util::to_timestamp_u64;
+
+const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetExtensionRewardEmissions<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(mut, has_one = solve)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = solve_reward_extension.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+// Not paused: the reward authority must be able to reduce or stop emissions while the pool is paused.
+pub fn handler(
+    ctx: Context<SetExtensionRewardEmissions>,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+) -> Result<()> {
+    let reward_vault = &ctx.accounts.reward_vault;
+
+    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
+    if reward_vault.amount < emissions_per_day {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let next_reward_infos = next_solve_extension_reward_infos(
+        &ctx.accounts.solve,
+        &ctx.accounts.solve_reward_extension,
+        timestamp,
+    )?;
+
+    ctx.accounts.solve_reward_extension.update_emissions(
+        reward_index as usize,
+        next_reward_infos,
+        timestamp,
+        emissions_per_second_x64,
+    )
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{LockBoostConfig, Solve, SolveRewardExtension};
+
+#[derive(Accounts)]
+pub struct SetLockBoostConfig<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(mut, has_one = solve)]
+    pub solve_reward_extension: Account<'info, SolveRewardExtension>,
+
+    #[account(address = solve.reward_infos[0].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+/// Sets the boost of the liquidity of locked positions for the boosted rewards of the reward extension.
+///
+/// The boosts of the positions are not updated by this instruction,
+/// they follow the new config once `update_position_boost` is called for them.
+pub fn handler(
+    ctx: Context<SetLockBoostConfig>,
+    permanent_boost_bps: u16,
+    max_timed_boost_bps: u16,
+    max_boost_duration: u64,
+) -> Result<()> {
+    let lock_boost_config =
+        LockBoostConfig::new(permanent_boost_bps, max_timed_boost_bps, max_boost_duration)?;
+    ctx.accounts
+        .solve_reward_extension
+        .update_lock_boost_config(lock_boost_config);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetPauseAuthority<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_pause_authority: UncheckedAccount<'info>,
+}
+
+/// Nominate the new pause authority. Only the config extension authority has permission to invoke this instruction.
+/// The nominated key becomes the pause authority once it signs accept_pause_authority.
+pub fn handler(ctx: Context<SetPauseAuthority>) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .update_pending_pause_authority(ctx.accounts.new_pause_authority.key());
+    Ok(())
+}
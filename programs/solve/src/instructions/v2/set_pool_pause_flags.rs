@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::state::{Solve, SolveTail, SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetPoolPauseFlags<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solves_config_extension.pause_authority)]
+    pub pause_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the pause flags for a single pool. Only the pause authority has permission to invoke this instruction.
+///
+/// The flags are kept in the tail of the Solve account, so that every instruction of the pool
+/// can check them without taking another account. The tail is allocated if needed.
+pub fn handler(ctx: Context<SetPoolPauseFlags>, pause_flags: u8) -> Result<()> {
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let solve_account_info = ctx.accounts.solve.to_account_info();
+    let mut solve_tail = SolveTail::load(&solve_account_info)?;
+    solve_tail.update_pause_flags(pause_flags)?;
+    solve_tail.store(&solve_account_info)?;
+
+    emit!(PauseFlagsUpdated {
+        solves_config: ctx.accounts.solves_config.key(),
+        solve: Some(ctx.accounts.solve.key()),
+        pause_flags,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ProtocolFeeRecipient, SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeRecipients<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config.collect_protocol_fees_authority)]
+    pub collect_protocol_fees_authority: Signer<'info>,
+}
+
+/// Set the protocol fee distribution table. Only the collect protocol fees authority has permission to invoke this instruction.
+pub fn handler(
+    ctx: Context<SetProtocolFeeRecipients>,
+    protocol_fee_recipients: Vec<ProtocolFeeRecipient>,
+) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .update_protocol_fee_recipients(&protocol_fee_recipients)
+}
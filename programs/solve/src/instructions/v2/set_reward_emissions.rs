@@ -4,7 +4,7 @@ use anchor_spl::token_interface::TokenAccount;
 use crate::errors::ErrorCode;
 use crate::manager::solve_manager::next_solve_reward_infos;
 use crate::math::checked_mul_shift_right;
-use crate::state::Solve;
+use crate::state::{Solve, SolveTail};
 use crate::util::to_timestamp_u64;
 
 const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
@@ -22,6 +22,7 @@ pub struct SetRewardEmissionsV2<'info> {
     pub reward_vault: InterfaceAccount<'info, TokenAccount>,
 }
 
+// Not paused: the reward authority must be able to reduce or stop emissions while the pool is paused.
 pub fn handler(
     ctx: Context<SetRewardEmissionsV2>,
     reward_index: u8,
@@ -37,7 +38,10 @@ pub fn handler(
 
     let clock = Clock::get()?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
-    let next_reward_infos = next_solve_reward_infos(solve, timestamp)?;
+    // The emission window of the reward is kept, only the emission rate is updated
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
+    let next_reward_infos = next_solve_reward_infos(solve, &reward_schedules, timestamp)?;
+    SolveTail::track_reward_emissions(solve, &next_reward_infos)?;
 
     ctx.accounts.solve.update_emissions(
         reward_index as usize,
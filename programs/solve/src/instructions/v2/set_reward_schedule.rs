@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::errors::ErrorCode;
+use crate::manager::solve_manager::next_solve_reward_infos;
+use crate::math::checked_mul_shift_right;
+use crate::state::{RewardSchedule, Solve, SolveTail};
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardSchedule<'info> {
+    #[account(mut)]
+    pub solve: Account<'info, Solve>,
+
+    #[account(address = solve.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = solve.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the emission rate of a reward together with the window in which it is emitted.
+///
+/// The reward vault must hold the rewards emitted for the rest of the window,
+/// so that a campaign can be funded exactly and stops on its own at the end timestamp.
+/// The schedule is kept in the tail of the Solve account, which is allocated on first use.
+pub fn handler(
+    ctx: Context<SetRewardSchedule>,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let reward_schedule = RewardSchedule::new(start_timestamp, end_timestamp)?;
+    if end_timestamp <= timestamp {
+        return Err(ErrorCode::InvalidRewardSchedule.into());
+    }
+
+    let emission_duration = reward_schedule.emission_duration(timestamp, end_timestamp);
+    let emissions_remaining =
+        checked_mul_shift_right(u128::from(emission_duration), emissions_per_second_x64)?;
+    if ctx.accounts.reward_vault.amount < emissions_remaining {
+        return Err(ErrorCode::RewardVaultAmountInsufficient.into());
+    }
+
+    let solve_account_info = ctx.accounts.solve.to_account_info();
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    let mut solve_tail = SolveTail::load(&solve_account_info)?;
+
+    // Rewards accrued so far follow the previous schedule
+    let solve = &mut ctx.accounts.solve;
+    let next_reward_infos =
+        next_solve_reward_infos(solve, &solve_tail.reward_schedules, timestamp)?;
+    solve_tail.record_reward_emissions(&solve.reward_emitted_amounts(&next_reward_infos));
+
+    solve.update_emissions(
+        reward_index as usize,
+        next_reward_infos,
+        timestamp,
+        emissions_per_second_x64,
+    )?;
+    solve_tail.reward_schedules[reward_index as usize] = reward_schedule;
+    solve_tail.store(&solve_account_info)?;
+
+    Ok(())
+}
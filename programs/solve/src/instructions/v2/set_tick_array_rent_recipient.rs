@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SetTickArrayRentRecipient<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(address = solves_config_extension.config_extension_authority)]
+    pub config_extension_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new recipient can be arbitrary
+    pub new_tick_array_rent_recipient: UncheckedAccount<'info>,
+}
+
+/// Set the recipient of the rent of closed tick arrays. Only the config extension authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetTickArrayRentRecipient>) -> Result<()> {
+    ctx.accounts
+        .solves_config_extension
+        .update_tick_array_rent_recipient(ctx.accounts.new_tick_array_rent_recipient.key());
+    Ok(())
+}
@@ -16,10 +16,11 @@ pub struct SetTokenBadgeAuthority<'info> {
     pub new_token_badge_authority: UncheckedAccount<'info>,
 }
 
-/// Set the token badge authority. Only the config extension authority has permission to invoke this instruction.
+/// Nominate the new token badge authority. Only the config extension authority has permission to invoke this instruction.
+/// The nominated key becomes the token badge authority once it signs accept_token_badge_authority.
 pub fn handler(ctx: Context<SetTokenBadgeAuthority>) -> Result<()> {
     ctx.accounts
         .solves_config_extension
-        .update_token_badge_authority(ctx.accounts.new_token_badge_authority.key());
+        .update_pending_token_badge_authority(ctx.accounts.new_token_badge_authority.key());
     Ok(())
 }
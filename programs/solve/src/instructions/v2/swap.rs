@@ -6,12 +6,18 @@ use crate::{
     constants::transfer_memo,
     errors::ErrorCode,
     events::*,
-    manager::swap_manager::*,
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+        swap_manager::*,
+    },
+    math::{get_referral_fee_amount, get_referral_fee_excluded_amount},
     state::*,
     util::{
         calculate_transfer_fee_excluded_amount, calculate_transfer_fee_included_amount,
-        parse_remaining_accounts, to_timestamp_u64, v2::update_and_swap_solve_v2, AccountsType,
-        RemainingAccountsInfo, SparseSwapTickSequenceBuilder, SwapTickSequence,
+        load_referral_token_account, parse_remaining_accounts, to_timestamp_u64,
+        transfer_referral_fee_v2, v2::update_and_swap_solve_v2, verify_pool_not_paused,
+        verify_referral_fee, AccountsType, RemainingAccountsInfo, SparseSwapTickSequenceBuilder,
+        SwapTickSequence,
     },
 };
 
@@ -63,15 +69,20 @@ pub struct SwapV2<'info> {
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
     // - supplemental TickArray accounts
+    // - reward extension accounts (SolveRewardExtension and TickArrayRewardExtension of crossed ticks)
+    // - referral token account receiving the referral fee in the input token (swap_with_referral_v2)
+    // - accounts for transfer hook program of the input token mint to the referral token account
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
-    ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
+    ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool, // Zero for one
+    referral_fee_rate: u16,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     let solve = &mut ctx.accounts.solve;
@@ -87,9 +98,20 @@ pub fn handler<'info>(
             AccountsType::TransferHookA,
             AccountsType::TransferHookB,
             AccountsType::SupplementalTickArrays,
+            AccountsType::RewardExtension,
+            AccountsType::ReferralTokenAccount,
+            AccountsType::TransferHookReferral,
         ],
     )?;
 
+    let referral_token_account =
+        load_referral_token_account(ctx.remaining_accounts, &remaining_accounts_info)?;
+    verify_referral_fee(
+        referral_token_account.as_ref(),
+        &solve.input_token_mint(a_to_b),
+        referral_fee_rate,
+    )?;
+
     let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
         vec![
             ctx.accounts.tick_array_0.to_account_info(),
@@ -104,21 +126,40 @@ pub fn handler<'info>(
     if !oracle_accessor.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve, PausableOperation::Swap)?;
     let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
+
+    // The referral fee is charged on top of the swap input, so less input is left for the swap (ExactIn)
+    let swap_amount = if amount_specified_is_input {
+        get_referral_fee_excluded_amount(amount, referral_fee_rate)
+    } else {
+        amount
+    };
 
     let swap_update = swap_with_transfer_fee_extension(
         solve,
         &ctx.accounts.token_mint_a,
         &ctx.accounts.token_mint_b,
         &mut swap_tick_sequence,
-        amount,
+        swap_amount,
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
+        &reward_schedules,
         timestamp,
         &adaptive_fee_info,
     )?;
 
+    let referral_fee = get_referral_fee_amount(
+        if a_to_b {
+            swap_update.amount_a
+        } else {
+            swap_update.amount_b
+        },
+        referral_fee_rate,
+    );
+
     if amount_specified_is_input {
         let transfer_fee_excluded_output_amount = if a_to_b {
             calculate_transfer_fee_excluded_amount(
@@ -142,12 +183,36 @@ pub fn handler<'info>(
         } else {
             swap_update.amount_b
         };
-        if transfer_fee_included_input_amount > other_amount_threshold {
+        let total_input_amount = transfer_fee_included_input_amount
+            .checked_add(referral_fee)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        if total_input_amount > other_amount_threshold {
             return Err(ErrorCode::AmountInAboveMaximum.into());
         }
     }
 
+    // The reward extension accrues rewards with the liquidity before the swap
+    if let Some(mut reward_extension) =
+        RewardExtensionAccounts::load(solve, &remaining_accounts.reward_extension)?
+    {
+        reward_extension.update_for_swap(
+            solve,
+            &swap_update.crossed_tick_indexes,
+            a_to_b,
+            timestamp,
+        )?;
+    }
+
     oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve, &swap_update.next_reward_infos)?;
+    oracle_accessor.record_observation(timestamp, solve.tick_current_index, solve.liquidity)?;
+    fill_limit_orders(
+        &oracle_accessor,
+        solve,
+        &mut swap_tick_sequence,
+        &swap_update,
+        a_to_b,
+    )?;
 
     let pre_sqrt_price = solve.sqrt_price;
     let (input_amount, output_amount) = if a_to_b {
@@ -186,6 +251,28 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    let (token_owner_account_input, token_program_input) = if a_to_b {
+        (
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+        )
+    } else {
+        (
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+        )
+    };
+    transfer_referral_fee_v2(
+        &ctx.accounts.token_authority,
+        token_mint_input,
+        token_owner_account_input,
+        referral_token_account.as_ref(),
+        token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_referral,
+        referral_fee,
+    )?;
+
     emit!(Traded {
         solve: solve.key(),
         a_to_b,
@@ -197,6 +284,7 @@ pub fn handler<'info>(
         output_transfer_fee,
         lp_fee,
         protocol_fee,
+        referral_fee,
     });
 
     Ok(())
@@ -212,6 +300,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     timestamp: u64,
     adaptive_fee_info: &Option<AdaptiveFeeInfo>,
 ) -> Result<Box<PostSwapUpdate>> {
@@ -235,6 +324,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
             sqrt_price_limit,
             amount_specified_is_input,
             a_to_b,
+            reward_schedules,
             timestamp,
             adaptive_fee_info,
         )?;
@@ -278,6 +368,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
             next_reward_infos: swap_update.next_reward_infos,
             next_protocol_fee: swap_update.next_protocol_fee,
             next_adaptive_fee_info: swap_update.next_adaptive_fee_info,
+            crossed_tick_indexes: swap_update.crossed_tick_indexes,
         }));
     }
 
@@ -294,6 +385,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
         sqrt_price_limit,
         amount_specified_is_input,
         a_to_b,
+        reward_schedules,
         timestamp,
         adaptive_fee_info,
     )?;
@@ -331,5 +423,102 @@ pub fn swap_with_transfer_fee_extension<'info>(
         next_reward_infos: swap_update.next_reward_infos,
         next_protocol_fee: swap_update.next_protocol_fee,
         next_adaptive_fee_info: swap_update.next_adaptive_fee_info,
+        crossed_tick_indexes: swap_update.crossed_tick_indexes,
     }))
 }
+
+// Swaps tokens that are already held by the vaults (e.g. liquidity withdrawn in the same instruction)
+// through the pool with exact input. Only the pool state is updated, no token is transferred,
+// so transfer fees are not applied. Returns the input and output amounts.
+// The reward extension accounts must be given if the reward extension is enabled for the solve.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_within_vaults<'info>(
+    solve: &mut Account<'info, Solve>,
+    oracle: AccountInfo<'info>,
+    swap_tick_sequence_builder: &SparseSwapTickSequenceBuilder<'info>,
+    reward_extension: Option<&mut RewardExtensionAccounts<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<(u64, u64)> {
+    let mut swap_tick_sequence = swap_tick_sequence_builder.try_build(solve, a_to_b)?;
+
+    let oracle_accessor = OracleAccessor::new(solve, oracle)?;
+    if !oracle_accessor.is_trade_enabled(timestamp)? {
+        return Err(ErrorCode::TradeIsNotEnabled.into());
+    }
+    let adaptive_fee_info = oracle_accessor.get_adaptive_fee_info()?;
+    let reward_schedules = SolveTail::load(&solve.to_account_info())?.reward_schedules;
+
+    let swap_update = swap(
+        solve,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        true,
+        a_to_b,
+        &reward_schedules,
+        timestamp,
+        &adaptive_fee_info,
+    )?;
+
+    let (input_amount, output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    if output_amount < other_amount_threshold {
+        return Err(ErrorCode::AmountOutBelowMinimum.into());
+    }
+
+    // The reward extension accrues rewards with the liquidity before the swap
+    if let Some(reward_extension) = reward_extension {
+        reward_extension.update_for_swap(
+            solve,
+            &swap_update.crossed_tick_indexes,
+            a_to_b,
+            timestamp,
+        )?;
+    }
+
+    oracle_accessor.update_adaptive_fee_variables(&swap_update.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve, &swap_update.next_reward_infos)?;
+    oracle_accessor.record_observation(timestamp, solve.tick_current_index, solve.liquidity)?;
+    fill_limit_orders(
+        &oracle_accessor,
+        solve,
+        &mut swap_tick_sequence,
+        &swap_update,
+        a_to_b,
+    )?;
+
+    let pre_sqrt_price = solve.sqrt_price;
+    solve.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        a_to_b,
+        timestamp,
+    );
+
+    emit!(Traded {
+        solve: solve.key(),
+        a_to_b,
+        pre_sqrt_price,
+        post_sqrt_price: solve.sqrt_price,
+        input_amount,
+        output_amount,
+        input_transfer_fee: 0,
+        output_transfer_fee: 0,
+        lp_fee: swap_update.lp_fee,
+        protocol_fee: swap_update.next_protocol_fee,
+        referral_fee: 0,
+    });
+
+    Ok((input_amount, output_amount))
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Solve, SolveTail, SolvesConfig, SolvesConfigExtension};
+
+#[derive(Accounts)]
+pub struct SyncPoolPauseFlags<'info> {
+    pub solves_config: Box<Account<'info, SolvesConfig>>,
+
+    #[account(has_one = solves_config)]
+    pub solves_config_extension: Account<'info, SolvesConfigExtension>,
+
+    #[account(mut, has_one = solves_config)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Copy the pause flags of the config into the tail of the Solve account.
+/// Anyone can invoke this instruction, the copy always follows the config.
+///
+/// Instructions that do not take the config extension only see the config pause flags through this copy.
+/// The tail is allocated if needed, unless the config is not paused.
+pub fn handler(ctx: Context<SyncPoolPauseFlags>) -> Result<()> {
+    let config_pause_flags = ctx.accounts.solves_config_extension.pause_flags;
+
+    let solve_account_info = ctx.accounts.solve.to_account_info();
+    if config_pause_flags == 0 && !SolveTail::is_allocated(&solve_account_info) {
+        return Ok(());
+    }
+
+    SolveTail::allocate(
+        &ctx.accounts.solve,
+        &ctx.accounts.funder.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut solve_tail = SolveTail::load(&solve_account_info)?;
+    solve_tail.update_config_pause_flags(config_pause_flags)?;
+    solve_tail.store(&solve_account_info)
+}
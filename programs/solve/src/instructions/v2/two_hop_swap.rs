@@ -7,11 +7,16 @@ use crate::{
     constants::transfer_memo,
     errors::ErrorCode,
     events::*,
-    state::{OracleAccessor, Solve},
+    manager::{
+        limit_order_manager::fill_limit_orders, reward_extension_manager::RewardExtensionAccounts,
+    },
+    math::{get_referral_fee_amount, get_referral_fee_excluded_amount},
+    state::{OracleAccessor, PausableOperation, Solve, SolveTail},
     util::{
-        calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
-        update_and_two_hop_swap_solve_v2, AccountsType, RemainingAccountsInfo,
-        SparseSwapTickSequenceBuilder,
+        calculate_transfer_fee_excluded_amount, load_referral_token_account,
+        parse_remaining_accounts, to_timestamp_u64, transfer_referral_fee_v2,
+        update_and_two_hop_swap_solve_v2, verify_pool_not_paused, verify_referral_fee,
+        AccountsType, RemainingAccountsInfo, SparseSwapTickSequenceBuilder,
     },
 };
 
@@ -98,11 +103,15 @@ pub struct TwoHopSwapV2<'info> {
     // - accounts for transfer hook program of token_mint_output
     // - supplemental TickArray accounts for solve_one
     // - supplemental TickArray accounts for solve_two
+    // - reward extension accounts for solve_one
+    // - reward extension accounts for solve_two
+    // - referral token account receiving the referral fee in the input token (two_hop_swap_with_referral_v2)
+    // - accounts for transfer hook program of token_mint_input to the referral token account
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn handler<'info>(
-    ctx: Context<'_, '_, '_, 'info, TwoHopSwapV2<'info>>,
+    ctx: Context<'_, '_, 'info, 'info, TwoHopSwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     amount_specified_is_input: bool,
@@ -110,6 +119,7 @@ pub fn handler<'info>(
     a_to_b_two: bool,
     sqrt_price_limit_one: u128,
     sqrt_price_limit_two: u128,
+    referral_fee_rate: u16,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
     let clock = Clock::get()?;
@@ -149,9 +159,21 @@ pub fn handler<'info>(
             AccountsType::TransferHookOutput,
             AccountsType::SupplementalTickArraysOne,
             AccountsType::SupplementalTickArraysTwo,
+            AccountsType::RewardExtensionOne,
+            AccountsType::RewardExtensionTwo,
+            AccountsType::ReferralTokenAccount,
+            AccountsType::TransferHookReferral,
         ],
     )?;
 
+    let referral_token_account =
+        load_referral_token_account(ctx.remaining_accounts, &remaining_accounts_info)?;
+    verify_referral_fee(
+        referral_token_account.as_ref(),
+        &ctx.accounts.token_mint_input.key(),
+        referral_fee_rate,
+    )?;
+
     let swap_tick_sequence_one = SparseSwapTickSequenceBuilder::new(
         vec![
             ctx.accounts.tick_array_one_0.to_account_info(),
@@ -177,14 +199,18 @@ pub fn handler<'info>(
     if !oracle_accessor_one.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve_one, PausableOperation::Swap)?;
     let adaptive_fee_info_one = oracle_accessor_one.get_adaptive_fee_info()?;
+    let reward_schedules_one = SolveTail::load(&solve_one.to_account_info())?.reward_schedules;
 
     let oracle_accessor_two =
         OracleAccessor::new(solve_two, ctx.accounts.oracle_two.to_account_info())?;
     if !oracle_accessor_two.is_trade_enabled(timestamp)? {
         return Err(ErrorCode::TradeIsNotEnabled.into());
     }
+    verify_pool_not_paused(solve_two, PausableOperation::Swap)?;
     let adaptive_fee_info_two = oracle_accessor_two.get_adaptive_fee_info()?;
+    let reward_schedules_two = SolveTail::load(&solve_two.to_account_info())?.reward_schedules;
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
@@ -205,10 +231,12 @@ pub fn handler<'info>(
                 &ctx.accounts.token_mint_input
             },
             &mut swap_tick_sequence_one,
-            amount,
+            // The referral fee is charged on top of the swap input, so less input is left for the swap
+            get_referral_fee_excluded_amount(amount, referral_fee_rate),
             sqrt_price_limit_one,
             amount_specified_is_input, // true
             a_to_b_one,
+            &reward_schedules_one,
             timestamp,
             &adaptive_fee_info_one,
         )?;
@@ -238,6 +266,7 @@ pub fn handler<'info>(
             sqrt_price_limit_two,
             amount_specified_is_input, // true
             a_to_b_two,
+            &reward_schedules_two,
             timestamp,
             &adaptive_fee_info_two,
         )?;
@@ -263,6 +292,7 @@ pub fn handler<'info>(
             sqrt_price_limit_two,
             amount_specified_is_input, // false
             a_to_b_two,
+            &reward_schedules_two,
             timestamp,
             &adaptive_fee_info_two,
         )?;
@@ -299,6 +329,7 @@ pub fn handler<'info>(
             sqrt_price_limit_one,
             amount_specified_is_input, // false
             a_to_b_one,
+            &reward_schedules_one,
             timestamp,
             &adaptive_fee_info_one,
         )?;
@@ -320,6 +351,15 @@ pub fn handler<'info>(
         return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
     }
 
+    let referral_fee = get_referral_fee_amount(
+        if a_to_b_one {
+            swap_update_one.amount_a
+        } else {
+            swap_update_one.amount_b
+        },
+        referral_fee_rate,
+    );
+
     if amount_specified_is_input {
         // If amount_specified_is_input == true, then we have a variable amount of output
         // The slippage we care about is the output of the second swap.
@@ -349,14 +389,65 @@ pub fn handler<'info>(
         } else {
             swap_update_one.amount_b
         };
-        if input_amount > other_amount_threshold {
+        let total_input_amount = input_amount
+            .checked_add(referral_fee)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        if total_input_amount > other_amount_threshold {
             return Err(ErrorCode::AmountInAboveMaximum.into());
         }
     }
 
+    // The reward extensions accrue rewards with the liquidity before the swaps
+    if let Some(mut reward_extension_one) =
+        RewardExtensionAccounts::load(solve_one, &remaining_accounts.reward_extension_one)?
+    {
+        reward_extension_one.update_for_swap(
+            solve_one,
+            &swap_update_one.crossed_tick_indexes,
+            a_to_b_one,
+            timestamp,
+        )?;
+    }
+    if let Some(mut reward_extension_two) =
+        RewardExtensionAccounts::load(solve_two, &remaining_accounts.reward_extension_two)?
+    {
+        reward_extension_two.update_for_swap(
+            solve_two,
+            &swap_update_two.crossed_tick_indexes,
+            a_to_b_two,
+            timestamp,
+        )?;
+    }
+
     oracle_accessor_one.update_adaptive_fee_variables(&swap_update_one.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve_one, &swap_update_one.next_reward_infos)?;
+    oracle_accessor_one.record_observation(
+        timestamp,
+        solve_one.tick_current_index,
+        solve_one.liquidity,
+    )?;
+    fill_limit_orders(
+        &oracle_accessor_one,
+        solve_one,
+        &mut swap_tick_sequence_one,
+        &swap_update_one,
+        a_to_b_one,
+    )?;
 
     oracle_accessor_two.update_adaptive_fee_variables(&swap_update_two.next_adaptive_fee_info)?;
+    SolveTail::track_reward_emissions(solve_two, &swap_update_two.next_reward_infos)?;
+    oracle_accessor_two.record_observation(
+        timestamp,
+        solve_two.tick_current_index,
+        solve_two.liquidity,
+    )?;
+    fill_limit_orders(
+        &oracle_accessor_two,
+        solve_two,
+        &mut swap_tick_sequence_two,
+        &swap_update_two,
+        a_to_b_two,
+    )?;
 
     let pre_sqrt_price_one = solve_one.sqrt_price;
     let (input_amount_one, output_amount_one) = if a_to_b_one {
@@ -420,6 +511,17 @@ pub fn handler<'info>(
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
     )?;
 
+    transfer_referral_fee_v2(
+        &ctx.accounts.token_authority,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_owner_account_input,
+        referral_token_account.as_ref(),
+        &ctx.accounts.token_program_input,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_referral,
+        referral_fee,
+    )?;
+
     emit!(Traded {
         solve: solve_one.key(),
         a_to_b: a_to_b_one,
@@ -431,6 +533,7 @@ pub fn handler<'info>(
         output_transfer_fee: output_transfer_fee_one,
         lp_fee: lp_fee_one,
         protocol_fee: protocol_fee_one,
+        referral_fee,
     });
 
     emit!(Traded {
@@ -444,6 +547,7 @@ pub fn handler<'info>(
         output_transfer_fee: output_transfer_fee_two,
         lp_fee: lp_fee_two,
         protocol_fee: protocol_fee_two,
+        referral_fee: 0,
     });
 
     Ok(())
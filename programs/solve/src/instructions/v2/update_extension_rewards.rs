@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, to_timestamp_u64, verify_pool_not_locked, AccountsType,
+    RemainingAccountsInfo,
+};
+
+#[derive(Accounts)]
+pub struct UpdateExtensionRewards<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(has_one = solve)]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+    // remaining accounts
+    // - SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension of the position ticks
+}
+
+/// Updates the rewards of the reward extension owed to a position, like `update_fees_and_rewards`
+/// does for the rewards of the solve.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateExtensionRewards<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let solve = &ctx.accounts.solve;
+    let position = &ctx.accounts.position;
+    verify_pool_not_locked(solve)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::RewardExtension],
+    )?;
+
+    let mut reward_extension =
+        RewardExtensionAccounts::load(solve, &remaining_accounts.reward_extension)?
+            .ok_or(ErrorCode::RewardExtensionRequired)?;
+
+    // Same as update_fees_and_rewards, positions without liquidity have nothing to update
+    if position.liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    reward_extension.update_for_modify_liquidity(
+        solve,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        0,
+        timestamp,
+    )
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::state::*;
+use crate::util::{
+    parse_remaining_accounts, to_timestamp_u64, verify_pool_not_locked, AccountsType,
+    RemainingAccountsInfo,
+};
+
+#[derive(Accounts)]
+pub struct UpdatePositionBoost<'info> {
+    pub solve: Account<'info, Solve>,
+
+    #[account(has_one = solve)]
+    pub position: Account<'info, Position>,
+
+    #[account(seeds = [b"lock_config".as_ref(), position.key().as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized if the position is not locked
+    pub lock_config: UncheckedAccount<'info>,
+
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+    // remaining accounts
+    // - SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension of the position ticks
+}
+
+/// Sets the boost of a position from its LockConfig and the lock boost config of the reward extension.
+///
+/// Anyone can invoke this instruction, so that the boost of a position is removed
+/// once its lock can be unlocked or has been unlocked.
+/// It is not paused, so that expired boosts can still be removed while the pool is paused.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdatePositionBoost<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let solve = &ctx.accounts.solve;
+    let position = &ctx.accounts.position;
+    verify_pool_not_locked(solve)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::RewardExtension],
+    )?;
+
+    let mut reward_extension =
+        RewardExtensionAccounts::load(solve, &remaining_accounts.reward_extension)?
+            .ok_or(ErrorCode::RewardExtensionRequired)?;
+
+    // The LockConfig account is closed when the position is unlocked
+    let lock_config_info = &ctx.accounts.lock_config;
+    let boost_bps = if lock_config_info.owner == &crate::ID {
+        let lock_config =
+            LockConfig::try_deserialize(&mut lock_config_info.try_borrow_data()?.as_ref())?;
+        reward_extension.lock_boost_config().get_boost_bps(
+            &lock_config,
+            position.liquidity,
+            timestamp,
+        )?
+    } else {
+        0
+    };
+
+    reward_extension.update_position_boost(
+        solve,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        boost_bps,
+        timestamp,
+    )
+}
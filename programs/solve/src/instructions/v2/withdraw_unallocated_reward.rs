@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::solve_manager::next_solve_reward_infos;
+use crate::math::checked_mul_shift_right_round_up_if;
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    state::*,
+    util::{to_timestamp_u64, v2::transfer_from_vault_to_owner_v2},
+};
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct WithdrawUnallocatedReward<'info> {
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = solve.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+
+    #[account(address = solve.reward_infos[reward_index as usize].mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = solve.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = reward_destination.mint == reward_mint.key())]
+    pub reward_destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = *reward_mint.to_account_info().owner)]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of reward_mint
+}
+
+/// Withdraws the reward tokens in the reward vault that are not owed to any position.
+///
+/// The rewards are accrued up to the current timestamp first, so that every reward emitted so far
+/// is reserved for the positions. The rewards to be emitted until the end of the schedule, or for
+/// UNSCHEDULED_REWARD_RESERVE_DURATION if the reward has no end, are also kept in the vault.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawUnallocatedReward<'info>>,
+    reward_index: u8,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookReward],
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let index = reward_index as usize;
+
+    let unallocated_amount = {
+        let solve_account_info = ctx.accounts.solve.to_account_info();
+        let mut solve_tail = SolveTail::load(&solve_account_info)?;
+        if !solve_tail.reward_accountings[index].tracked {
+            return Err(ErrorCode::RewardNotTracked.into());
+        }
+
+        // Rewards emitted up to now belong to the positions
+        let solve = &mut ctx.accounts.solve;
+        let next_reward_infos =
+            next_solve_reward_infos(solve, &solve_tail.reward_schedules, timestamp)?;
+        solve_tail.record_reward_emissions(&solve.reward_emitted_amounts(&next_reward_infos));
+        solve.update_rewards(next_reward_infos, timestamp);
+        solve_tail.store(&solve_account_info)?;
+
+        // Rewards to be emitted belong to the positions as well
+        let reserved_emission_duration =
+            solve_tail.reward_schedules[index].reserved_emission_duration(timestamp);
+        let reserved_amount = checked_mul_shift_right_round_up_if(
+            solve.reward_infos[index].emissions_per_second_x64,
+            reserved_emission_duration as u128,
+            true,
+        )
+        .unwrap_or(u64::MAX);
+
+        ctx.accounts
+            .reward_vault
+            .amount
+            .saturating_sub(solve_tail.reward_accountings[index].uncollected_amount())
+            .saturating_sub(reserved_amount)
+    };
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.solve,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_destination,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        unallocated_amount,
+        transfer_memo::TRANSFER_MEMO_WITHDRAW_UNALLOCATED_REWARD.as_bytes(),
+    )?;
+
+    emit!(UnallocatedRewardWithdrawn {
+        solve: ctx.accounts.solve.key(),
+        reward_index,
+        amount: unallocated_amount,
+    });
+
+    Ok(())
+}
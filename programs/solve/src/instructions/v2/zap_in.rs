@@ -0,0 +1,530 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_from_token_amounts, calculate_liquidity_token_deltas,
+    calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, to_timestamp_u64,
+    v2::{transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2},
+    verify_pool_and_config_not_paused, verify_position_authority_interface, AccountsType,
+    RemainingAccountsInfo, SparseSwapTickSequenceBuilder,
+};
+
+use super::swap::swap_within_vaults;
+
+#[derive(Accounts)]
+pub struct ZapV2<'info> {
+    #[account(mut)]
+    pub solve: Box<Account<'info, Solve>>,
+
+    #[account(address = *token_mint_a.to_account_info().owner)]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = *token_mint_b.to_account_info().owner)]
+    pub token_program_b: Interface<'info, TokenInterface>,
+
+    pub memo_program: Program<'info, Memo>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = solve)]
+    pub position: Account<'info, Position>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = solve.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = solve.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == solve.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == solve.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == solve.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == solve.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_lower: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by the tick array loader
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"oracle", solve.key().as_ref()], bump)]
+    /// CHECK: checked in the handler
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(seeds = [b"config_extension", solve.solves_config.as_ref()], bump)]
+    /// CHECK: checked in the handler, may be uninitialized
+    pub solves_config_extension: UncheckedAccount<'info>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - supplemental TickArray accounts for the swap
+    // - reward extension accounts (SolveRewardExtension, PositionRewardExtension and TickArrayRewardExtension
+    //   of the position ticks and the crossed ticks)
+}
+
+/*
+  Deposits a single token into a position.
+  The portion of the input token given by the client (quoted off-chain so that the position is balanced
+  at the post-swap price) is swapped through the same pool, then the maximum liquidity is added.
+  Leftover tokens are returned to the owner.
+*/
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ZapV2<'info>>,
+    amount: u64,
+    a_to_b: bool,
+    swap_amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    liquidity_min: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::IncreaseLiquidity,
+    )?;
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Swap,
+    )?;
+
+    if amount == 0 {
+        return Err(ErrorCode::ZeroTradableAmount.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+    let mut reward_extension =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?;
+
+    let (
+        token_mint_input,
+        token_owner_account_input,
+        token_vault_input,
+        token_program_input,
+        transfer_hook_input,
+    ) = if a_to_b {
+        (
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    } else {
+        (
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    };
+
+    // amount is the transfer fee included amount, the vault receives the transfer fee excluded amount
+    let transfer_fee_excluded_amount =
+        calculate_transfer_fee_excluded_amount(token_mint_input, amount)?;
+
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.position_authority,
+        token_mint_input,
+        token_owner_account_input,
+        token_vault_input,
+        token_program_input,
+        &ctx.accounts.memo_program,
+        transfer_hook_input,
+        amount,
+    )?;
+
+    let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+        vec![
+            ctx.accounts.tick_array_lower.to_account_info(),
+            ctx.accounts.tick_array_upper.to_account_info(),
+        ],
+        remaining_accounts.supplemental_tick_arrays,
+    );
+
+    let (amount_a, amount_b) = swap_deposited_amount(
+        &mut ctx.accounts.solve,
+        ctx.accounts.oracle.to_account_info(),
+        &swap_tick_sequence_builder,
+        reward_extension.as_mut(),
+        transfer_fee_excluded_amount.amount,
+        swap_amount,
+        other_amount_threshold,
+        sqrt_price_limit,
+        a_to_b,
+        timestamp,
+    )?;
+    drop(swap_tick_sequence_builder);
+
+    let liquidity_amount = calculate_liquidity_from_token_amounts(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        amount_a,
+        amount_b,
+    )?;
+    if liquidity_amount < liquidity_min {
+        return Err(ErrorCode::LiquidityBelowMinimum.into());
+    }
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+
+    // The reward extension is updated with the solve, position and ticks before the modification
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_for_modify_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    let (deposited_a, deposited_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    // Return the dust that could not be deposited
+    let refund_a = amount_a
+        .checked_sub(deposited_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let refund_b = amount_b
+        .checked_sub(deposited_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+
+    if refund_a > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.solve,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            refund_a,
+            transfer_memo::TRANSFER_MEMO_ZAP.as_bytes(),
+        )?;
+    }
+
+    if refund_b > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.solve,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            refund_b,
+            transfer_memo::TRANSFER_MEMO_ZAP.as_bytes(),
+        )?;
+    }
+
+    emit!(PositionZappedIn {
+        solve: ctx.accounts.solve.key(),
+        position: ctx.accounts.position.key(),
+        a_to_b,
+        input_amount: amount,
+        input_transfer_fee: transfer_fee_excluded_amount.transfer_fee,
+        liquidity: liquidity_amount,
+        token_a_refund_amount: refund_a,
+        token_b_refund_amount: refund_b,
+    });
+
+    Ok(())
+}
+
+// Swaps swap_amount of the deposited token, which is already in the vault, so the swap only updates
+// the pool state. Returns the amounts of token A and B to be added to the position.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn swap_deposited_amount<'info>(
+    solve: &mut Account<'info, Solve>,
+    oracle: AccountInfo<'info>,
+    swap_tick_sequence_builder: &SparseSwapTickSequenceBuilder<'info>,
+    reward_extension: Option<&mut RewardExtensionAccounts<'info>>,
+    deposited_amount: u64,
+    swap_amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<(u64, u64)> {
+    if swap_amount > deposited_amount {
+        return Err(ErrorCode::ZapSwapAmountExceeded.into());
+    }
+
+    let (swap_input_amount, swap_output_amount) = if swap_amount > 0 {
+        swap_within_vaults(
+            solve,
+            oracle,
+            swap_tick_sequence_builder,
+            reward_extension,
+            swap_amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            a_to_b,
+            timestamp,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    // The swap stops early if it reaches sqrt_price_limit, the rest of the input is deposited
+    let input_amount_left = deposited_amount - swap_input_amount;
+    Ok(if a_to_b {
+        (input_amount_left, swap_output_amount)
+    } else {
+        (swap_output_amount, input_amount_left)
+    })
+}
+
+#[cfg(test)]
+mod zap_in_tests {
+    use super::*;
+    use crate::math::{sqrt_price_from_tick_index, NO_EXPLICIT_SQRT_PRICE_LIMIT};
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+    const TICK_CURRENT_INDEX: i32 = 2_048;
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>, owner: Pubkey) -> Self {
+            Self {
+                key,
+                owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    struct ZapTestAccounts {
+        solve: TestAccount,
+        oracle: TestAccount,
+        tick_array: TestAccount,
+    }
+
+    impl ZapTestAccounts {
+        fn new() -> Self {
+            let solve_key = Pubkey::new_unique();
+            let solve = Solve {
+                tick_spacing: TICK_SPACING,
+                fee_rate: 3_000,
+                liquidity: LIQUIDITY,
+                sqrt_price: sqrt_price_from_tick_index(TICK_CURRENT_INDEX),
+                tick_current_index: TICK_CURRENT_INDEX,
+                ..Default::default()
+            };
+            let mut data = vec![0u8; Solve::LEN];
+            solve.try_serialize(&mut data.as_mut_slice()).unwrap();
+
+            // A fixed tick array starting at tick 0 without initialized ticks
+            let mut tick_array_data = vec![0u8; FixedTickArray::LEN];
+            tick_array_data[0..8].copy_from_slice(&FixedTickArray::discriminator());
+            tick_array_data[9956..9988].copy_from_slice(&solve_key.to_bytes());
+
+            Self {
+                solve: TestAccount::new(solve_key, data, crate::ID),
+                // The Oracle account is not initialized
+                oracle: TestAccount::new(Pubkey::new_unique(), vec![], System::id()),
+                tick_array: TestAccount::new(Pubkey::new_unique(), tick_array_data, crate::ID),
+            }
+        }
+
+        fn swap_deposited_amount(
+            &mut self,
+            deposited_amount: u64,
+            swap_amount: u64,
+            other_amount_threshold: u64,
+            sqrt_price_limit: u128,
+        ) -> Result<(u64, u64, Solve)> {
+            let solve_info = self.solve.account_info(true);
+            let mut solve: Account<Solve> = Account::try_from(&solve_info)?;
+            let swap_tick_sequence_builder =
+                SparseSwapTickSequenceBuilder::new(vec![self.tick_array.account_info(true)], None);
+            let (amount_a, amount_b) = swap_deposited_amount(
+                &mut solve,
+                self.oracle.account_info(false),
+                &swap_tick_sequence_builder,
+                None,
+                deposited_amount,
+                swap_amount,
+                other_amount_threshold,
+                sqrt_price_limit,
+                true,
+                1_000,
+            )?;
+            Ok((amount_a, amount_b, solve.into_inner()))
+        }
+    }
+
+    #[test]
+    fn test_swap_amount_exceeds_deposited_amount() {
+        let mut accounts = ZapTestAccounts::new();
+        assert_eq!(
+            accounts
+                .swap_deposited_amount(1_000, 1_001, 0, NO_EXPLICIT_SQRT_PRICE_LIMIT)
+                .map(|(amount_a, amount_b, _)| (amount_a, amount_b))
+                .unwrap_err(),
+            ErrorCode::ZapSwapAmountExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_no_swap() {
+        let mut accounts = ZapTestAccounts::new();
+        let (amount_a, amount_b, solve) = accounts
+            .swap_deposited_amount(1_000, 0, 0, NO_EXPLICIT_SQRT_PRICE_LIMIT)
+            .unwrap();
+        assert_eq!((amount_a, amount_b), (1_000, 0));
+        assert_eq!(solve.tick_current_index, TICK_CURRENT_INDEX);
+    }
+
+    #[test]
+    fn test_other_amount_threshold() {
+        let mut accounts = ZapTestAccounts::new();
+        let (amount_a, amount_b, _) = accounts
+            .swap_deposited_amount(1_000_000, 500_000, 0, NO_EXPLICIT_SQRT_PRICE_LIMIT)
+            .unwrap();
+        assert_eq!(amount_a, 500_000);
+        assert!(amount_b > 0);
+
+        let mut accounts = ZapTestAccounts::new();
+        assert_eq!(
+            accounts
+                .swap_deposited_amount(
+                    1_000_000,
+                    500_000,
+                    amount_b + 1,
+                    NO_EXPLICIT_SQRT_PRICE_LIMIT
+                )
+                .map(|(amount_a, amount_b, _)| (amount_a, amount_b))
+                .unwrap_err(),
+            ErrorCode::AmountOutBelowMinimum.into()
+        );
+    }
+
+    #[test]
+    fn test_sqrt_price_limit() {
+        // The swap stops at the limit and the rest of the input is deposited
+        let sqrt_price_limit = sqrt_price_from_tick_index(TICK_CURRENT_INDEX - 10);
+        let mut accounts = ZapTestAccounts::new();
+        let (amount_a, amount_b, solve) = accounts
+            .swap_deposited_amount(u64::MAX >> 8, u64::MAX >> 9, 0, sqrt_price_limit)
+            .unwrap();
+        assert_eq!(solve.sqrt_price, sqrt_price_limit);
+        assert!(amount_a > (u64::MAX >> 9));
+        assert!(amount_b > 0);
+    }
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::reward_extension_manager::RewardExtensionAccounts;
+use crate::manager::tick_array_manager::update_tick_array_accounts;
+use crate::math::{convert_to_liquidity_delta, NO_EXPLICIT_SQRT_PRICE_LIMIT};
+use crate::state::*;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, is_locked_position, parse_remaining_accounts,
+    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_pool_and_config_not_paused,
+    verify_position_authority_interface, AccountsType, RemainingAccountsInfo,
+    SparseSwapTickSequenceBuilder,
+};
+
+use super::swap::swap_within_vaults;
+use super::zap_in::ZapV2;
+
+/*
+  Removes liquidity from a position and swaps the withdrawn tokens into a single token.
+  a_to_b swaps the withdrawn token A into token B.
+*/
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ZapV2<'info>>,
+    liquidity_amount: u128,
+    a_to_b: bool,
+    token_min_out: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority_interface(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    verify_pool_and_config_not_paused(
+        &ctx.accounts.solve,
+        &ctx.accounts.solves_config_extension,
+        PausableOperation::Swap,
+    )?;
+
+    if is_locked_position(&ctx.accounts.position_token_account) {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let reward_schedules = SolveTail::load(&ctx.accounts.solve.to_account_info())?.reward_schedules;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+            AccountsType::RewardExtension,
+        ],
+    )?;
+
+    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+
+    // The reward extension is updated with the solve, position and ticks before the modification
+    let mut reward_extension =
+        RewardExtensionAccounts::load(&ctx.accounts.solve, &remaining_accounts.reward_extension)?;
+    if let Some(reward_extension) = reward_extension.as_mut() {
+        reward_extension.update_for_decrease_liquidity(
+            &ctx.accounts.solve,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            liquidity_delta,
+            timestamp,
+        )?;
+    }
+
+    let mut tick_arrays = TickArraysMut::load(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        &ctx.accounts.solve.key(),
+    )?;
+
+    let (lower_tick_array, upper_tick_array) = tick_arrays.deref();
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.solve,
+        &ctx.accounts.position,
+        lower_tick_array,
+        upper_tick_array,
+        liquidity_delta,
+        &reward_schedules,
+        timestamp,
+    )?;
+
+    SolveTail::track_reward_emissions(&ctx.accounts.solve, &update.reward_infos)?;
+    let (lower_tick_array_mut, upper_tick_array_mut) = tick_arrays.deref_mut();
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.solve,
+        &mut ctx.accounts.position,
+        lower_tick_array_mut,
+        upper_tick_array_mut,
+        &update,
+        timestamp,
+    )?;
+
+    // Need to drop the tick arrays so we can potentially resize them
+    drop(tick_arrays);
+
+    update_tick_array_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.to_account_info(),
+        ctx.accounts.tick_array_upper.to_account_info(),
+        &update.tick_array_lower_update,
+        &update.tick_array_upper_update,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.solve.tick_current_index,
+        ctx.accounts.solve.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    // The withdrawn tokens stay in the vaults, so the swap only updates the pool state
+    let swap_amount = if a_to_b { delta_a } else { delta_b };
+    let (swap_input_amount, swap_output_amount) = if swap_amount > 0 {
+        let swap_tick_sequence_builder = SparseSwapTickSequenceBuilder::new(
+            vec![
+                ctx.accounts.tick_array_lower.to_account_info(),
+                ctx.accounts.tick_array_upper.to_account_info(),
+            ],
+            remaining_accounts.supplemental_tick_arrays,
+        );
+        swap_within_vaults(
+            &mut ctx.accounts.solve,
+            ctx.accounts.oracle.to_account_info(),
+            &swap_tick_sequence_builder,
+            reward_extension.as_mut(),
+            swap_amount,
+            0,
+            NO_EXPLICIT_SQRT_PRICE_LIMIT,
+            a_to_b,
+            timestamp,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    // The input token is left only if the pool runs out of liquidity
+    let (amount_a, amount_b) = if a_to_b {
+        (
+            delta_a - swap_input_amount,
+            delta_b
+                .checked_add(swap_output_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?,
+        )
+    } else {
+        (
+            delta_a
+                .checked_add(swap_output_amount)
+                .ok_or(ErrorCode::AmountCalcOverflow)?,
+            delta_b - swap_input_amount,
+        )
+    };
+
+    let transfer_fee_excluded_amount_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, amount_a)?;
+    let transfer_fee_excluded_amount_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, amount_b)?;
+
+    // token_min_out should be applied to the transfer fee excluded amount
+    let transfer_fee_excluded_amount_out = if a_to_b {
+        transfer_fee_excluded_amount_b.amount
+    } else {
+        transfer_fee_excluded_amount_a.amount
+    };
+    if transfer_fee_excluded_amount_out < token_min_out {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    }
+
+    if amount_a > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.solve,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            &ctx.accounts.token_owner_account_a,
+            &ctx.accounts.token_program_a,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_a,
+            amount_a,
+            transfer_memo::TRANSFER_MEMO_ZAP.as_bytes(),
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_from_vault_to_owner_v2(
+            &ctx.accounts.solve,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            &ctx.accounts.token_owner_account_b,
+            &ctx.accounts.token_program_b,
+            &ctx.accounts.memo_program,
+            &remaining_accounts.transfer_hook_b,
+            amount_b,
+            transfer_memo::TRANSFER_MEMO_ZAP.as_bytes(),
+        )?;
+    }
+
+    emit!(PositionZappedOut {
+        solve: ctx.accounts.solve.key(),
+        position: ctx.accounts.position.key(),
+        a_to_b,
+        liquidity: liquidity_amount,
+        token_a_amount: amount_a,
+        token_b_amount: amount_b,
+        token_a_transfer_fee: transfer_fee_excluded_amount_a.transfer_fee,
+        token_b_transfer_fee: transfer_fee_excluded_amount_b.transfer_fee,
+    });
+
+    Ok(())
+}
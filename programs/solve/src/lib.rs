@@ -21,7 +21,10 @@ pub mod state;
 #[doc(hidden)]
 pub mod util;
 
-use crate::state::{LockType, OpenPositionBumps, OpenPositionWithMetadataBumps, SolveBumps};
+use crate::state::{
+    LockType, OpenPositionBumps, OpenPositionWithMetadataBumps, ParameterChange,
+    ProtocolFeeRecipient, SolveBumps,
+};
 use crate::util::RemainingAccountsInfo;
 use instructions::*;
 
@@ -108,6 +111,32 @@ pub mod solve {
         instructions::initialize_dynamic_tick_array::handler(ctx, start_tick_index, idempotent)
     }
 
+    /// Close a FixedTickArray or DynamicTickArray without initialized ticks.
+    /// The rent is refunded to the tick array rent recipient configured in the SolvesConfigExtension,
+    /// not to the original funder: tick arrays do not record their funder and their layout is kept unchanged.
+    /// The TickArrayRewardExtension of the tick array, if initialized, is closed with it.
+    ///
+    /// ### Authority
+    /// - "tick_array_rent_recipient" - Set recipient of the rent of closed tick arrays in the SolvesConfigExtension
+    ///
+    /// #### Special Errors
+    /// - `TickArrayNotEmpty` - if any tick in the tick array is initialized.
+    /// - `InvalidRewardExtensionAccount` - if the TickArrayRewardExtension address does not match the tick array.
+    pub fn close_tick_array(ctx: Context<CloseTickArray>) -> Result<()> {
+        instructions::close_tick_array::handler(ctx)
+    }
+
+    /// Migrate a FixedTickArray to the DynamicTickArray layout in place, preserving all initialized ticks.
+    /// The account is shrunk and the excess rent is refunded to the tick array rent recipient
+    /// configured in the SolvesConfigExtension. Anyone can invoke this instruction.
+    ///
+    /// #### Special Errors
+    /// - `TickArrayRentRecipientNotSet` - if the tick array rent recipient is not set.
+    /// - `DifferentSolveTickArrayAccount` - if the tick array does not belong to the Solve.
+    pub fn migrate_tick_array(ctx: Context<MigrateTickArray>) -> Result<()> {
+        instructions::migrate_tick_array::handler(ctx)
+    }
+
     /// Initializes a fee_tier account usable by Solves in a SolveConfig space.
     ///
     /// ### Authority
@@ -130,6 +159,8 @@ pub mod solve {
     }
 
     /// Initialize reward for a Solve. A pool can only support up to a set number of rewards.
+    /// The funder also pays for the tail of the Solve account on the first call, so that the
+    /// emissions of the reward are tracked from the start.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -147,6 +178,7 @@ pub mod solve {
     }
 
     /// Set the reward emissions for a reward in a Solve.
+    /// The emission window set by `set_reward_schedule` is kept.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -229,8 +261,13 @@ pub mod solve {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
-    pub fn increase_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    /// - `IncreaseLiquidityPaused` - Liquidity increases are paused on the pool.
+    /// - `OperationPaused` - All operations except withdrawals are paused on the pool.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and the SolveRewardExtension, or
+    ///                               an account of the extension of the position or its ticks, is not provided
+    ///                               in the remaining accounts.
+    pub fn increase_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_max_a: u64,
         token_max_b: u64,
@@ -252,8 +289,12 @@ pub mod solve {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
-    pub fn decrease_liquidity(
-        ctx: Context<ModifyLiquidity>,
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and the SolveRewardExtension, or
+    ///                               an account of the extension of a position with a PositionRewardExtension, is
+    ///                               not provided in the remaining accounts.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    pub fn decrease_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidity<'info>>,
         liquidity_amount: u128,
         token_min_a: u64,
         token_min_b: u64,
@@ -287,9 +328,14 @@ pub mod solve {
     }
 
     /// Collect the protocol fees accrued in this Solve
+    /// The SolvesConfigExtension can be passed as the first remaining account to check the fee distribution table.
     ///
     /// ### Authority
     /// - `collect_protocol_fees_authority` - assigned authority in the SolveConfig that can collect protocol fees
+    ///
+    /// #### Special Errors
+    /// - `ProtocolFeeRecipientsSet` - The fee distribution table of the SolvesConfigExtension is set.
+    ///                                The fees must be distributed with `distribute_protocol_fees_v2` instead.
     pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
         instructions::collect_protocol_fees::handler(ctx)
     }
@@ -315,8 +361,13 @@ pub mod solve {
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
-    pub fn swap(
-        ctx: Context<Swap>,
+    /// - `SwapPaused` - Swaps are paused on the pool.
+    /// - `OperationPaused` - All operations except withdrawals are paused on the pool.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and the SolveRewardExtension, or
+    ///                               the TickArrayRewardExtension of a crossed tick, is not provided in the
+    ///                               remaining accounts.
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit: u128,
@@ -340,12 +391,17 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `ClosePositionNotEmpty` - The provided position account is not empty.
-    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    /// - `RewardExtensionRequired` - The position has a PositionRewardExtension and it is not provided as the first
+    ///                               remaining account. The extension is closed with the position.
+    pub fn close_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClosePosition<'info>>,
+    ) -> Result<()> {
         instructions::close_position::handler(ctx)
     }
 
     /// Set the default_fee_rate for a FeeTier
     /// Only the current fee authority has permission to invoke this instruction.
+    /// The SolvesConfigExtension address is passed as the last account, it may be uninitialized.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority in the SolveConfig
@@ -356,6 +412,7 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `FeeRateMaxExceeded` - If the provided default_fee_rate exceeds MAX_FEE_RATE.
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The change must be queued instead.
     pub fn set_default_fee_rate(
         ctx: Context<SetDefaultFeeRate>,
         default_fee_rate: u16,
@@ -366,6 +423,7 @@ pub mod solve {
     /// Sets the default protocol fee rate for a SolveConfig
     /// Protocol fee rate is represented as a basis point.
     /// Only the current fee authority has permission to invoke this instruction.
+    /// The SolvesConfigExtension address is passed as the last account, it may be uninitialized.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
@@ -375,6 +433,7 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `ProtocolFeeRateMaxExceeded` - If the provided default_protocol_fee_rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The change must be queued instead.
     pub fn set_default_protocol_fee_rate(
         ctx: Context<SetDefaultProtocolFeeRate>,
         default_protocol_fee_rate: u16,
@@ -385,6 +444,7 @@ pub mod solve {
     /// Sets the fee rate for a Solve.
     /// Fee rate is represented as hundredths of a basis point.
     /// Only the current fee authority has permission to invoke this instruction.
+    /// The SolvesConfigExtension address is passed as the last account, it may be uninitialized.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
@@ -394,6 +454,7 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `FeeRateMaxExceeded` - If the provided fee_rate exceeds MAX_FEE_RATE.
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The change must be queued instead.
     pub fn set_fee_rate(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
         instructions::set_fee_rate::handler(ctx, fee_rate)
     }
@@ -401,6 +462,7 @@ pub mod solve {
     /// Sets the protocol fee rate for a Solve.
     /// Protocol fee rate is represented as a basis point.
     /// Only the current fee authority has permission to invoke this instruction.
+    /// The SolvesConfigExtension address is passed as the last account, it may be uninitialized.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
@@ -410,6 +472,7 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `ProtocolFeeRateMaxExceeded` - If the provided default_protocol_fee_rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The change must be queued instead.
     pub fn set_protocol_fee_rate(
         ctx: Context<SetProtocolFeeRate>,
         protocol_fee_rate: u16,
@@ -417,10 +480,12 @@ pub mod solve {
         instructions::set_protocol_fee_rate::handler(ctx, protocol_fee_rate)
     }
 
-    /// Sets the fee authority for a SolveConfig.
+    /// Nominates the new fee authority for a SolveConfig.
     /// The fee authority can set the fee & protocol fee rate for individual pools or
     /// set the default fee rate for newly minted pools.
     /// Only the current fee authority has permission to invoke this instruction.
+    /// The nominated key is kept in the SolvesConfigExtension until it signs `accept_fee_authority`.
+    /// The SolvesConfigExtension is passed after the accounts of the one-step version of this instruction.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
@@ -428,8 +493,19 @@ pub mod solve {
         instructions::set_fee_authority::handler(ctx)
     }
 
-    /// Sets the fee authority to collect protocol fees for a SolveConfig.
+    /// Accepts the fee authority nominated with `set_fee_authority` for a SolveConfig.
+    ///
+    /// ### Authority
+    /// - "pending_fee_authority" - The nominated fee authority in the SolvesConfigExtension
+    pub fn accept_fee_authority(ctx: Context<AcceptFeeAuthority>) -> Result<()> {
+        instructions::accept_fee_authority::handler(ctx)
+    }
+
+    /// Nominates the new authority to collect protocol fees for a SolveConfig.
     /// Only the current collect protocol fee authority has permission to invoke this instruction.
+    /// The nominated key is kept in the SolvesConfigExtension until it signs
+    /// `accept_collect_protocol_fees_authority`.
+    /// The SolvesConfigExtension is passed after the accounts of the one-step version of this instruction.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can collect protocol fees in the SolveConfig
@@ -439,6 +515,16 @@ pub mod solve {
         instructions::set_collect_protocol_fees_authority::handler(ctx)
     }
 
+    /// Accepts the collect protocol fees authority nominated with `set_collect_protocol_fees_authority`.
+    ///
+    /// ### Authority
+    /// - "pending_collect_protocol_fees_authority" - The nominated authority in the SolvesConfigExtension
+    pub fn accept_collect_protocol_fees_authority(
+        ctx: Context<AcceptCollectProtocolFeesAuthority>,
+    ) -> Result<()> {
+        instructions::accept_collect_protocol_fees_authority::handler(ctx)
+    }
+
     /// Set the solve reward authority at the provided `reward_index`.
     /// Only the current reward authority for this reward index has permission to invoke this instruction.
     ///
@@ -470,8 +556,11 @@ pub mod solve {
         instructions::set_reward_authority_by_super_authority::handler(ctx, reward_index)
     }
 
-    /// Set the solve reward super authority for a SolveConfig
+    /// Nominate the new solve reward super authority for a SolveConfig
     /// Only the current reward super authority has permission to invoke this instruction.
+    /// The nominated key is kept in the SolvesConfigExtension until it signs
+    /// `accept_reward_emissions_super_authority`.
+    /// The SolvesConfigExtension is passed after the accounts of the one-step version of this instruction.
     /// This instruction will not change the authority on any `SolveRewardInfo` solve rewards.
     ///
     /// ### Authority
@@ -482,6 +571,16 @@ pub mod solve {
         instructions::set_reward_emissions_super_authority::handler(ctx)
     }
 
+    /// Accepts the reward super authority nominated with `set_reward_emissions_super_authority`.
+    ///
+    /// ### Authority
+    /// - "pending_reward_emissions_super_authority" - The nominated authority in the SolvesConfigExtension
+    pub fn accept_reward_emissions_super_authority(
+        ctx: Context<AcceptRewardEmissionsSuperAuthority>,
+    ) -> Result<()> {
+        instructions::accept_reward_emissions_super_authority::handler(ctx)
+    }
+
     /// Perform a two-hop swap in this Solve
     ///
     /// ### Authority
@@ -507,9 +606,14 @@ pub mod solve {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if solve one & two are the same pool.
+    /// - `SwapPaused` - Swaps are paused on either pool.
+    /// - `OperationPaused` - All operations except withdrawals are paused on either pool.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for a pool and its SolveRewardExtension, or
+    ///                               the TickArrayRewardExtension of a crossed tick, is not provided in the
+    ///                               remaining accounts.
     #[allow(clippy::too_many_arguments)]
-    pub fn two_hop_swap(
-        ctx: Context<TwoHopSwap>,
+    pub fn two_hop_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, TwoHopSwap<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         amount_specified_is_input: bool,
@@ -597,8 +701,10 @@ pub mod solve {
     /// #### Special Errors
     /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
     /// - `ClosePositionNotEmpty` - The provided position account is not empty.
-    pub fn close_bundled_position(
-        ctx: Context<CloseBundledPosition>,
+    /// - `RewardExtensionRequired` - The position has a PositionRewardExtension and it is not provided as the first
+    ///                               remaining account. The extension is closed with the position.
+    pub fn close_bundled_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseBundledPosition<'info>>,
         bundle_index: u16,
     ) -> Result<()> {
         instructions::close_bundled_position::handler(ctx, bundle_index)
@@ -639,13 +745,16 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `ClosePositionNotEmpty` - The provided position account is not empty.
-    pub fn close_position_with_token_extensions(
-        ctx: Context<ClosePositionWithTokenExtensions>,
+    /// - `RewardExtensionRequired` - The position has a PositionRewardExtension and it is not provided as the first
+    ///                               remaining account. The extension is closed with the position.
+    pub fn close_position_with_token_extensions<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClosePositionWithTokenExtensions<'info>>,
     ) -> Result<()> {
         instructions::close_position_with_token_extensions::handler(ctx)
     }
 
     /// Lock the position to prevent any liquidity changes.
+    /// A vesting lock releases the liquidity of the position linearly between its start and end timestamps.
     ///
     /// ### Authority
     /// - `position_authority` - The authority that owns the position token.
@@ -653,10 +762,27 @@ pub mod solve {
     /// #### Special Errors
     /// - `PositionAlreadyLocked` - The provided position is already locked.
     /// - `PositionNotLockable` - The provided position is not lockable (e.g. An empty position).
+    /// - `InvalidUnlockTimestamp` - The unlock timestamp of a timed or vesting lock is not in the future,
+    ///                              or the vesting ends before it starts.
     pub fn lock_position(ctx: Context<LockPosition>, lock_type: LockType) -> Result<()> {
         instructions::lock_position::handler(ctx, lock_type)
     }
 
+    /// Unlock a position with an expired timed lock and close the lock config account.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The authority that owns the position token.
+    ///
+    /// #### Special Errors
+    /// - `PositionNotUnlockable` - The lock is permanent or the unlock timestamp has not passed.
+    /// - `RewardExtensionRequired` - The position has a PositionRewardExtension and it is not provided as the first
+    ///                               remaining account.
+    /// - `PositionBoosted` - The position still has a boost. `update_position_boost` removes it once the lock
+    ///                       can be unlocked, and must be invoked before this instruction.
+    pub fn unlock_position(ctx: Context<UnlockPosition>) -> Result<()> {
+        instructions::unlock_position::handler(ctx)
+    }
+
     /// Reset the position range to a new range.
     ///
     /// ### Authority
@@ -679,6 +805,47 @@ pub mod solve {
         instructions::reset_position_range::handler(ctx, new_tick_lower_index, new_tick_upper_index)
     }
 
+    /// Split part of the liquidity of a position into a new position with the same range.
+    /// A unique Token-2022 token is minted to represent the new position.
+    /// Fees and rewards owed are split pro-rata to the liquidity, and the ticks are not modified.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to the split position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The amount of liquidity to move into the new position.
+    /// - `with_token_metadata_extension` - If true, the token metadata extension will be initialized.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityUnderflow` - Provided liquidity amount exceeds the liquidity of the position.
+    /// - `OperationNotAllowedOnLockedPosition` - The provided position is locked.
+    /// - `RewardExtensionNotSupported` - The position has a PositionRewardExtension.
+    pub fn split_position(
+        ctx: Context<SplitPosition>,
+        liquidity_amount: u128,
+        with_token_metadata_extension: bool,
+    ) -> Result<()> {
+        instructions::split_position::handler(ctx, liquidity_amount, with_token_metadata_extension)
+    }
+
+    /// Merge a position into another position with the same range and owner.
+    /// The liquidity and the fees and rewards owed are moved into `position`, then the token of
+    /// `closed_position` is burned and its accounts are closed. The ticks are not modified.
+    /// A legacy position mint cannot be closed by the Token program, so it is left with no supply,
+    /// as in `close_position`. Token-2022 position mints are closed.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the tokens corresponding to both positions.
+    ///
+    /// #### Special Errors
+    /// - `PositionsNotMergeable` - The positions are the same, or have a different range or owner.
+    /// - `OperationNotAllowedOnLockedPosition` - One of the provided positions is locked.
+    /// - `RewardExtensionNotSupported` - One of the provided positions has a PositionRewardExtension.
+    pub fn merge_positions(ctx: Context<MergePositions>) -> Result<()> {
+        instructions::merge_positions::handler(ctx)
+    }
+
     /// Transfer a locked position to to a different token account.
     ///
     /// ### Authority
@@ -687,6 +854,41 @@ pub mod solve {
         instructions::transfer_locked_position::handler(ctx)
     }
 
+    /// Authorize a keeper to compound the fees of a position on behalf of the position owner.
+    /// The authorization becomes ineffective once the position token is transferred to another owner.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The authority that owns the position token.
+    ///
+    /// ### Parameters
+    /// - `keeper` - The keeper that can call compound_fees_v2 for this position.
+    /// - `sqrt_price_lower_bound` - The minimum pool price at which the keeper can compound.
+    /// - `sqrt_price_upper_bound` - The maximum pool price at which the keeper can compound.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPositionKeeperPriceBounds` - The bounds are out of the pool price range or not ordered.
+    pub fn initialize_position_keeper(
+        ctx: Context<InitializePositionKeeper>,
+        keeper: Pubkey,
+        sqrt_price_lower_bound: u128,
+        sqrt_price_upper_bound: u128,
+    ) -> Result<()> {
+        instructions::initialize_position_keeper::handler(
+            ctx,
+            keeper,
+            sqrt_price_lower_bound,
+            sqrt_price_upper_bound,
+        )
+    }
+
+    /// Revoke the keeper authorization of a position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - The authority that owns the position token.
+    pub fn close_position_keeper(ctx: Context<ClosePositionKeeper>) -> Result<()> {
+        instructions::close_position_keeper::handler(ctx)
+    }
+
     /// Initializes an adaptive_fee_tier account usable by Solves in a SolveConfig space.
     ///
     /// ### Authority
@@ -764,9 +966,11 @@ pub mod solve {
         instructions::set_default_base_fee_rate::handler(ctx, default_base_fee_rate)
     }
 
-    /// Sets the delegated fee authority for an AdaptiveFeeTier.
+    /// Nominates the new delegated fee authority for an AdaptiveFeeTier.
     /// The delegated fee authority can set the fee rate for individual pools initialized with the adaptive fee-tier.
     /// Only the current fee authority in SolvesConfig has permission to invoke this instruction.
+    /// The nominated key takes effect once it signs `accept_delegated_fee_authority`.
+    /// Nominating the default pubkey revokes the delegated fee authority immediately.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority in the SolveConfig
@@ -774,9 +978,19 @@ pub mod solve {
         instructions::set_delegated_fee_authority::handler(ctx)
     }
 
-    /// Sets the initialize pool authority for an AdaptiveFeeTier.
+    /// Accepts the delegated fee authority nominated with `set_delegated_fee_authority`.
+    ///
+    /// ### Authority
+    /// - "pending_delegated_fee_authority" - The nominated authority in the AdaptiveFeeTier
+    pub fn accept_delegated_fee_authority(ctx: Context<AcceptDelegatedFeeAuthority>) -> Result<()> {
+        instructions::accept_delegated_fee_authority::handler(ctx)
+    }
+
+    /// Nominates the new initialize pool authority for an AdaptiveFeeTier.
     /// Only the initialize pool authority can initialize pools with the adaptive fee-tier.
     /// Only the current fee authority in SolvesConfig has permission to invoke this instruction.
+    /// The nominated key takes effect once it signs `accept_initialize_pool_authority`.
+    /// Nominating the default pubkey makes the adaptive fee-tier permission-less immediately.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority in the SolveConfig
@@ -784,6 +998,16 @@ pub mod solve {
         instructions::set_initialize_pool_authority::handler(ctx)
     }
 
+    /// Accepts the initialize pool authority nominated with `set_initialize_pool_authority`.
+    ///
+    /// ### Authority
+    /// - "pending_initialize_pool_authority" - The nominated authority in the AdaptiveFeeTier
+    pub fn accept_initialize_pool_authority(
+        ctx: Context<AcceptInitializePoolAuthority>,
+    ) -> Result<()> {
+        instructions::accept_initialize_pool_authority::handler(ctx)
+    }
+
     /// Sets the adaptive fee constants for an AdaptiveFeeTier.
     /// Only the current fee authority in SolvesConfig has permission to invoke this instruction.
     ///
@@ -847,6 +1071,7 @@ pub mod solve {
 
     /// Sets the fee rate for a Solve by the delegated fee authority in AdaptiveFeeTier.
     /// Fee rate is represented as hundredths of a basis point.
+    /// The SolvesConfigExtension address is passed as the last account, it may be uninitialized.
     ///
     /// ### Authority
     /// - "delegated_fee_authority" - Set authority that can modify pool fees in the AdaptiveFeeTier
@@ -856,6 +1081,7 @@ pub mod solve {
     ///
     /// #### Special Errors
     /// - `FeeRateMaxExceeded` - If the provided fee_rate exceeds MAX_FEE_RATE.
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The fee authority must queue the change instead.
     pub fn set_fee_rate_by_delegated_fee_authority(
         ctx: Context<SetFeeRateByDelegatedFeeAuthority>,
         fee_rate: u16,
@@ -863,6 +1089,56 @@ pub mod solve {
         instructions::set_fee_rate_by_delegated_fee_authority::handler(ctx, fee_rate)
     }
 
+    /// Initializes an Oracle account for a Solve initialized with FeeTier (static fee),
+    /// so that the pool can opt in to price observations.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the SolvesConfig
+    pub fn initialize_oracle(ctx: Context<InitializeOracle>) -> Result<()> {
+        instructions::initialize_oracle::handler(ctx)
+    }
+
+    /// Grows the observation ring buffer in the Oracle account.
+    /// The first call enables price observations for the pool.
+    /// Rent for the new observation slots is paid by the funder.
+    ///
+    /// ### Parameters
+    /// - `observation_cardinality_next` - The new number of observation slots.
+    ///                                    The account can grow by at most 10KiB per call.
+    ///
+    /// #### Special Errors
+    /// - `InvalidObservationCardinality` - If observation_cardinality_next is not greater than the current value.
+    /// - `ObservationCardinalityIncreaseTooLarge` - If the Oracle account would grow by more than 10KiB.
+    pub fn increase_observation_cardinality(
+        ctx: Context<IncreaseObservationCardinality>,
+        observation_cardinality_next: u16,
+    ) -> Result<()> {
+        instructions::increase_observation_cardinality::handler(ctx, observation_cardinality_next)
+    }
+
+    /// Returns the time-weighted average tick over the window
+    /// [now - seconds_ago_start, now - seconds_ago_end] as return data.
+    ///
+    /// ### Parameters
+    /// - `seconds_ago_start` - The start of the window in seconds before the current block time.
+    /// - `seconds_ago_end` - The end of the window in seconds before the current block time.
+    ///
+    /// #### Special Errors
+    /// - `ObservationsNotInitialized` - If price observations are not enabled for the pool.
+    /// - `InvalidObservationWindow` - If seconds_ago_start is not greater than seconds_ago_end.
+    /// - `ObservationTooOld` - If the window starts before the oldest stored observation.
+    pub fn get_time_weighted_average_tick(
+        ctx: Context<GetTimeWeightedAverageTick>,
+        seconds_ago_start: u32,
+        seconds_ago_end: u32,
+    ) -> Result<i32> {
+        instructions::get_time_weighted_average_tick::handler(
+            ctx,
+            seconds_ago_start,
+            seconds_ago_end,
+        )
+    }
+
     ////////////////////////////////////////////////////////////////////////////////
     // V2 instructions (TokenExtensions)
     ////////////////////////////////////////////////////////////////////////////////
@@ -880,10 +1156,61 @@ pub mod solve {
         instructions::v2::collect_fees::handler(ctx, remaining_accounts_info)
     }
 
+    /// Convert the fees owed to a position into additional liquidity in the same range.
+    /// The fees never leave the vaults, and the dust that cannot be added is kept as fees owed.
+    ///
+    /// ### Authority
+    /// - `authority` - authority that owns the token corresponding to this desired position,
+    ///                 or the keeper authorized by the position_keeper account.
+    ///
+    /// ### Parameters
+    /// - `swap_amount` - The amount of the fees to swap through the same pool before adding liquidity.
+    ///                   No swap if zero.
+    /// - `a_to_b` - The fees in token A are swapped if true, the fees in token B if false.
+    /// - `other_amount_threshold` - The minimum amount of the other token the swap must output.
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to. For a keeper, the
+    ///                        limit is bounded by the price bounds of the position_keeper account.
+    /// - `liquidity_min` - The minimum liquidity to be added.
+    ///
+    /// #### Special Errors
+    /// - `MissingOrInvalidDelegate` - The authority is neither the position authority nor the keeper.
+    /// - `PriceOutOfPositionKeeperBounds` - The keeper compounds while the pool price is out of the
+    ///                                      price bounds set by the position owner.
+    /// - `ZapSwapAmountExceeded` - The swap amount exceeds the fees owed.
+    /// - `AmountOutBelowMinimum` - The swap output is below other_amount_threshold.
+    /// - `LiquidityZero` - The fees are too small to add any liquidity.
+    /// - `LiquidityBelowMinimum` - The added liquidity is below liquidity_min.
+    #[allow(clippy::too_many_arguments)]
+    pub fn compound_fees_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompoundFeesV2<'info>>,
+        swap_amount: u64,
+        a_to_b: bool,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        liquidity_min: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::compound_fees::handler(
+            ctx,
+            swap_amount,
+            a_to_b,
+            other_amount_threshold,
+            sqrt_price_limit,
+            liquidity_min,
+            remaining_accounts_info,
+        )
+    }
+
     /// Collect the protocol fees accrued in this Solve
+    /// The SolvesConfigExtension can be passed in the ConfigExtension remaining accounts slice to check
+    /// the fee distribution table.
     ///
     /// ### Authority
     /// - `collect_protocol_fees_authority` - assigned authority in the SolveConfig that can collect protocol fees
+    ///
+    /// #### Special Errors
+    /// - `ProtocolFeeRecipientsSet` - The fee distribution table of the SolvesConfigExtension is set.
+    ///                                The fees must be distributed with `distribute_protocol_fees_v2` instead.
     pub fn collect_protocol_fees_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, CollectProtocolFeesV2<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
@@ -891,6 +1218,21 @@ pub mod solve {
         instructions::v2::collect_protocol_fees::handler(ctx, remaining_accounts_info)
     }
 
+    /// Distribute the protocol fees accrued in this Solve according to the fee distribution table
+    /// of the SolvesConfigExtension. This instruction is permissionless.
+    ///
+    /// #### Special Errors
+    /// - `ProtocolFeeRecipientsNotSet` - The fee distribution table is empty.
+    /// - `InvalidProtocolFeeDestination` - The ProtocolFeeDestinations slice does not have a token account
+    ///                                     for each recipient and token, or a provided token account is not
+    ///                                     owned by the recipient or does not hold the token of the Solve.
+    pub fn distribute_protocol_fees_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeProtocolFeesV2<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::distribute_protocol_fees::handler(ctx, remaining_accounts_info)
+    }
+
     /// Collect rewards accrued for this position.
     ///
     /// ### Authority
@@ -917,6 +1259,13 @@ pub mod solve {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    /// - `OperationNotAllowedOnLockedPosition` - The position is locked without a vesting lock, or its LockConfig
+    ///                                           account is not provided in the remaining accounts.
+    /// - `VestedLiquidityExceeded` - The liquidity left in a vesting locked position would be below the unvested liquidity.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and the SolveRewardExtension, or
+    ///                               an account of the extension of a position with a PositionRewardExtension, is
+    ///                               missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
     pub fn decrease_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -947,6 +1296,8 @@ pub mod solve {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and an account of the extension is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
     pub fn increase_liquidity_v2<'info>(
         ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
@@ -963,6 +1314,152 @@ pub mod solve {
         )
     }
 
+    /// Add liquidity to a position in the Solve by token amounts instead of liquidity units.
+    /// The maximum liquidity that can be funded with the given amounts is calculated at the current price.
+    /// This call also updates the position's accrued fees and rewards.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `token_max_a` - The maximum amount of tokenA the user is willing to deposit (transfer fee included).
+    /// - `token_max_b` - The maximum amount of tokenB the user is willing to deposit (transfer fee included).
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - The given amounts are too small to add any liquidity.
+    /// - `LiquidityTooHigh` - Calculated liquidity exceeds u128::max.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    pub fn increase_liquidity_by_token_amounts_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyLiquidityV2<'info>>,
+        token_max_a: u64,
+        token_max_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::increase_liquidity_by_token_amounts::handler(
+            ctx,
+            token_max_a,
+            token_max_b,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Move the liquidity of a position to a new range in a single instruction.
+    /// The liquidity is withdrawn, fees are collected, the range is moved, the imbalance is optionally
+    /// swapped through the same pool, and the maximum liquidity is redeposited into the new range.
+    /// Tokens that could not be redeposited are returned to the owner.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `new_tick_lower_index` - The new tick specifying the lower end of the position range.
+    /// - `new_tick_upper_index` - The new tick specifying the upper end of the position range.
+    /// - `liquidity_min` - The minimum liquidity the user is willing to have after the reposition.
+    /// - `swap_params` - Optional swap of the withdrawn tokens (exact input) before the redeposit.
+    ///
+    /// #### Special Errors
+    /// - `OperationNotAllowedOnLockedPosition` - The provided position is locked.
+    /// - `LiquidityZero` - The position has no liquidity, or no liquidity can be redeposited.
+    /// - `SameTickRangeNotAllowed` - The new range is the same as the current range.
+    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
+    ///                        the tick-spacing in this pool.
+    /// - `RepositionSwapAmountExceeded` - The swap amount exceeds the tokens withdrawn from the position.
+    /// - `AmountOutBelowMinimum` - The swap output is below other_amount_threshold.
+    /// - `LiquidityBelowMinimum` - The redeposited liquidity is below liquidity_min.
+    pub fn reposition_position_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, RepositionPositionV2<'info>>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+        liquidity_min: u128,
+        swap_params: Option<RepositionSwap>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::reposition_position::handler(
+            ctx,
+            new_tick_lower_index,
+            new_tick_upper_index,
+            liquidity_min,
+            swap_params,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Deposit a single token into a position. A portion of the input token is swapped through the
+    /// same pool, then the maximum liquidity is added. Leftover tokens are returned to the owner.
+    /// The swap amount that balances the position at the post-swap price is quoted by the client.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of the input token the user deposits (transfer fee included).
+    /// - `a_to_b` - Token A is deposited if true, token B if false.
+    /// - `swap_amount` - The amount of the deposited token (transfer fee excluded) to swap. No swap if zero.
+    /// - `other_amount_threshold` - The minimum amount of the other token the swap must output.
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `liquidity_min` - The minimum liquidity the user is willing to add.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - Provided amount is zero.
+    /// - `ZapSwapAmountExceeded` - The swap amount exceeds the deposited amount.
+    /// - `AmountOutBelowMinimum` - The swap output is below other_amount_threshold.
+    /// - `SqrtPriceOutOfBounds` - The sqrt_price_limit is out of bounds.
+    /// - `LiquidityZero` - The amount is too small to add any liquidity.
+    /// - `LiquidityBelowMinimum` - The added liquidity is below liquidity_min.
+    #[allow(clippy::too_many_arguments)]
+    pub fn zap_in_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, ZapV2<'info>>,
+        amount: u64,
+        a_to_b: bool,
+        swap_amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        liquidity_min: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::zap_in::handler(
+            ctx,
+            amount,
+            a_to_b,
+            swap_amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            liquidity_min,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Withdraw liquidity from a position and swap the withdrawn tokens into a single token
+    /// through the same pool. This call also updates the position's accrued fees and rewards.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
+    /// - `a_to_b` - Withdrawn token A is swapped into token B if true, token B into token A if false.
+    /// - `token_min_out` - The minimum amount of the output token the user is willing to receive.
+    ///
+    /// #### Special Errors
+    /// - `OperationNotAllowedOnLockedPosition` - The provided position is locked.
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `TokenMinSubceeded` - The output token amount subceeds token_min_out.
+    pub fn zap_out_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, ZapV2<'info>>,
+        liquidity_amount: u128,
+        a_to_b: bool,
+        token_min_out: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::zap_out::handler(
+            ctx,
+            liquidity_amount,
+            a_to_b,
+            token_min_out,
+            remaining_accounts_info,
+        )
+    }
+
     /// Initializes a Solve account.
     /// Fee rate is set to the default values on the config and supplied fee_tier.
     ///
@@ -984,6 +1481,8 @@ pub mod solve {
     }
 
     /// Initialize reward for a Solve. A pool can only support up to a set number of rewards.
+    /// The funder also pays for the tail of the Solve account on the first call, so that the
+    /// emissions of the reward are tracked from the start.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -1001,6 +1500,7 @@ pub mod solve {
     }
 
     /// Set the reward emissions for a reward in a Solve.
+    /// The emission window set by `set_reward_schedule` is kept.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -1025,58 +1525,325 @@ pub mod solve {
         instructions::v2::set_reward_emissions::handler(ctx, reward_index, emissions_per_second_x64)
     }
 
-    /// Perform a swap in this Solve
+    /// Set the reward emissions for a reward in a Solve together with the window in which they are emitted.
+    /// Rewards stop accruing after the end timestamp, so the reward vault can be funded for exactly
+    /// the rest of the window. The schedules are kept in a tail appended to the Solve account,
+    /// which is allocated on first use with the rent paid by the funder.
     ///
     /// ### Authority
-    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this Solve
     ///
     /// ### Parameters
-    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
-    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
-    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
-    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
-    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to modify.
+    /// - `emissions_per_second_x64` - The amount of rewards emitted in this pool.
+    /// - `start_timestamp` - The timestamp from which the rewards are emitted.
+    /// - `end_timestamp` - The timestamp after which the rewards are no longer emitted.
     ///
     /// #### Special Errors
-    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
-    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
-    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
-    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
-    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
-    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
-    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
-    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
-    pub fn swap_v2<'info>(
-        ctx: Context<'_, '_, '_, 'info, SwapV2<'info>>,
-        amount: u64,
-        other_amount_threshold: u64,
-        sqrt_price_limit: u128,
-        amount_specified_is_input: bool,
-        a_to_b: bool,
-        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault cannot emit
+    ///                                     the desired emissions until the end timestamp.
+    /// - `InvalidRewardSchedule` - The end timestamp is not after the start timestamp or the current timestamp.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - The provided reward index exceeds NUM_REWARDS.
+    pub fn set_reward_schedule(
+        ctx: Context<SetRewardSchedule>,
+        reward_index: u8,
+        emissions_per_second_x64: u128,
+        start_timestamp: u64,
+        end_timestamp: u64,
     ) -> Result<()> {
-        instructions::v2::swap::handler(
+        instructions::v2::set_reward_schedule::handler(
             ctx,
-            amount,
-            other_amount_threshold,
-            sqrt_price_limit,
-            amount_specified_is_input,
-            a_to_b,
-            remaining_accounts_info,
+            reward_index,
+            emissions_per_second_x64,
+            start_timestamp,
+            end_timestamp,
         )
     }
 
-    /// Perform a two-hop swap in this Solve
+    /// Withdraw the reward tokens of a Solve reward vault that are not owed to any position.
+    /// The rewards are accrued up to the current timestamp before the surplus over the emitted but
+    /// uncollected rewards is withdrawn. The rewards to be emitted until the end of the schedule are
+    /// also reserved, or UNSCHEDULED_REWARD_RESERVE_DURATION of them if the reward has no end.
     ///
     /// ### Authority
-    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this Solve
     ///
     /// ### Parameters
-    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
-    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
-    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
-    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
-    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to withdraw.
+    ///
+    /// #### Special Errors
+    /// - `RewardNotTracked` - The emissions of the reward are not tracked, because the tail of the
+    ///                        Solve account was allocated after the reward started to emit.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    pub fn withdraw_unallocated_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawUnallocatedReward<'info>>,
+        reward_index: u8,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::withdraw_unallocated_reward::handler(
+            ctx,
+            reward_index,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Enable the reward extension for a Solve. The reward extension tracks up to
+    /// NUM_EXTENSION_REWARDS additional rewards in companion accounts of the pool, its positions and tick arrays.
+    /// Once enabled, instructions that do not keep the extension in sync are rejected for this pool,
+    /// except decrease_liquidity which settles the extension with the SolveRewardExtension only.
+    /// The flag is stored in the tail of the Solve account, allocated if needed with rent paid by the funder.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the first reward-index in this Solve
+    ///
+    /// #### Special Errors
+    /// - `RewardExtensionAlreadyEnabled` - The reward extension is already enabled for this pool.
+    /// - `RewardExtensionLimitOrdersExist` - The pool has open limit orders.
+    pub fn initialize_reward_extension(ctx: Context<InitializeRewardExtension>) -> Result<()> {
+        instructions::v2::initialize_reward_extension::handler(ctx)
+    }
+
+    /// Initialize an additional reward of the reward extension of a Solve.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the specified reward-index of the reward extension
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index that we'd like to initialize. (0 <= index < NUM_EXTENSION_REWARDS)
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
+    ///                          index of the reward extension, or exceeds NUM_EXTENSION_REWARDS, or
+    ///                          all reward slots of the reward extension have been initialized.
+    pub fn initialize_extension_reward(
+        ctx: Context<InitializeExtensionReward>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::v2::initialize_extension_reward::handler(ctx, reward_index)
+    }
+
+    /// Set the reward emissions for an additional reward of the reward extension of a Solve.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the specified reward-index of the reward extension
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index < NUM_EXTENSION_REWARDS) that we'd like to modify.
+    /// - `emissions_per_second_x64` - The amount of rewards emitted in this pool.
+    ///
+    /// #### Special Errors
+    /// - `RewardVaultAmountInsufficient` - The amount of rewards in the reward vault cannot emit
+    ///                                     more than a day of desired emissions.
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - The provided reward index exceeds NUM_EXTENSION_REWARDS.
+    pub fn set_extension_reward_emissions(
+        ctx: Context<SetExtensionRewardEmissions>,
+        reward_index: u8,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        instructions::v2::set_extension_reward_emissions::handler(
+            ctx,
+            reward_index,
+            emissions_per_second_x64,
+        )
+    }
+
+    /// Set the authority of an additional reward of the reward extension of a Solve.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the specified reward-index of the reward extension
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index < NUM_EXTENSION_REWARDS) that we'd like to modify.
+    pub fn set_extension_reward_authority(
+        ctx: Context<SetExtensionRewardAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        instructions::v2::set_extension_reward_authority::handler(ctx, reward_index)
+    }
+
+    /// Initialize the reward extension of a tick array, which holds the reward growths outside
+    /// of the additional rewards for the ticks of the tick array.
+    ///
+    /// ### Parameters
+    /// - `start_tick_index` - The starting tick index of the tick array.
+    ///
+    /// #### Special Errors
+    /// - `InvalidStartTick` - if the provided start tick is out of bounds or is not a multiple of
+    ///                        TICK_ARRAY_SIZE * tick spacing.
+    pub fn initialize_tick_array_reward_extension(
+        ctx: Context<InitializeTickArrayRewardExtension>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        instructions::v2::initialize_tick_array_reward_extension::handler(ctx, start_tick_index)
+    }
+
+    /// Initialize the reward extension of a position, which holds the accrued additional rewards of the position.
+    /// Must be initialized before liquidity is added to the position, or removed from a locked position.
+    /// The position account grows by one byte, paid by the funder, and can then only be closed with its extension.
+    pub fn initialize_position_reward_extension(
+        ctx: Context<InitializePositionRewardExtension>,
+    ) -> Result<()> {
+        instructions::v2::initialize_position_reward_extension::handler(ctx)
+    }
+
+    /// Update the accrued additional rewards of the reward extension for a position.
+    ///
+    /// #### Special Errors
+    /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
+    /// - `LiquidityZero` - Position has zero liquidity and therefore already has the most updated reward values.
+    /// - `RewardExtensionRequired` - A reward extension account of the pool, position or ticks is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    pub fn update_extension_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateExtensionRewards<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::update_extension_rewards::handler(ctx, remaining_accounts_info)
+    }
+
+    /// Collect additional rewards of the reward extension accrued for this position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    pub fn collect_extension_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectExtensionReward<'info>>,
+        reward_index: u8,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::collect_extension_reward::handler(
+            ctx,
+            reward_index,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Set the boost of the liquidity of locked positions for the boosted rewards of the reward extension.
+    /// A permanent lock is boosted by `permanent_boost_bps`, timed and vesting locks are boosted in proportion
+    /// to their remaining locked duration, up to `max_timed_boost_bps` for `max_boost_duration` or more.
+    /// Vesting locks only boost the liquidity that is not vested yet.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the first reward-index in this Solve
+    ///
+    /// ### Parameters
+    /// - `permanent_boost_bps` - The boost of permanently locked positions, in basis points of their liquidity.
+    /// - `max_timed_boost_bps` - The boost of timed and vesting locks of at least `max_boost_duration`.
+    /// - `max_boost_duration` - The remaining lock duration in seconds from which the boost no longer increases.
+    ///
+    /// #### Special Errors
+    /// - `InvalidLockBoostConfig` - A boost exceeds MAX_LOCK_BOOST_BPS, or a timed boost is set
+    ///                              without a max boost duration.
+    pub fn set_lock_boost_config(
+        ctx: Context<SetLockBoostConfig>,
+        permanent_boost_bps: u16,
+        max_timed_boost_bps: u16,
+        max_boost_duration: u64,
+    ) -> Result<()> {
+        instructions::v2::set_lock_boost_config::handler(
+            ctx,
+            permanent_boost_bps,
+            max_timed_boost_bps,
+            max_boost_duration,
+        )
+    }
+
+    /// Set whether an additional reward of the reward extension is boosted for locked positions.
+    /// A boosted reward is shared in proportion to the liquidity of the positions plus their boost.
+    /// Only the additional rewards of the reward extension can be boosted: Solve and Tick have no room
+    /// to track the boosted liquidity, so the rewards of the Solve are never boosted.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority for the specified reward-index of the reward extension
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index < NUM_EXTENSION_REWARDS) that we'd like to modify.
+    /// - `boosted` - Whether the reward is boosted.
+    ///
+    /// #### Special Errors
+    /// - `RewardBoostNotConfigurable` - The reward has already been emitted.
+    /// - `InvalidRewardIndex` - The provided reward index exceeds NUM_EXTENSION_REWARDS.
+    pub fn set_extension_reward_boosted(
+        ctx: Context<SetExtensionRewardBoosted>,
+        reward_index: u8,
+        boosted: bool,
+    ) -> Result<()> {
+        instructions::v2::set_extension_reward_boosted::handler(ctx, reward_index, boosted)
+    }
+
+    /// Update the boost of a position from its LockConfig, after its additional rewards are updated.
+    /// Positions without a LockConfig, or whose lock can be unlocked, are not boosted.
+    /// The boosts of timed and vesting locks decrease over time, anyone can invoke this instruction to refresh them.
+    ///
+    /// #### Special Errors
+    /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
+    /// - `RewardExtensionRequired` - A reward extension account of the pool, position or ticks is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    pub fn update_position_boost<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdatePositionBoost<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::update_position_boost::handler(ctx, remaining_accounts_info)
+    }
+
+    /// Perform a swap in this Solve
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
+    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and an account of the extension is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            0,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a two-hop swap in this Solve
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
+    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
     /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
     /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
     ///
@@ -1091,9 +1858,124 @@ pub mod solve {
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if solve one & two are the same pool.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and an account of the extension is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
     #[allow(clippy::too_many_arguments)]
     pub fn two_hop_swap_v2<'info>(
-        ctx: Context<'_, '_, '_, 'info, TwoHopSwapV2<'info>>,
+        ctx: Context<'_, '_, 'info, 'info, TwoHopSwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::two_hop_swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            0,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a swap in this Solve, charging a referral fee on top of the swap input.
+    /// The referral token account is passed in the ReferralTokenAccount slice of the remaining accounts,
+    /// and the transfer hook accounts for the referral fee in the TransferHookReferral slice.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `referral_fee_rate` - The referral fee in basis points of the swap input, charged on top of it and sent to
+    ///                         the referral token account. The referral fee is included in `amount` and
+    ///                         `other_amount_threshold` for the input token.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
+    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `ReferralFeeRateMaxExceeded` - Provided referral fee rate exceeds MAX_REFERRAL_FEE_RATE.
+    /// - `InvalidReferralTokenAccount` - The referral token account is missing or does not hold the input token.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and an account of the extension is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_with_referral_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        referral_fee_rate: u16,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            referral_fee_rate,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a two-hop swap in this Solve, charging a referral fee on top of the swap input of hop one.
+    /// The referral token account is passed in the ReferralTokenAccount slice of the remaining accounts,
+    /// and the transfer hook accounts for the referral fee in the TransferHookReferral slice.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
+    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
+    /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
+    /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
+    /// - `referral_fee_rate` - The referral fee in basis points of the swap input of hop one, charged on top of it and
+    ///                         sent to the referral token account. The referral fee is included in `amount` and
+    ///                         `other_amount_threshold` for the input token.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
+    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
+    /// - `DuplicateTwoHopPool` - Error if solve one & two are the same pool.
+    /// - `ReferralFeeRateMaxExceeded` - Provided referral fee rate exceeds MAX_REFERRAL_FEE_RATE.
+    /// - `InvalidReferralTokenAccount` - The referral token account is missing or does not hold the input token.
+    /// - `RewardExtensionRequired` - The reward extension is enabled for the pool and an account of the extension is missing.
+    /// - `InvalidRewardExtensionAccount` - A provided account is not a reward extension of the pool or position.
+    #[allow(clippy::too_many_arguments)]
+    pub fn two_hop_swap_with_referral_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TwoHopSwapV2<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         amount_specified_is_input: bool,
@@ -1101,6 +1983,7 @@ pub mod solve {
         a_to_b_two: bool,
         sqrt_price_limit_one: u128,
         sqrt_price_limit_two: u128,
+        referral_fee_rate: u16,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
         instructions::v2::two_hop_swap::handler(
@@ -1112,10 +1995,238 @@ pub mod solve {
             a_to_b_two,
             sqrt_price_limit_one,
             sqrt_price_limit_two,
+            referral_fee_rate,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a flash swap in this Solve.
+    /// The output tokens are sent first, then the callback program is invoked with the swap result.
+    /// After the callback, the input tokens are transferred from the input token account of the token authority,
+    /// which the callback program must have funded.
+    /// The Solve is stored and locked before the output tokens are sent, instructions modifying the pool fail
+    /// with `SolveLocked` until the input tokens are received. The lock is kept in the tail of the Solve account,
+    /// allocated if needed with rent paid by the funder.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `callback_data` - The instruction data passed to the callback program, followed by the serialized `FlashSwapCallback`.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `InvalidFlashSwapCallbackProgram` - The callback program is this program.
+    /// - `FlashSwapNotPaid` - The input token account does not hold the input amount after the callback.
+    #[allow(clippy::too_many_arguments)]
+    pub fn flash_swap_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashSwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        callback_data: Vec<u8>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            callback_data,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Perform a swap through a route of up to MAX_ROUTE_SWAP_HOPS Solves.
+    /// The accounts for each hop are passed in the remaining accounts.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `transfer_hook_input_len` - The number of transfer hook accounts for the input token mint.
+    /// - `hops` - The direction, sqrt price limit and the number of variable-length accounts of each hop.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `InvalidIntermediaryMint` - Error if the output mint of a hop does not equal the input mint of the next hop.
+    /// - `InvalidRouteLength` - Error if the route is empty or has more than MAX_ROUTE_SWAP_HOPS hops.
+    /// - `DuplicateRoutePool` - Error if the same solve is used in more than one hop.
+    pub fn route_swap_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RouteSwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        transfer_hook_input_len: u8,
+        hops: Vec<RouteSwapHop>,
+    ) -> Result<()> {
+        instructions::v2::route_swap::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            transfer_hook_input_len,
+            hops,
+        )
+    }
+
+    /// Quote a swap in this Solve without moving tokens.
+    /// The swap is computed in the same way as swap_v2, but no account is modified.
+    /// The result is returned as a `SwapQuote` through return data, so it can also be used via CPI.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over pool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `TradeIsNotEnabled` - Trading is not enabled yet for this pool.
+    pub fn quote_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, QuoteSwap<'info>>,
+        amount: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<SwapQuote> {
+        instructions::v2::quote_swap::handler(
+            ctx,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            remaining_accounts_info,
+        )
+    }
+
+    /// Borrow tokens from the vaults of a Solve.
+    /// The loan must be repaid by a flash_repay instruction with the same amounts
+    /// later in the same transaction.
+    /// The fee is charged at the fee rate of the Solve and is distributed to the liquidity in range
+    /// when the loan is taken and to the protocol, in the same way as swap fees.
+    ///
+    /// ### Parameters
+    /// - `amount_a` - The amount of token A to borrow.
+    /// - `amount_b` - The amount of token B to borrow.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - Both `amount_a` and `amount_b` are 0.
+    /// - `FlashLoanCpiNotAllowed` - The instruction is invoked via CPI.
+    /// - `FlashRepayNotFound` - No matching flash_repay instruction follows this instruction.
+    /// - `FlashLoanAmountMismatch` - The amounts of the flash_repay instruction do not match.
+    pub fn flash_borrow<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashBorrow<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_borrow::handler(ctx, amount_a, amount_b, remaining_accounts_info)
+    }
+
+    /// Repay tokens borrowed by a flash_borrow instruction earlier in the same transaction,
+    /// plus the fee accrued by the flash_borrow instruction.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the token accounts.
+    ///
+    /// ### Parameters
+    /// - `amount_a` - The amount of token A borrowed.
+    /// - `amount_b` - The amount of token B borrowed.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - Both `amount_a` and `amount_b` are 0.
+    /// - `FlashLoanCpiNotAllowed` - The instruction is invoked via CPI.
+    /// - `FlashBorrowNotFound` - No matching flash_borrow instruction precedes this instruction.
+    /// - `FlashLoanAmountMismatch` - The amounts of the flash_borrow instruction do not match.
+    pub fn flash_repay<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlashRepay<'info>>,
+        amount_a: u64,
+        amount_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::flash_repay::handler(ctx, amount_a, amount_b, remaining_accounts_info)
+    }
+
+    /// Open a limit order on a one tick spacing range on the far side of the current price.
+    /// The order is filled by the swap which crosses the far tick of the range, or by a later swap if the Oracle
+    /// account is not writable, and the proceeds can no longer be swapped back.
+    /// Limit orders are stored in the Oracle account of the Solve and earn fees until filled, but no rewards.
+    /// The rewards emitted to the liquidity of an order are released to the reward authority when it is closed.
+    /// The funder deposits `LIMIT_ORDER_DEPOSIT` lamports into the LimitOrder account, refunded when it is closed.
+    ///
+    /// ### Authority
+    /// - "owner" - The owner of the limit order and the authority to withdraw tokens from the token account.
+    ///
+    /// ### Parameters
+    /// - `tick_lower_index` - The lower tick index of the range. The upper tick index is `tick_lower_index + tick_spacing`.
+    /// - `a_to_b` - True to sell token A for token B (range above the current price).
+    ///              False to sell token B for token A (range below the current price).
+    /// - `liquidity_amount` - The total amount of Liquidity the user is willing to deposit.
+    /// - `token_max` - The maximum amount of the sold token to deposit.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `InvalidLimitOrderTickIndex` - The range is not usable or its ticks are not in the same tick array.
+    /// - `InvalidLimitOrderRange` - The range is not on the far side of the current price.
+    /// - `LimitOrderCapacityExceeded` - The Solve already has the max number of limit orders.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    pub fn open_limit_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenLimitOrder<'info>>,
+        tick_lower_index: i32,
+        a_to_b: bool,
+        liquidity_amount: u128,
+        token_max: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::open_limit_order::handler(
+            ctx,
+            tick_lower_index,
+            a_to_b,
+            liquidity_amount,
+            token_max,
             remaining_accounts_info,
         )
     }
 
+    /// Close a limit order and pay out its tokens and fees to the owner.
+    /// A filled order pays out the bought token. An order not filled yet is cancelled.
+    /// The rent and the deposit of the LimitOrder account are refunded to the receiver.
+    ///
+    /// ### Authority
+    /// - "owner" - The owner of the limit order.
+    ///
+    /// #### Special Errors
+    /// - `LimitOrderNotFound` - The limit order is not registered in the Oracle account.
+    pub fn close_limit_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseLimitOrder<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        instructions::v2::close_limit_order::handler(ctx, remaining_accounts_info)
+    }
+
     pub fn initialize_config_extension(ctx: Context<InitializeConfigExtension>) -> Result<()> {
         instructions::v2::initialize_config_extension::handler(ctx)
     }
@@ -1124,10 +2235,165 @@ pub mod solve {
         instructions::v2::set_config_extension_authority::handler(ctx)
     }
 
+    pub fn accept_config_extension_authority(
+        ctx: Context<AcceptConfigExtensionAuthority>,
+    ) -> Result<()> {
+        instructions::v2::accept_config_extension_authority::handler(ctx)
+    }
+
     pub fn set_token_badge_authority(ctx: Context<SetTokenBadgeAuthority>) -> Result<()> {
         instructions::v2::set_token_badge_authority::handler(ctx)
     }
 
+    pub fn accept_token_badge_authority(ctx: Context<AcceptTokenBadgeAuthority>) -> Result<()> {
+        instructions::v2::accept_token_badge_authority::handler(ctx)
+    }
+
+    /// Sets the fee distribution table of a SolvesConfigExtension.
+    /// While the table is set, the protocol fees of the pools in this config can only be distributed
+    /// to the recipients with `distribute_protocol_fees_v2`.
+    /// Only the current collect protocol fees authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "collect_protocol_fees_authority" - Set authority that can collect protocol fees in the SolveConfig
+    ///
+    /// ### Parameters
+    /// - `protocol_fee_recipients` - The recipients and their shares in basis points. An empty list clears the table.
+    ///
+    /// #### Special Errors
+    /// - `InvalidProtocolFeeRecipients` - If there are more than MAX_PROTOCOL_FEE_RECIPIENTS recipients,
+    ///                                    a recipient is duplicated or has no share, or the shares do not add up to 10000.
+    pub fn set_protocol_fee_recipients(
+        ctx: Context<SetProtocolFeeRecipients>,
+        protocol_fee_recipients: Vec<ProtocolFeeRecipient>,
+    ) -> Result<()> {
+        instructions::v2::set_protocol_fee_recipients::handler(ctx, protocol_fee_recipients)
+    }
+
+    /// Sets the admin timelock delay for a SolveConfig.
+    /// While the delay is non-zero, fee rate changes must be queued and can be executed after the delay.
+    /// Without a SolvesConfigExtension there is no timelock.
+    /// Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
+    ///
+    /// ### Parameters
+    /// - `admin_timelock_delay` - The minimum delay in seconds. Zero disables the timelock.
+    ///
+    /// #### Special Errors
+    /// - `AdminTimelockEnabled` - If the admin timelock is enabled. The change must be queued instead.
+    /// - `AdminTimelockDelayMaxExceeded` - If the delay exceeds MAX_ADMIN_TIMELOCK_DELAY.
+    pub fn set_admin_timelock_delay(
+        ctx: Context<SetAdminTimelockDelay>,
+        admin_timelock_delay: u64,
+    ) -> Result<()> {
+        instructions::v2::set_admin_timelock_delay::handler(ctx, admin_timelock_delay)
+    }
+
+    /// Queues a parameter change that can be executed once the admin timelock delay has passed.
+    /// A target can have only one queued change per parameter.
+    /// The change expires if it is not executed within QUEUED_CHANGE_EXECUTION_WINDOW after it becomes executable.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
+    ///
+    /// ### Parameters
+    /// - `parameter_change` - The parameter and its new value.
+    ///
+    /// #### Special Errors
+    /// - `InvalidQueuedChangeTarget` - If the target is not a Solve of this config for pool changes,
+    ///                                 not a FeeTier of this config for fee tier changes,
+    ///                                 or not the config itself for config changes.
+    /// - `AdminTimelockDisabled` - If the admin timelock is disabled. The change can be applied directly.
+    /// - `FeeRateMaxExceeded` - If the provided fee_rate exceeds MAX_FEE_RATE.
+    /// - `ProtocolFeeRateMaxExceeded` - If the provided protocol fee rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `AdminTimelockDelayMaxExceeded` - If the provided admin timelock delay exceeds MAX_ADMIN_TIMELOCK_DELAY.
+    pub fn queue_parameter_change(
+        ctx: Context<QueueParameterChange>,
+        parameter_change: ParameterChange,
+    ) -> Result<()> {
+        instructions::v2::queue_parameter_change::handler(ctx, parameter_change)
+    }
+
+    /// Executes a queued parameter change and closes the queued change account.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
+    ///
+    /// #### Special Errors
+    /// - `QueuedChangeNotExecutable` - If the current admin timelock delay has not passed since the change was queued.
+    /// - `QueuedChangeExpired` - If the execution window has passed. The change can only be cancelled.
+    /// - `InvalidQueuedChangeTarget` - If the Solve or FeeTier does not match the target of the change.
+    pub fn execute_parameter_change(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+        instructions::v2::execute_parameter_change::handler(ctx)
+    }
+
+    /// Cancels a queued parameter change and closes the queued change account.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the SolveConfig
+    pub fn cancel_parameter_change(ctx: Context<CancelParameterChange>) -> Result<()> {
+        instructions::v2::cancel_parameter_change::handler(ctx)
+    }
+
+    pub fn set_pause_authority(ctx: Context<SetPauseAuthority>) -> Result<()> {
+        instructions::v2::set_pause_authority::handler(ctx)
+    }
+
+    pub fn accept_pause_authority(ctx: Context<AcceptPauseAuthority>) -> Result<()> {
+        instructions::v2::accept_pause_authority::handler(ctx)
+    }
+
+    /// Sets the pause flags of a SolvesConfig. The flags apply to all pools of the config, in the instructions that
+    /// take the SolvesConfigExtension. Other instructions are paused by them once the pool is synced
+    /// with sync_pool_pause_flags.
+    /// Withdrawals (decrease liquidity, collect fees and rewards) are never paused.
+    ///
+    /// ### Authority
+    /// - "pause_authority" - Set authority that can pause pools in the SolvesConfigExtension
+    ///
+    /// ### Parameters
+    /// - `pause_flags` - Bitmask of PAUSE_FLAG_SWAP, PAUSE_FLAG_INCREASE_LIQUIDITY
+    ///                   and PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS. Zero unpauses.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPauseFlags` - If an unknown flag is set.
+    pub fn set_config_pause_flags(
+        ctx: Context<SetConfigPauseFlags>,
+        pause_flags: u8,
+    ) -> Result<()> {
+        instructions::v2::set_config_pause_flags::handler(ctx, pause_flags)
+    }
+
+    /// Sets the pause flags of a Solve. The flags are combined with the flags of the SolvesConfig.
+    /// Withdrawals (decrease liquidity, collect fees and rewards) are never paused.
+    /// The flags are stored in the tail of the Solve account, allocated if needed with rent paid by the funder.
+    ///
+    /// ### Authority
+    /// - "pause_authority" - Set authority that can pause pools in the SolvesConfigExtension
+    ///
+    /// ### Parameters
+    /// - `pause_flags` - Bitmask of PAUSE_FLAG_SWAP, PAUSE_FLAG_INCREASE_LIQUIDITY
+    ///                   and PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS. Zero unpauses.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPauseFlags` - If an unknown flag is set.
+    pub fn set_pool_pause_flags(ctx: Context<SetPoolPauseFlags>, pause_flags: u8) -> Result<()> {
+        instructions::v2::set_pool_pause_flags::handler(ctx, pause_flags)
+    }
+
+    /// Copies the pause flags of the SolvesConfig into the tail of a Solve account, so that the instructions
+    /// that do not take the SolvesConfigExtension are also paused by them. Anyone can sync a pool.
+    /// The tail is allocated if needed with rent paid by the funder.
+    pub fn sync_pool_pause_flags(ctx: Context<SyncPoolPauseFlags>) -> Result<()> {
+        instructions::v2::sync_pool_pause_flags::handler(ctx)
+    }
+
+    pub fn set_tick_array_rent_recipient(ctx: Context<SetTickArrayRentRecipient>) -> Result<()> {
+        instructions::v2::set_tick_array_rent_recipient::handler(ctx)
+    }
+
     pub fn initialize_token_badge(ctx: Context<InitializeTokenBadge>) -> Result<()> {
         instructions::v2::initialize_token_badge::handler(ctx)
     }
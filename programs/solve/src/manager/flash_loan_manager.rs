@@ -0,0 +1,96 @@
+use crate::{
+    errors::ErrorCode,
+    manager::swap_manager::calculate_fees,
+    math::{checked_mul_div_round_up, FEE_RATE_MUL_VALUE},
+    state::Solve,
+};
+use anchor_lang::prelude::*;
+use std::convert::TryInto;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PostFlashLoanUpdate {
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub next_fee_growth_global_a: u128,
+    pub next_fee_growth_global_b: u128,
+}
+
+/// Flash loan fee is charged at the pool fee rate on the borrowed amount, rounded up.
+pub fn calculate_flash_loan_fee(amount: u64, fee_rate: u16) -> Result<u64> {
+    let fee = checked_mul_div_round_up(amount as u128, fee_rate as u128, FEE_RATE_MUL_VALUE)?;
+    fee.try_into()
+        .map_err(|_| ErrorCode::NumberCastError.into())
+}
+
+/// Split the flash loan fees between LPs (in-range liquidity) and the protocol,
+/// in the same way as swap fees.
+pub fn calculate_flash_loan_update(
+    solve: &Solve,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<PostFlashLoanUpdate> {
+    let fee_a = calculate_flash_loan_fee(amount_a, solve.fee_rate)?;
+    let fee_b = calculate_flash_loan_fee(amount_b, solve.fee_rate)?;
+
+    let (protocol_fee_a, next_fee_growth_global_a) = calculate_fees(
+        fee_a,
+        solve.protocol_fee_rate,
+        solve.liquidity,
+        0,
+        solve.fee_growth_global_a,
+    );
+    let (protocol_fee_b, next_fee_growth_global_b) = calculate_fees(
+        fee_b,
+        solve.protocol_fee_rate,
+        solve.liquidity,
+        0,
+        solve.fee_growth_global_b,
+    );
+
+    Ok(PostFlashLoanUpdate {
+        fee_a,
+        fee_b,
+        protocol_fee_a,
+        protocol_fee_b,
+        next_fee_growth_global_a,
+        next_fee_growth_global_b,
+    })
+}
+
+#[cfg(test)]
+mod flash_loan_manager_tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_flash_loan_fee_rounds_up() {
+        // 0.3%
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 3000).unwrap(), 3_000);
+        assert_eq!(calculate_flash_loan_fee(1, 3000).unwrap(), 1);
+        assert_eq!(calculate_flash_loan_fee(1_000_000, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_calculate_flash_loan_update_splits_fees() {
+        let solve = Solve {
+            fee_rate: 3000,
+            // 1/20 of the fees go to the protocol
+            protocol_fee_rate: 500,
+            liquidity: 1 << 20,
+            fee_growth_global_a: 100,
+            ..Solve::default()
+        };
+
+        let update = calculate_flash_loan_update(&solve, 1_000_000, 0).unwrap();
+        assert_eq!(update.fee_a, 3_000);
+        assert_eq!(update.fee_b, 0);
+        assert_eq!(update.protocol_fee_a, 150);
+        assert_eq!(update.protocol_fee_b, 0);
+        assert_eq!(
+            update.next_fee_growth_global_a,
+            100 + ((2_850u128 << 64) >> 20)
+        );
+        assert_eq!(update.next_fee_growth_global_b, 0);
+    }
+}
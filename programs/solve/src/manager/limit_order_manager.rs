@@ -0,0 +1,426 @@
+use crate::{
+    manager::{
+        position_manager::next_position_reward_infos,
+        swap_manager::PostSwapUpdate,
+        tick_manager::{
+            next_fee_growths_inside, next_reward_growths_inside, next_tick_fill_limit_order_update,
+        },
+    },
+    math::{
+        checked_mul_shift_right, convert_to_liquidity_delta, get_amount_delta_a,
+        get_amount_delta_b, sqrt_price_from_tick_index,
+    },
+    state::*,
+    util::SwapTickSequence,
+};
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct LimitOrderFillUpdate {
+    pub tick_lower_update: TickUpdate,
+    pub tick_upper_update: TickUpdate,
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
+}
+
+// Calculates the state after removing the liquidity of a limit order whose far tick has been crossed.
+// Fees and rewards are accrued until the order is filled.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_limit_order_fill(
+    entry: &LimitOrderEntry,
+    tick_lower: &Tick,
+    tick_upper: &Tick,
+    tick_spacing: u16,
+    tick_current_index: i32,
+    fee_growth_global_a: u128,
+    fee_growth_global_b: u128,
+    reward_infos: &[SolveRewardInfo; NUM_REWARDS],
+) -> Result<LimitOrderFillUpdate> {
+    let liquidity = entry.liquidity;
+    let liquidity_delta = convert_to_liquidity_delta(liquidity, true)?;
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = next_fee_growths_inside(
+        tick_current_index,
+        tick_lower,
+        entry.tick_lower_index,
+        tick_upper,
+        entry.tick_upper_index(tick_spacing),
+        fee_growth_global_a,
+        fee_growth_global_b,
+    );
+
+    // If fee deltas overflow, default to a zero value (same as positions).
+    let growth_delta_a = fee_growth_inside_a.wrapping_sub(entry.fee_growth_checkpoint_a);
+    let fee_delta_a = checked_mul_shift_right(liquidity, growth_delta_a).unwrap_or(0);
+
+    let growth_delta_b = fee_growth_inside_b.wrapping_sub(entry.fee_growth_checkpoint_b);
+    let fee_delta_b = checked_mul_shift_right(liquidity, growth_delta_b).unwrap_or(0);
+
+    let reward_growths_inside = next_reward_growths_inside(
+        tick_current_index,
+        tick_lower,
+        entry.tick_lower_index,
+        tick_upper,
+        entry.tick_upper_index(tick_spacing),
+        reward_infos,
+    );
+
+    Ok(LimitOrderFillUpdate {
+        tick_lower_update: next_tick_fill_limit_order_update(tick_lower, liquidity_delta, false)?,
+        tick_upper_update: next_tick_fill_limit_order_update(tick_upper, liquidity_delta, true)?,
+        fee_owed_a: entry.fee_owed_a.wrapping_add(fee_delta_a),
+        fee_owed_b: entry.fee_owed_b.wrapping_add(fee_delta_b),
+        reward_infos: next_position_reward_infos(
+            liquidity,
+            &reward_growths_inside,
+            &entry.reward_infos(),
+        ),
+    })
+}
+
+// Calculates the tokens held by a filled limit order, whose liquidity has been removed from the ticks.
+// The order holds only the bought token.
+pub fn calculate_filled_limit_order_amounts(
+    entry: &LimitOrderEntry,
+    tick_spacing: u16,
+) -> Result<(u64, u64)> {
+    let liquidity = entry.liquidity;
+    let lower_price = sqrt_price_from_tick_index(entry.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(entry.tick_upper_index(tick_spacing));
+    if entry.is_a_to_b() {
+        Ok((
+            0,
+            get_amount_delta_b(lower_price, upper_price, liquidity, false)?,
+        ))
+    } else {
+        Ok((
+            get_amount_delta_a(lower_price, upper_price, liquidity, false)?,
+            0,
+        ))
+    }
+}
+
+// The LimitOrder account holds a deposit until it is closed, see LIMIT_ORDER_DEPOSIT.
+pub fn collect_limit_order_deposit<'info>(
+    funder: &Signer<'info>,
+    limit_order: &Account<'info, LimitOrder>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let limit_order_account = limit_order.to_account_info();
+    let instruction =
+        system_instruction::transfer(funder.key, limit_order_account.key, LIMIT_ORDER_DEPOSIT);
+    let account_infos = [
+        funder.to_account_info(),
+        limit_order_account,
+        system_program.to_account_info(),
+    ];
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
+
+// Fills the limit orders crossed by a swap, so that their proceeds cannot be swapped back.
+// Both ticks of a limit order are stored in the same tick array. If the far tick has been crossed by
+// this swap, the tick array is always included in the swap tick sequence. Orders crossed by a previous
+// swap that could not fill them are filled only if their tick array is included.
+pub fn fill_limit_orders(
+    oracle_accessor: &OracleAccessor,
+    solve: &Solve,
+    swap_tick_sequence: &mut SwapTickSequence,
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+) -> Result<()> {
+    let tick_spacing = solve.tick_spacing;
+    let tick_current_index = swap_update.next_tick_index;
+    let (fee_growth_global_a, fee_growth_global_b) = if a_to_b {
+        (
+            swap_update.next_fee_growth_global,
+            solve.fee_growth_global_b,
+        )
+    } else {
+        (
+            solve.fee_growth_global_a,
+            swap_update.next_fee_growth_global,
+        )
+    };
+
+    oracle_accessor.fill_limit_orders(tick_current_index, tick_spacing, |entry| {
+        let tick_lower_index = entry.tick_lower_index;
+        let tick_upper_index = entry.tick_upper_index(tick_spacing);
+
+        let Some(array_index) = swap_tick_sequence.get_array_index(tick_lower_index, tick_spacing)
+        else {
+            return Ok(false);
+        };
+        let tick_lower =
+            swap_tick_sequence.get_tick(array_index, tick_lower_index, tick_spacing)?;
+        let tick_upper =
+            swap_tick_sequence.get_tick(array_index, tick_upper_index, tick_spacing)?;
+
+        let update = calculate_limit_order_fill(
+            entry,
+            &tick_lower,
+            &tick_upper,
+            tick_spacing,
+            tick_current_index,
+            fee_growth_global_a,
+            fee_growth_global_b,
+            &swap_update.next_reward_infos,
+        )?;
+
+        swap_tick_sequence.update_tick(
+            array_index,
+            tick_lower_index,
+            tick_spacing,
+            &update.tick_lower_update,
+        )?;
+        swap_tick_sequence.update_tick(
+            array_index,
+            tick_upper_index,
+            tick_spacing,
+            &update.tick_upper_update,
+        )?;
+
+        entry.fee_owed_a = update.fee_owed_a;
+        entry.fee_owed_b = update.fee_owed_b;
+        entry.update_reward_infos(&update.reward_infos);
+        Ok(true)
+    })
+}
+
+#[cfg(test)]
+mod limit_order_manager_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+    const LIQUIDITY: u128 = 1_000;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(data: Vec<u8>) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: crate::ID,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn solve_account() -> TestAccount {
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        solve.try_serialize(&mut data).unwrap();
+        TestAccount::new(data)
+    }
+
+    // An a_to_b order on [0, TICK_SPACING) and a b_to_a order on [-TICK_SPACING, 0)
+    fn oracle_account(solve: Pubkey) -> TestAccount {
+        let mut data = vec![0u8; Oracle::space(0, 2)];
+        data[..8].copy_from_slice(&Oracle::DISCRIMINATOR);
+        let oracle: &mut Oracle = bytemuck::from_bytes_mut(&mut data[8..OBSERVATIONS_OFFSET]);
+        oracle.solve = solve;
+        oracle.limit_order_capacity = 2;
+
+        let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data).unwrap();
+        for (tick_lower_index, a_to_b) in [(0, true), (-(TICK_SPACING as i32), false)] {
+            oracle
+                .add_limit_order(
+                    limit_orders,
+                    LimitOrderEntry {
+                        limit_order: Pubkey::new_unique(),
+                        tick_lower_index,
+                        a_to_b: a_to_b as u8,
+                        liquidity: LIQUIDITY,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+        TestAccount::new(data)
+    }
+
+    fn filled_flags(oracle_info: &AccountInfo) -> Vec<bool> {
+        let data = oracle_info.try_borrow_data().unwrap();
+        let (_, limit_orders) = load_oracle_with_limit_orders(&data).unwrap();
+        limit_orders.iter().map(|entry| entry.is_filled()).collect()
+    }
+
+    #[test]
+    fn test_calculate_limit_order_fill() {
+        let mut entry = LimitOrderEntry {
+            tick_lower_index: 0,
+            a_to_b: 1,
+            liquidity: LIQUIDITY,
+            fee_growth_checkpoint_a: 2 << 64,
+            fee_owed_a: 10,
+            ..Default::default()
+        };
+        entry.update_reward_infos(&[
+            PositionRewardInfo {
+                growth_inside_checkpoint: 1 << 64,
+                amount_owed: 20,
+            },
+            PositionRewardInfo::default(),
+            PositionRewardInfo::default(),
+        ]);
+
+        let tick_lower = Tick {
+            initialized: true,
+            liquidity_net: LIQUIDITY as i128,
+            liquidity_gross: LIQUIDITY,
+            ..Default::default()
+        };
+        // The upper tick has been crossed, so its outside growths are the growths inside the order
+        let mut tick_upper = Tick {
+            initialized: true,
+            liquidity_net: -(LIQUIDITY as i128),
+            liquidity_gross: LIQUIDITY,
+            fee_growth_outside_a: 5 << 64,
+            ..Default::default()
+        };
+        tick_upper.reward_growths_outside[0] = 4 << 64;
+        let mut reward_infos = [SolveRewardInfo::default(); NUM_REWARDS];
+        reward_infos[0].mint = Pubkey::new_unique();
+        reward_infos[0].growth_global_x64 = 9 << 64;
+
+        let update = calculate_limit_order_fill(
+            &entry,
+            &tick_lower,
+            &tick_upper,
+            TICK_SPACING,
+            TICK_SPACING as i32,
+            9 << 64,
+            0,
+            &reward_infos,
+        )
+        .unwrap();
+
+        assert_eq!(update.fee_owed_a, 10 + 3 * LIQUIDITY as u64);
+        assert_eq!(update.fee_owed_b, 0);
+        assert_eq!(
+            update.reward_infos[0].amount_owed,
+            20 + 3 * LIQUIDITY as u64
+        );
+        assert_eq!(update.reward_infos[0].growth_inside_checkpoint, 4 << 64);
+
+        // The ticks stay initialized without liquidity until the order is closed
+        assert!(update.tick_lower_update.initialized);
+        assert_eq!(update.tick_lower_update.liquidity_gross, 0);
+        assert_eq!(update.tick_lower_update.liquidity_net, 0);
+        assert!(update.tick_upper_update.initialized);
+        assert_eq!(update.tick_upper_update.liquidity_gross, 0);
+        assert_eq!(update.tick_upper_update.liquidity_net, 0);
+    }
+
+    #[test]
+    fn test_calculate_filled_limit_order_amounts() {
+        let lower_price = sqrt_price_from_tick_index(0);
+        let upper_price = sqrt_price_from_tick_index(TICK_SPACING as i32);
+        let mut entry = LimitOrderEntry {
+            tick_lower_index: 0,
+            a_to_b: 1,
+            liquidity: 1 << 64,
+            ..Default::default()
+        };
+
+        // a_to_b sells token A, so it holds only token B once filled
+        assert_eq!(
+            calculate_filled_limit_order_amounts(&entry, TICK_SPACING).unwrap(),
+            (
+                0,
+                get_amount_delta_b(lower_price, upper_price, 1 << 64, false).unwrap()
+            )
+        );
+
+        entry.a_to_b = 0;
+        assert_eq!(
+            calculate_filled_limit_order_amounts(&entry, TICK_SPACING).unwrap(),
+            (
+                get_amount_delta_a(lower_price, upper_price, 1 << 64, false).unwrap(),
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn test_fill_limit_orders_is_skipped_with_read_only_oracle() {
+        let mut solve = solve_account();
+        let solve_info = solve.account_info(true);
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+        let mut oracle = oracle_account(solve.key());
+
+        // The a_to_b order is fillable, but left in the ticks for a later swap
+        let oracle_info = oracle.account_info(false);
+        let oracle_accessor = OracleAccessor::new(&solve, oracle_info.clone()).unwrap();
+        oracle_accessor
+            .fill_limit_orders(TICK_SPACING as i32, TICK_SPACING, |_| {
+                panic!("no limit order is filled with a read-only Oracle account")
+            })
+            .unwrap();
+        assert_eq!(filled_flags(&oracle_info), vec![false, false]);
+    }
+
+    #[test]
+    fn test_fill_limit_orders() {
+        let mut solve = solve_account();
+        let solve_info = solve.account_info(true);
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+        let mut oracle = oracle_account(solve.key());
+        let oracle_info = oracle.account_info(true);
+        let oracle_accessor = OracleAccessor::new(&solve, oracle_info.clone()).unwrap();
+
+        // An order whose tick array is not available is left to a later swap
+        oracle_accessor
+            .fill_limit_orders(TICK_SPACING as i32, TICK_SPACING, |_| Ok(false))
+            .unwrap();
+        assert_eq!(filled_flags(&oracle_info), vec![false, false]);
+
+        // Only the a_to_b order is fillable above its range
+        let mut filled = Vec::new();
+        oracle_accessor
+            .fill_limit_orders(TICK_SPACING as i32, TICK_SPACING, |entry| {
+                filled.push(entry.tick_lower_index);
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(filled, vec![0]);
+        assert_eq!(filled_flags(&oracle_info), vec![true, false]);
+
+        // A filled order is not filled again
+        filled.clear();
+        oracle_accessor
+            .fill_limit_orders(-(TICK_SPACING as i32) - 1, TICK_SPACING, |entry| {
+                filled.push(entry.tick_lower_index);
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(filled, vec![-(TICK_SPACING as i32)]);
+        assert_eq!(filled_flags(&oracle_info), vec![true, true]);
+    }
+}
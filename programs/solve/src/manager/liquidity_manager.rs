@@ -8,7 +8,10 @@ use super::{
 };
 use crate::{
     errors::ErrorCode,
-    math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
+    math::{
+        get_amount_delta_a, get_amount_delta_b, get_liquidity_from_amount_a,
+        get_liquidity_from_amount_b, sqrt_price_from_tick_index,
+    },
     state::*,
 };
 use anchor_lang::prelude::*;
@@ -33,6 +36,7 @@ pub fn calculate_modify_liquidity<'info>(
     tick_array_lower: &dyn TickArrayType,
     tick_array_upper: &dyn TickArrayType,
     liquidity_delta: i128,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
     let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, solve.tick_spacing)?;
@@ -49,6 +53,7 @@ pub fn calculate_modify_liquidity<'info>(
         tick_array_lower.is_variable_size(),
         tick_array_upper.is_variable_size(),
         liquidity_delta,
+        reward_schedules,
         timestamp,
     )
 }
@@ -58,6 +63,7 @@ pub fn calculate_fee_and_reward_growths<'info>(
     position: &Position,
     tick_array_lower: &dyn TickArrayType,
     tick_array_upper: &dyn TickArrayType,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     timestamp: u64,
 ) -> Result<(PositionUpdate, [SolveRewardInfo; NUM_REWARDS])> {
     let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, solve.tick_spacing)?;
@@ -76,6 +82,7 @@ pub fn calculate_fee_and_reward_growths<'info>(
         tick_array_lower.is_variable_size(),
         tick_array_upper.is_variable_size(),
         0,
+        reward_schedules,
         timestamp,
     )?;
     Ok((update.position_update, update.reward_infos))
@@ -93,6 +100,7 @@ fn _calculate_modify_liquidity(
     tick_array_lower_variable_size: bool,
     tick_array_upper_variable_size: bool,
     liquidity_delta: i128,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
     // Disallow only updating position fee and reward growth when position has zero liquidity
@@ -100,7 +108,7 @@ fn _calculate_modify_liquidity(
         return Err(ErrorCode::LiquidityZero.into());
     }
 
-    let next_reward_infos = next_solve_reward_infos(solve, timestamp)?;
+    let next_reward_infos = next_solve_reward_infos(solve, reward_schedules, timestamp)?;
 
     let next_global_liquidity = next_solve_liquidity(
         solve,
@@ -219,6 +227,72 @@ pub fn calculate_liquidity_token_deltas(
     Ok((delta_a, delta_b))
 }
 
+// Calculates the maximum liquidity that can be added to the given position with at most amount_a and amount_b.
+// The token deltas for the returned liquidity (rounded up) never exceed the given amounts.
+pub fn calculate_liquidity_from_token_amounts(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    let (liquidity_a, liquidity_b) = calculate_liquidity_from_each_token_amount(
+        current_tick_index,
+        sqrt_price,
+        position,
+        amount_a,
+        amount_b,
+    )?;
+
+    let liquidity = liquidity_a.min(liquidity_b);
+    if liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    Ok(liquidity)
+}
+
+// Calculates the maximum liquidity that can be funded by amount_a and by amount_b respectively.
+// u128::MAX is returned for a token that is not required at the current price.
+pub fn calculate_liquidity_from_each_token_amount(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<(u128, u128)> {
+    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+
+    if current_tick_index < position.tick_lower_index {
+        // current tick below position
+        Ok((
+            get_liquidity_from_amount_a(lower_price, upper_price, amount_a)?,
+            u128::MAX,
+        ))
+    } else if current_tick_index < position.tick_upper_index {
+        // current tick inside position
+        // sqrt_price may be on the boundary of the position, then only one token is required
+        let liquidity_a = if sqrt_price < upper_price {
+            get_liquidity_from_amount_a(sqrt_price, upper_price, amount_a)?
+        } else {
+            u128::MAX
+        };
+        let liquidity_b = if sqrt_price > lower_price {
+            get_liquidity_from_amount_b(lower_price, sqrt_price, amount_b)?
+        } else {
+            u128::MAX
+        };
+        Ok((liquidity_a, liquidity_b))
+    } else {
+        // current tick above position
+        Ok((
+            u128::MAX,
+            get_liquidity_from_amount_b(lower_price, upper_price, amount_b)?,
+        ))
+    }
+}
+
 pub fn sync_modify_liquidity_values<'info>(
     solve: &mut Solve,
     position: &mut Position,
@@ -259,3 +333,145 @@ pub fn sync_modify_liquidity_values<'info>(
     Ok(())
 }
 
+#[cfg(test)]
+mod liquidity_manager_tests {
+    use super::*;
+    use crate::math::tick_index_from_sqrt_price;
+
+    const TICK_LOWER_INDEX: i32 = -1_280;
+    const TICK_UPPER_INDEX: i32 = 2_560;
+    const TOKEN_AMOUNTS: [u64; 6] = [1, 7, 1_000, 123_456_789, 1_000_000_000_000, u64::MAX >> 8];
+
+    fn position() -> Position {
+        Position {
+            tick_lower_index: TICK_LOWER_INDEX,
+            tick_upper_index: TICK_UPPER_INDEX,
+            ..Default::default()
+        }
+    }
+
+    // Feeds the round-down liquidity back through the round-up token deltas.
+    fn assert_within_token_max(current_tick_index: i32, sqrt_price: u128) {
+        let position = position();
+        for token_max_a in TOKEN_AMOUNTS {
+            for token_max_b in TOKEN_AMOUNTS {
+                let liquidity = match calculate_liquidity_from_token_amounts(
+                    current_tick_index,
+                    sqrt_price,
+                    &position,
+                    token_max_a,
+                    token_max_b,
+                ) {
+                    Ok(liquidity) => liquidity,
+                    Err(error) => {
+                        assert_eq!(error, ErrorCode::LiquidityZero.into());
+                        continue;
+                    }
+                };
+
+                let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+                    current_tick_index,
+                    sqrt_price,
+                    &position,
+                    liquidity as i128,
+                )
+                .unwrap();
+                assert!(delta_a <= token_max_a);
+                assert!(delta_b <= token_max_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_below_range() {
+        assert_within_token_max(
+            TICK_LOWER_INDEX - 100,
+            sqrt_price_from_tick_index(TICK_LOWER_INDEX - 100),
+        );
+    }
+
+    #[test]
+    fn test_lower_tick() {
+        let lower_price = sqrt_price_from_tick_index(TICK_LOWER_INDEX);
+        // price on the lower tick, only token A is required
+        assert_within_token_max(TICK_LOWER_INDEX, lower_price);
+        // price on the lower tick after a swap from A to B crossed it
+        assert_within_token_max(TICK_LOWER_INDEX - 1, lower_price);
+        // price just above the lower tick
+        assert_within_token_max(TICK_LOWER_INDEX, lower_price + 1);
+    }
+
+    #[test]
+    fn test_inside_range() {
+        for tick_index in [TICK_LOWER_INDEX + 1, 0, 1_000, TICK_UPPER_INDEX - 1] {
+            assert_within_token_max(tick_index, sqrt_price_from_tick_index(tick_index));
+        }
+
+        let sqrt_price = sqrt_price_from_tick_index(7) + 12_345;
+        assert_within_token_max(tick_index_from_sqrt_price(&sqrt_price), sqrt_price);
+    }
+
+    #[test]
+    fn test_upper_tick() {
+        let upper_price = sqrt_price_from_tick_index(TICK_UPPER_INDEX);
+        // price on the upper tick after a swap from A to B crossed it, only token B is required
+        assert_within_token_max(TICK_UPPER_INDEX - 1, upper_price);
+        // price on the upper tick, the position is out of range
+        assert_within_token_max(TICK_UPPER_INDEX, upper_price);
+        // price just below the upper tick
+        assert_within_token_max(TICK_UPPER_INDEX - 1, upper_price - 1);
+    }
+
+    #[test]
+    fn test_above_range() {
+        assert_within_token_max(
+            TICK_UPPER_INDEX + 100,
+            sqrt_price_from_tick_index(TICK_UPPER_INDEX + 100),
+        );
+    }
+
+    #[test]
+    fn test_boundary_requires_single_token() {
+        let position = position();
+        let lower_price = sqrt_price_from_tick_index(TICK_LOWER_INDEX);
+        let upper_price = sqrt_price_from_tick_index(TICK_UPPER_INDEX);
+
+        // token B is not needed on the lower tick, so zero B must not limit the liquidity
+        let liquidity = calculate_liquidity_from_token_amounts(
+            TICK_LOWER_INDEX,
+            lower_price,
+            &position,
+            1_000_000,
+            0,
+        )
+        .unwrap();
+        let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+            TICK_LOWER_INDEX,
+            lower_price,
+            &position,
+            liquidity as i128,
+        )
+        .unwrap();
+        assert!(delta_a <= 1_000_000);
+        assert_eq!(delta_b, 0);
+
+        // token A is not needed on the upper tick
+        let liquidity = calculate_liquidity_from_token_amounts(
+            TICK_UPPER_INDEX - 1,
+            upper_price,
+            &position,
+            0,
+            1_000_000,
+        )
+        .unwrap();
+        let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+            TICK_UPPER_INDEX - 1,
+            upper_price,
+            &position,
+            liquidity as i128,
+        )
+        .unwrap();
+        assert_eq!(delta_a, 0);
+        assert!(delta_b <= 1_000_000);
+    }
+}
@@ -1,6 +1,9 @@
 pub mod fee_rate_manager;
+pub mod flash_loan_manager;
+pub mod limit_order_manager;
 pub mod liquidity_manager;
 pub mod position_manager;
+pub mod reward_extension_manager;
 pub mod solve_manager;
 pub mod swap_manager;
 pub mod tick_array_manager;
@@ -1,7 +1,7 @@
 use crate::{
     errors::ErrorCode,
     math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    state::{Position, PositionRewardInfo, PositionUpdate, NUM_REWARDS},
 };
 
 pub fn next_position_modify_liquidity_update(
@@ -29,9 +29,29 @@ pub fn next_position_modify_liquidity_update(
     update.fee_owed_a = position.fee_owed_a.wrapping_add(fee_delta_a);
     update.fee_owed_b = position.fee_owed_b.wrapping_add(fee_delta_b);
 
-    for (i, update) in update.reward_infos.iter_mut().enumerate() {
+    update.reward_infos = next_position_reward_infos(
+        position.liquidity,
+        reward_growths_inside,
+        &position.reward_infos,
+    );
+
+    update.liquidity = add_liquidity_delta(position.liquidity, liquidity_delta)?;
+
+    Ok(update)
+}
+
+// Calculates the reward checkpoints and owed amounts of a position with the given liquidity.
+// Used for both the rewards of the solve and the rewards of its reward extension.
+pub fn next_position_reward_infos<const N: usize>(
+    liquidity: u128,
+    reward_growths_inside: &[u128; N],
+    reward_infos: &[PositionRewardInfo; N],
+) -> [PositionRewardInfo; N] {
+    let mut next_reward_infos = *reward_infos;
+
+    for (i, update) in next_reward_infos.iter_mut().enumerate() {
         let reward_growth_inside = reward_growths_inside[i];
-        let curr_reward_info = position.reward_infos[i];
+        let curr_reward_info = reward_infos[i];
 
         // Calculate reward delta.
         // If reward delta overflows, default to a zero value. This means the position loses all
@@ -39,7 +59,7 @@ pub fn next_position_modify_liquidity_update(
         let reward_growth_delta =
             reward_growth_inside.wrapping_sub(curr_reward_info.growth_inside_checkpoint);
         let amount_owed_delta =
-            checked_mul_shift_right(position.liquidity, reward_growth_delta).unwrap_or(0);
+            checked_mul_shift_right(liquidity, reward_growth_delta).unwrap_or(0);
 
         update.growth_inside_checkpoint = reward_growth_inside;
 
@@ -47,8 +67,6 @@ pub fn next_position_modify_liquidity_update(
         update.amount_owed = curr_reward_info.amount_owed.wrapping_add(amount_owed_delta);
     }
 
-    update.liquidity = add_liquidity_delta(position.liquidity, liquidity_delta)?;
-
-    Ok(update)
+    next_reward_infos
 }
 
@@ -0,0 +1,1393 @@
+use crate::{
+    errors::ErrorCode,
+    manager::{
+        position_manager::next_position_reward_infos,
+        solve_manager::next_solve_extension_reward_infos,
+        tick_manager::{
+            next_extension_reward_growths_inside,
+            next_extension_reward_growths_outside_modify_liquidity,
+            next_reward_growths_outside_cross,
+        },
+    },
+    math::{add_liquidity_delta, convert_to_liquidity_delta},
+    state::*,
+};
+use anchor_lang::{prelude::*, Discriminator};
+use arrayref::array_ref;
+use std::cell::{Ref, RefMut};
+
+/// Accounts of the reward extension passed to an instruction as remaining accounts.
+///
+/// The SolveRewardExtension comes first, followed by the PositionRewardExtension (if a position
+/// is modified) and the TickArrayRewardExtension accounts of the ticks that are touched, in any order.
+pub struct RewardExtensionAccounts<'info> {
+    solve_reward_extension_info: AccountInfo<'info>,
+    solve_reward_extension: SolveRewardExtension,
+    position_reward_extension_info: Option<AccountInfo<'info>>,
+    position_reward_extension: Option<PositionRewardExtension>,
+    tick_array_reward_extension_infos: Vec<AccountInfo<'info>>,
+}
+
+impl<'info> RewardExtensionAccounts<'info> {
+    /// Loads the reward extension accounts if the reward extension is enabled for the solve.
+    ///
+    /// # Returns
+    /// - `Ok(None)`: The reward extension is not enabled, the accounts are ignored
+    /// - `Ok(Some)`: The reward extension is enabled and the accounts belong to the solve
+    /// - `Err(RewardExtensionRequired)`: The reward extension is enabled but no account is passed
+    /// - `Err(InvalidRewardExtensionAccount)`: An account is not a reward extension of the solve
+    pub fn load(
+        solve: &Account<'info, Solve>,
+        accounts: &Option<Vec<AccountInfo<'info>>>,
+    ) -> Result<Option<Self>> {
+        if !SolveTail::load(&solve.to_account_info())?.reward_extension_enabled {
+            return Ok(None);
+        }
+
+        let accounts = match accounts {
+            Some(accounts) if !accounts.is_empty() => accounts,
+            _ => return Err(ErrorCode::RewardExtensionRequired.into()),
+        };
+
+        if accounts.iter().any(|account| !account.is_writable) {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+
+        let solve_reward_extension_info = accounts[0].clone();
+        let solve_reward_extension: SolveRewardExtension =
+            deserialize_account(&solve_reward_extension_info)?;
+        if solve_reward_extension.solve != solve.key() {
+            return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+        }
+
+        let mut position_reward_extension_info = None;
+        let mut position_reward_extension = None;
+        let mut tick_array_reward_extension_infos = Vec::with_capacity(accounts.len() - 1);
+        for account in accounts[1..].iter() {
+            let discriminator = {
+                let data = account.try_borrow_data()?;
+                if data.len() < 8 {
+                    return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+                }
+                *array_ref![data, 0, 8]
+            };
+
+            match discriminator {
+                PositionRewardExtension::DISCRIMINATOR => {
+                    if position_reward_extension.is_some() {
+                        return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+                    }
+                    position_reward_extension = Some(deserialize_account(account)?);
+                    position_reward_extension_info = Some(account.clone());
+                }
+                TickArrayRewardExtension::DISCRIMINATOR => {
+                    if account.owner != &crate::ID
+                        || account.data_len() != TickArrayRewardExtension::LEN
+                        || load_tick_array_reward_extension(account)?.solve != solve.key()
+                    {
+                        return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+                    }
+                    tick_array_reward_extension_infos.push(account.clone());
+                }
+                _ => return Err(ErrorCode::InvalidRewardExtensionAccount.into()),
+            }
+        }
+
+        Ok(Some(Self {
+            solve_reward_extension_info,
+            solve_reward_extension,
+            position_reward_extension_info,
+            position_reward_extension,
+            tick_array_reward_extension_infos,
+        }))
+    }
+
+    /// Loads the reward extension accounts of the solve from the remaining accounts of an instruction
+    /// that has no RemainingAccountsInfo.
+    ///
+    /// The remaining accounts may hold the reward extension accounts of several solves (e.g. both
+    /// pools of two_hop_swap). Each group of accounts starts with its SolveRewardExtension, and the
+    /// group of the solve is loaded in the same way as by `load`.
+    pub fn load_from_remaining_accounts(
+        solve: &Account<'info, Solve>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Option<Self>> {
+        if !SolveTail::load(&solve.to_account_info())?.reward_extension_enabled {
+            return Ok(None);
+        }
+
+        let mut group_start = None;
+        for (i, account) in remaining_accounts.iter().enumerate() {
+            if !is_solve_reward_extension(account)? {
+                continue;
+            }
+            if let Some(group_start) = group_start {
+                return Self::load(solve, &Some(remaining_accounts[group_start..i].to_vec()));
+            }
+            if deserialize_account::<SolveRewardExtension>(account)?.solve == solve.key() {
+                group_start = Some(i);
+            }
+        }
+
+        Self::load(
+            solve,
+            &group_start.map(|group_start| remaining_accounts[group_start..].to_vec()),
+        )
+    }
+
+    /// Updates the reward extension before the liquidity of a position is modified.
+    ///
+    /// Must be called before the solve, the position and the ticks are updated, so that the
+    /// extension rewards are accrued with the same state as the rewards of the solve.
+    pub fn update_for_modify_liquidity(
+        &mut self,
+        solve: &Account<'info, Solve>,
+        position: &Account<'info, Position>,
+        tick_array_lower: &AccountInfo<'info>,
+        tick_array_upper: &AccountInfo<'info>,
+        liquidity_delta: i128,
+        timestamp: u64,
+    ) -> Result<()> {
+        self.update_position(
+            solve,
+            position,
+            tick_array_lower,
+            tick_array_upper,
+            liquidity_delta,
+            None,
+            timestamp,
+        )
+    }
+
+    /// Updates the reward extension before the liquidity of a position is decreased.
+    ///
+    /// Positions with a PositionRewardExtension are updated in the same way as for any other
+    /// modification. Other positions have no boost and no checkpoints to update, so only the global
+    /// reward growths are accrued with the liquidity before the decrease. The removed liquidity does
+    /// not earn the extension rewards accrued inside the range since the position was last updated,
+    /// which keeps the extension rewards owed below the rewards emitted.
+    pub fn update_for_decrease_liquidity(
+        &mut self,
+        solve: &Account<'info, Solve>,
+        position: &Account<'info, Position>,
+        tick_array_lower: &AccountInfo<'info>,
+        tick_array_upper: &AccountInfo<'info>,
+        liquidity_delta: i128,
+        timestamp: u64,
+    ) -> Result<()> {
+        if self.position_reward_extension.is_some()
+            || Position::has_reward_extension(&position.to_account_info())
+        {
+            return self.update_for_modify_liquidity(
+                solve,
+                position,
+                tick_array_lower,
+                tick_array_upper,
+                liquidity_delta,
+                timestamp,
+            );
+        }
+
+        let reward_infos =
+            next_solve_extension_reward_infos(solve, &self.solve_reward_extension, timestamp)?;
+        self.solve_reward_extension
+            .update_rewards(reward_infos, timestamp);
+        self.exit()
+    }
+
+    /// Updates the boost of a position, after the extension rewards owed to the position are updated.
+    pub fn update_position_boost(
+        &mut self,
+        solve: &Account<'info, Solve>,
+        position: &Account<'info, Position>,
+        tick_array_lower: &AccountInfo<'info>,
+        tick_array_upper: &AccountInfo<'info>,
+        boost_bps: u16,
+        timestamp: u64,
+    ) -> Result<()> {
+        self.update_position(
+            solve,
+            position,
+            tick_array_lower,
+            tick_array_upper,
+            0,
+            Some(boost_bps),
+            timestamp,
+        )
+    }
+
+    pub fn lock_boost_config(&self) -> LockBoostConfig {
+        self.solve_reward_extension.lock_boost_config
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn update_position(
+        &mut self,
+        solve: &Account<'info, Solve>,
+        position: &Account<'info, Position>,
+        tick_array_lower: &AccountInfo<'info>,
+        tick_array_upper: &AccountInfo<'info>,
+        liquidity_delta: i128,
+        next_boost_bps: Option<u16>,
+        timestamp: u64,
+    ) -> Result<()> {
+        let tick_spacing = solve.tick_spacing;
+        let tick_lower_index = position.tick_lower_index;
+        let tick_upper_index = position.tick_upper_index;
+
+        let tick_lower = load_tick_array(tick_array_lower, &solve.key())?
+            .get_tick(tick_lower_index, tick_spacing)?;
+        let tick_upper = load_tick_array(tick_array_upper, &solve.key())?
+            .get_tick(tick_upper_index, tick_spacing)?;
+
+        let reward_infos =
+            next_solve_extension_reward_infos(solve, &self.solve_reward_extension, timestamp)?;
+        let reward_boosted = self.solve_reward_extension.reward_boosted;
+
+        let tick_lower_reward_growths_outside = load_tick_array_reward_extension(
+            self.tick_array_reward_extension_info(tick_lower_index, tick_spacing)?,
+        )?
+        .get_reward_growths_outside(tick_lower_index, tick_spacing)?;
+        let tick_upper_reward_growths_outside = load_tick_array_reward_extension(
+            self.tick_array_reward_extension_info(tick_upper_index, tick_spacing)?,
+        )?
+        .get_reward_growths_outside(tick_upper_index, tick_spacing)?;
+
+        let reward_growths_inside = next_extension_reward_growths_inside(
+            solve.tick_current_index,
+            &tick_lower,
+            &tick_lower_reward_growths_outside,
+            tick_lower_index,
+            &tick_upper,
+            &tick_upper_reward_growths_outside,
+            tick_upper_index,
+            &reward_infos,
+        );
+
+        let position_reward_extension = self
+            .position_reward_extension
+            .as_mut()
+            .ok_or(ErrorCode::RewardExtensionRequired)?;
+        if position_reward_extension.position != position.key() {
+            return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+        }
+
+        // Boosted rewards are owed in proportion to the boosted liquidity of the position
+        let position_liquidity_boost =
+            liquidity_boost(position.liquidity, position_reward_extension.boost_bps)?;
+        let position_boosted_liquidity = position
+            .liquidity
+            .checked_add(position_liquidity_boost)
+            .ok_or(ErrorCode::LiquidityOverflow)?;
+        let position_reward_infos = next_position_reward_infos(
+            position.liquidity,
+            &reward_growths_inside,
+            &position_reward_extension.reward_infos,
+        );
+        let position_boosted_reward_infos = next_position_reward_infos(
+            position_boosted_liquidity,
+            &reward_growths_inside,
+            &position_reward_extension.reward_infos,
+        );
+        position_reward_extension.reward_infos = std::array::from_fn(|i| {
+            if reward_boosted[i] {
+                position_boosted_reward_infos[i]
+            } else {
+                position_reward_infos[i]
+            }
+        });
+
+        let next_boost_bps = next_boost_bps.unwrap_or(position_reward_extension.boost_bps);
+        let next_position_liquidity_boost = liquidity_boost(
+            add_liquidity_delta(position.liquidity, liquidity_delta)?,
+            next_boost_bps,
+        )?;
+        position_reward_extension.update_boost_bps(next_boost_bps);
+        let liquidity_boost_delta = if next_position_liquidity_boost >= position_liquidity_boost {
+            convert_to_liquidity_delta(
+                next_position_liquidity_boost - position_liquidity_boost,
+                true,
+            )?
+        } else {
+            convert_to_liquidity_delta(
+                position_liquidity_boost - next_position_liquidity_boost,
+                false,
+            )?
+        };
+
+        // The lower and upper ticks may share a TickArrayRewardExtension, so they are updated one by one
+        self.update_tick(
+            solve,
+            &tick_lower,
+            tick_lower_index,
+            &reward_infos,
+            liquidity_delta,
+            liquidity_boost_delta,
+        )?;
+        self.update_tick(
+            solve,
+            &tick_upper,
+            tick_upper_index,
+            &reward_infos,
+            liquidity_delta,
+            liquidity_boost_delta
+                .checked_neg()
+                .ok_or(ErrorCode::LiquidityNetError)?,
+        )?;
+
+        self.solve_reward_extension
+            .update_rewards(reward_infos, timestamp);
+        if solve.tick_current_index >= tick_lower_index
+            && solve.tick_current_index < tick_upper_index
+        {
+            let liquidity_boost = add_liquidity_delta(
+                self.solve_reward_extension.liquidity_boost,
+                liquidity_boost_delta,
+            )?;
+            self.solve_reward_extension
+                .update_liquidity_boost(liquidity_boost);
+        }
+        self.exit()
+    }
+
+    /// Updates the reward extension before the liquidity of a limit order is added to its ticks.
+    ///
+    /// Limit orders have no PositionRewardExtension and no boost, so only the global reward growths
+    /// are accrued and the reward growths outside of the ticks initialized by the order are set.
+    pub fn update_for_open_limit_order(
+        &mut self,
+        solve: &Account<'info, Solve>,
+        tick_array: &AccountInfo<'info>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        liquidity_delta: i128,
+        timestamp: u64,
+    ) -> Result<()> {
+        let tick_spacing = solve.tick_spacing;
+        let (tick_lower, tick_upper) = {
+            let tick_array = load_tick_array(tick_array, &solve.key())?;
+            (
+                tick_array.get_tick(tick_lower_index, tick_spacing)?,
+                tick_array.get_tick(tick_upper_index, tick_spacing)?,
+            )
+        };
+
+        let reward_infos =
+            next_solve_extension_reward_infos(solve, &self.solve_reward_extension, timestamp)?;
+        self.update_tick(
+            solve,
+            &tick_lower,
+            tick_lower_index,
+            &reward_infos,
+            liquidity_delta,
+            0,
+        )?;
+        self.update_tick(
+            solve,
+            &tick_upper,
+            tick_upper_index,
+            &reward_infos,
+            liquidity_delta,
+            0,
+        )?;
+
+        self.solve_reward_extension
+            .update_rewards(reward_infos, timestamp);
+        self.exit()
+    }
+
+    /// Updates the reward extension for a swap.
+    ///
+    /// Must be called before the solve is updated with the result of the swap, because the
+    /// extension rewards are accrued with the liquidity before the swap.
+    pub fn update_for_swap(
+        &mut self,
+        solve: &Solve,
+        crossed_tick_indexes: &[i32],
+        a_to_b: bool,
+        timestamp: u64,
+    ) -> Result<()> {
+        let tick_spacing = solve.tick_spacing;
+        let reward_infos =
+            next_solve_extension_reward_infos(solve, &self.solve_reward_extension, timestamp)?;
+
+        let mut liquidity_boost = self.solve_reward_extension.liquidity_boost;
+        for &tick_index in crossed_tick_indexes {
+            let mut tick_array_reward_extension = load_tick_array_reward_extension_mut(
+                self.tick_array_reward_extension_info(tick_index, tick_spacing)?,
+            )?;
+            let reward_growths_outside =
+                tick_array_reward_extension.get_reward_growths_outside(tick_index, tick_spacing)?;
+            tick_array_reward_extension.update_reward_growths_outside(
+                tick_index,
+                tick_spacing,
+                next_reward_growths_outside_cross(&reward_growths_outside, &reward_infos),
+            )?;
+
+            // Same as the liquidity net, the liquidity boost net is subtracted when moving left
+            let liquidity_boost_net =
+                tick_array_reward_extension.get_liquidity_boost_net(tick_index, tick_spacing)?;
+            let liquidity_boost_delta = if a_to_b {
+                liquidity_boost_net
+                    .checked_neg()
+                    .ok_or(ErrorCode::LiquidityNetError)?
+            } else {
+                liquidity_boost_net
+            };
+            liquidity_boost = add_liquidity_delta(liquidity_boost, liquidity_boost_delta)?;
+        }
+
+        self.solve_reward_extension
+            .update_rewards(reward_infos, timestamp);
+        self.solve_reward_extension
+            .update_liquidity_boost(liquidity_boost);
+        self.exit()
+    }
+
+    // Updates the reward growths outside and the liquidity boost net of a tick before its liquidity is modified
+    fn update_tick(
+        &self,
+        solve: &Solve,
+        tick: &Tick,
+        tick_index: i32,
+        reward_infos: &[SolveRewardInfo; NUM_EXTENSION_REWARDS],
+        liquidity_delta: i128,
+        liquidity_boost_net_delta: i128,
+    ) -> Result<()> {
+        let tick_spacing = solve.tick_spacing;
+        let mut tick_array_reward_extension = load_tick_array_reward_extension_mut(
+            self.tick_array_reward_extension_info(tick_index, tick_spacing)?,
+        )?;
+        let next_reward_growths_outside = next_extension_reward_growths_outside_modify_liquidity(
+            tick,
+            tick_index,
+            solve.tick_current_index,
+            &tick_array_reward_extension.get_reward_growths_outside(tick_index, tick_spacing)?,
+            reward_infos,
+            liquidity_delta,
+        )?;
+        tick_array_reward_extension.update_reward_growths_outside(
+            tick_index,
+            tick_spacing,
+            next_reward_growths_outside,
+        )?;
+        let liquidity_boost_net = liquidity_boost_net_before_update(
+            &tick_array_reward_extension,
+            tick,
+            tick_index,
+            tick_spacing,
+        )?
+        .checked_add(liquidity_boost_net_delta)
+        .ok_or(ErrorCode::LiquidityNetError)?;
+        tick_array_reward_extension.update_liquidity_boost_net(
+            tick_index,
+            tick_spacing,
+            liquidity_boost_net,
+        )
+    }
+
+    fn tick_array_reward_extension_info(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+    ) -> Result<&AccountInfo<'info>> {
+        for tick_array_reward_extension_info in self.tick_array_reward_extension_infos.iter() {
+            if load_tick_array_reward_extension(tick_array_reward_extension_info)?
+                .contains(tick_index, tick_spacing)
+            {
+                return Ok(tick_array_reward_extension_info);
+            }
+        }
+        Err(ErrorCode::RewardExtensionRequired.into())
+    }
+
+    // Remaining accounts are not serialized by Anchor, so the updates are written here
+    fn exit(&self) -> Result<()> {
+        serialize_account(
+            &self.solve_reward_extension_info,
+            &self.solve_reward_extension,
+        )?;
+        if let (Some(info), Some(position_reward_extension)) = (
+            &self.position_reward_extension_info,
+            &self.position_reward_extension,
+        ) {
+            serialize_account(info, position_reward_extension)?;
+        }
+        Ok(())
+    }
+}
+
+// A tick that is not initialized has no liquidity boost net.
+// Values left by a tick that was uninitialized without the reward extension are discarded.
+fn liquidity_boost_net_before_update(
+    tick_array_reward_extension: &TickArrayRewardExtension,
+    tick: &Tick,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<i128> {
+    if tick.liquidity_gross == 0 {
+        return Ok(0);
+    }
+    tick_array_reward_extension.get_liquidity_boost_net(tick_index, tick_spacing)
+}
+
+/// Closes the PositionRewardExtension of a position that is being closed.
+///
+/// A position marked with a PositionRewardExtension can only be closed with its extension, passed as
+/// the first remaining account, so that no extension rewards are left behind and the extension of a
+/// bundled position is not reused by the next position opened with the same bundle index.
+///
+/// # Errors
+/// - `RewardExtensionRequired`: The position has a PositionRewardExtension but it is not passed
+/// - `InvalidRewardExtensionAccount`: The account is not the PositionRewardExtension of the position
+/// - `ClosePositionNotEmpty`: Extension rewards are still owed to the position
+pub fn close_position_reward_extension<'info>(
+    position: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    receiver: &AccountInfo<'info>,
+) -> Result<()> {
+    if !Position::has_reward_extension(position) {
+        return Ok(());
+    }
+
+    let position_reward_extension_info =
+        first_position_reward_extension_info(position, remaining_accounts)?;
+    if !position_reward_extension_info.is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+
+    let position_reward_extension: PositionRewardExtension =
+        deserialize_account(position_reward_extension_info)?;
+    if position_reward_extension
+        .reward_infos
+        .iter()
+        .any(|reward_info| reward_info.amount_owed != 0)
+    {
+        return Err(ErrorCode::ClosePositionNotEmpty.into());
+    }
+
+    let lamports = position_reward_extension_info.lamports();
+    position_reward_extension_info.sub_lamports(lamports)?;
+    receiver.add_lamports(lamports)?;
+    position_reward_extension_info.assign(&System::id());
+    position_reward_extension_info.realloc(0, false)?;
+    Ok(())
+}
+
+/// Verifies that a position being unlocked has no boost left.
+///
+/// The boost of a position whose lock can be unlocked is removed by update_position_boost, which must
+/// be invoked before the position is unlocked, so that the position does not keep earning boosted
+/// rewards once it is unlocked. The PositionRewardExtension is passed as the first remaining account.
+///
+/// # Errors
+/// - `RewardExtensionRequired`: The position has a PositionRewardExtension but it is not passed
+/// - `InvalidRewardExtensionAccount`: The account is not the PositionRewardExtension of the position
+/// - `PositionBoosted`: The boost of the position has not been removed
+pub fn verify_position_not_boosted(
+    position: &AccountInfo,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !Position::has_reward_extension(position) {
+        return Ok(());
+    }
+
+    let position_reward_extension_info =
+        first_position_reward_extension_info(position, remaining_accounts)?;
+    let position_reward_extension: PositionRewardExtension =
+        deserialize_account(position_reward_extension_info)?;
+    if position_reward_extension.boost_bps != 0 {
+        return Err(ErrorCode::PositionBoosted.into());
+    }
+    Ok(())
+}
+
+fn first_position_reward_extension_info<'a, 'info>(
+    position: &AccountInfo,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a AccountInfo<'info>> {
+    let position_reward_extension_info = remaining_accounts
+        .first()
+        .ok_or(ErrorCode::RewardExtensionRequired)?;
+    let (position_reward_extension_address, _) = Pubkey::find_program_address(
+        &[b"position_reward_extension", position.key.as_ref()],
+        &crate::ID,
+    );
+    if position_reward_extension_info.key() != position_reward_extension_address {
+        return Err(ErrorCode::InvalidRewardExtensionAccount.into());
+    }
+    Ok(position_reward_extension_info)
+}
+
+fn is_solve_reward_extension(account_info: &AccountInfo) -> Result<bool> {
+    if account_info.owner != &crate::ID {
+        return Ok(false);
+    }
+    let data = account_info.try_borrow_data()?;
+    Ok(data.len() >= 8 && *array_ref![data, 0, 8] == SolveRewardExtension::DISCRIMINATOR)
+}
+
+fn deserialize_account<T: AccountDeserialize>(account_info: &AccountInfo) -> Result<T> {
+    if account_info.owner != &crate::ID {
+        return Err(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram.into());
+    }
+    T::try_deserialize(&mut account_info.try_borrow_data()?.as_ref())
+}
+
+fn serialize_account<T: AccountSerialize>(account_info: &AccountInfo, account: &T) -> Result<()> {
+    account.try_serialize(&mut account_info.try_borrow_mut_data()?.as_mut())
+}
+
+// TickArrayRewardExtension accounts are validated when they are loaded into RewardExtensionAccounts
+fn load_tick_array_reward_extension<'a>(
+    account_info: &'a AccountInfo,
+) -> Result<Ref<'a, TickArrayRewardExtension>> {
+    let data = account_info.try_borrow_data()?;
+    Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..])))
+}
+
+fn load_tick_array_reward_extension_mut<'a>(
+    account_info: &'a AccountInfo,
+) -> Result<RefMut<'a, TickArrayRewardExtension>> {
+    let data = account_info.try_borrow_mut_data()?;
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(&mut data[8..])
+    }))
+}
+
+#[cfg(test)]
+mod reward_extension_manager_tests {
+    use super::*;
+
+    const TICK_SPACING: u16 = 1;
+    const TICK_LOWER_INDEX: i32 = 8;
+    const TICK_UPPER_INDEX: i32 = 24;
+    const LIQUIDITY: u128 = 1_000;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>) -> Self {
+            Self {
+                key,
+                owner: crate::ID,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn with_account<T: AccountSerialize>(key: Pubkey, account: &T, data_len: usize) -> Self {
+            let mut data = Vec::new();
+            account.try_serialize(&mut data).unwrap();
+            data.resize(data_len, 0);
+            Self::new(key, data)
+        }
+
+        fn with_zero_copy<T: bytemuck::Pod + Discriminator>(key: Pubkey, account: &T) -> Self {
+            let mut data = T::DISCRIMINATOR.to_vec();
+            data.extend_from_slice(bytemuck::bytes_of(account));
+            Self::new(key, data)
+        }
+
+        fn account_info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    // The rewards of the solve are not used by the extension, only its liquidity and current tick
+    fn solve_account(key: Pubkey, reward_extension_enabled: bool) -> TestAccount {
+        solve_account_with_liquidity(key, reward_extension_enabled, LIQUIDITY)
+    }
+
+    fn solve_account_with_liquidity(
+        key: Pubkey,
+        reward_extension_enabled: bool,
+        liquidity: u128,
+    ) -> TestAccount {
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            tick_current_index: 16,
+            liquidity,
+            ..Default::default()
+        };
+        let mut account = TestAccount::with_account(key, &solve, Solve::LEN);
+        let tail = SolveTail {
+            reward_extension_enabled,
+            ..Default::default()
+        };
+        tail.serialize(&mut account.data).unwrap();
+        account
+    }
+
+    // 1 token per second for the first reward, since timestamp 0
+    fn solve_reward_extension_account(solve: Pubkey) -> TestAccount {
+        let mut solve_reward_extension = SolveRewardExtension {
+            solve,
+            ..Default::default()
+        };
+        solve_reward_extension.reward_infos[0].mint = Pubkey::new_unique();
+        solve_reward_extension.reward_infos[0].emissions_per_second_x64 = 1 << 64;
+        TestAccount::with_account(
+            Pubkey::new_unique(),
+            &solve_reward_extension,
+            SolveRewardExtension::LEN,
+        )
+    }
+
+    fn position_reward_extension_account(position: Pubkey, boost_bps: u16) -> TestAccount {
+        let position_reward_extension = PositionRewardExtension {
+            position,
+            boost_bps,
+            ..Default::default()
+        };
+        let key = Pubkey::find_program_address(
+            &[b"position_reward_extension", position.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        TestAccount::with_account(
+            key,
+            &position_reward_extension,
+            PositionRewardExtension::LEN,
+        )
+    }
+
+    fn tick_array_reward_extension_account(
+        solve: Pubkey,
+        liquidity_boost_nets: &[(i32, i128)],
+    ) -> TestAccount {
+        let mut tick_array_reward_extension = TickArrayRewardExtension {
+            solve,
+            ..Default::default()
+        };
+        for &(tick_index, liquidity_boost_net) in liquidity_boost_nets {
+            tick_array_reward_extension
+                .update_liquidity_boost_net(tick_index, TICK_SPACING, liquidity_boost_net)
+                .unwrap();
+        }
+        TestAccount::with_zero_copy(Pubkey::new_unique(), &tick_array_reward_extension)
+    }
+
+    // The position ticks are initialized with the liquidity of the position
+    fn tick_array_account(solve: &Account<Solve>, initialized: bool) -> TestAccount {
+        let mut tick_array = FixedTickArray::default();
+        tick_array.initialize(solve, 0).unwrap();
+        if initialized {
+            for (tick_index, liquidity_net) in [
+                (TICK_LOWER_INDEX, LIQUIDITY as i128),
+                (TICK_UPPER_INDEX, -(LIQUIDITY as i128)),
+            ] {
+                let update = TickUpdate {
+                    initialized: true,
+                    liquidity_net,
+                    liquidity_gross: LIQUIDITY,
+                    ..Default::default()
+                };
+                tick_array
+                    .update_tick(tick_index, TICK_SPACING, &update)
+                    .unwrap();
+            }
+        }
+        TestAccount::with_zero_copy(Pubkey::new_unique(), &tick_array)
+    }
+
+    fn position_account(solve: Pubkey, liquidity: u128, data_len: usize) -> TestAccount {
+        let position = Position {
+            solve,
+            liquidity,
+            tick_lower_index: TICK_LOWER_INDEX,
+            tick_upper_index: TICK_UPPER_INDEX,
+            ..Default::default()
+        };
+        TestAccount::with_account(Pubkey::new_unique(), &position, data_len)
+    }
+
+    fn load_solve_reward_extension(account_info: &AccountInfo) -> SolveRewardExtension {
+        deserialize_account(account_info).unwrap()
+    }
+
+    fn load_position_reward_extension(account_info: &AccountInfo) -> PositionRewardExtension {
+        deserialize_account(account_info).unwrap()
+    }
+
+    #[test]
+    fn test_load_ignores_accounts_when_disabled() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, false);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        assert!(RewardExtensionAccounts::load(&solve, &None)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_load_requires_accounts_when_enabled() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        assert_eq!(
+            RewardExtensionAccounts::load(&solve, &None).err().unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+
+        // The SolveRewardExtension of another solve is rejected
+        let mut solve_reward_extension = solve_reward_extension_account(Pubkey::new_unique());
+        let accounts = Some(vec![solve_reward_extension.account_info()]);
+        assert_eq!(
+            RewardExtensionAccounts::load(&solve, &accounts)
+                .err()
+                .unwrap(),
+            ErrorCode::InvalidRewardExtensionAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_load_from_remaining_accounts() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        // The reward extension accounts of another solve come first
+        let other_solve_key = Pubkey::new_unique();
+        let mut other_solve_reward_extension = solve_reward_extension_account(other_solve_key);
+        let mut other_tick_array_reward_extension =
+            tick_array_reward_extension_account(other_solve_key, &[]);
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let mut tick_array_reward_extension = tick_array_reward_extension_account(solve_key, &[]);
+        let other_accounts = vec![
+            other_solve_reward_extension.account_info(),
+            other_tick_array_reward_extension.account_info(),
+        ];
+        let mut accounts = other_accounts.clone();
+        accounts.push(solve_reward_extension.account_info());
+        accounts.push(tick_array_reward_extension.account_info());
+
+        let reward_extension =
+            RewardExtensionAccounts::load_from_remaining_accounts(&solve, &accounts)
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            reward_extension.solve_reward_extension_info.key,
+            accounts[2].key
+        );
+        assert_eq!(reward_extension.tick_array_reward_extension_infos.len(), 1);
+        assert_eq!(
+            reward_extension.tick_array_reward_extension_infos[0].key,
+            accounts[3].key
+        );
+
+        // The group of the solve ends at the SolveRewardExtension of the next solve
+        let accounts = [&accounts[2..], &other_accounts[..]].concat();
+        let reward_extension =
+            RewardExtensionAccounts::load_from_remaining_accounts(&solve, &accounts)
+                .unwrap()
+                .unwrap();
+        assert_eq!(reward_extension.tick_array_reward_extension_infos.len(), 1);
+
+        assert_eq!(
+            RewardExtensionAccounts::load_from_remaining_accounts(&solve, &other_accounts)
+                .err()
+                .unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_update_for_swap_crossing_tick() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        // A boosted position ends at the upper tick
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let mut tick_array_reward_extension =
+            tick_array_reward_extension_account(solve_key, &[(TICK_UPPER_INDEX, -500)]);
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        {
+            let mut extension = load_solve_reward_extension(&solve_reward_extension_info);
+            extension.update_liquidity_boost(500);
+            serialize_account(&solve_reward_extension_info, &extension).unwrap();
+        }
+        let tick_array_reward_extension_info = tick_array_reward_extension.account_info();
+        let accounts = Some(vec![
+            solve_reward_extension_info.clone(),
+            tick_array_reward_extension_info.clone(),
+        ]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_swap(&solve, &[TICK_UPPER_INDEX], false, 100)
+            .unwrap();
+
+        let extension = load_solve_reward_extension(&solve_reward_extension_info);
+        let growth_global_x64 = extension.reward_infos[0].growth_global_x64;
+        assert_eq!(growth_global_x64, (100u128 << 64) / LIQUIDITY);
+        assert_eq!(extension.reward_last_updated_timestamp, 100);
+        // The boosted position is not in range anymore
+        assert_eq!(extension.liquidity_boost, 0);
+
+        let tick_array_reward_extension =
+            load_tick_array_reward_extension(&tick_array_reward_extension_info).unwrap();
+        assert_eq!(
+            tick_array_reward_extension
+                .get_reward_growths_outside(TICK_UPPER_INDEX, TICK_SPACING)
+                .unwrap(),
+            [growth_global_x64, 0, 0]
+        );
+
+        // A crossed tick without its TickArrayRewardExtension is rejected
+        let accounts = Some(vec![solve_reward_extension_info.clone()]);
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reward_extension
+                .update_for_swap(&solve, &[TICK_UPPER_INDEX], true, 200)
+                .err()
+                .unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_update_for_modify_liquidity_accrues_position_rewards() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array = tick_array_account(&solve, true);
+        let tick_array_info = tick_array.account_info();
+        let mut position = position_account(solve_key, LIQUIDITY, Position::LEN);
+        let position_info = position.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let mut position_reward_extension = position_reward_extension_account(position.key(), 0);
+        let mut tick_array_reward_extension = tick_array_reward_extension_account(solve_key, &[]);
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        let position_reward_extension_info = position_reward_extension.account_info();
+        let accounts = Some(vec![
+            solve_reward_extension_info.clone(),
+            position_reward_extension_info.clone(),
+            tick_array_reward_extension.account_info(),
+        ]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_modify_liquidity(
+                &solve,
+                &position,
+                &tick_array_info,
+                &tick_array_info,
+                -(LIQUIDITY as i128) / 2,
+                100,
+            )
+            .unwrap();
+
+        // The position holds all the liquidity in range, so it is owed all emissions, rounded down
+        let extension = load_solve_reward_extension(&solve_reward_extension_info);
+        let position_extension = load_position_reward_extension(&position_reward_extension_info);
+        assert_eq!(position_extension.reward_infos[0].amount_owed, 99);
+        assert_eq!(
+            position_extension.reward_infos[0].growth_inside_checkpoint,
+            extension.reward_infos[0].growth_global_x64
+        );
+        assert_eq!(extension.reward_last_updated_timestamp, 100);
+    }
+
+    #[test]
+    fn test_update_for_modify_liquidity_resets_boost_of_initialized_ticks() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        // The ticks of the position are not initialized, but hold a stale boost
+        let mut tick_array = tick_array_account(&solve, false);
+        let tick_array_info = tick_array.account_info();
+        let mut position = position_account(solve_key, 0, Position::LEN);
+        let position_info = position.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let mut position_reward_extension =
+            position_reward_extension_account(position.key(), LOCK_BOOST_BPS_DENOMINATOR);
+        let mut tick_array_reward_extension = tick_array_reward_extension_account(
+            solve_key,
+            &[(TICK_LOWER_INDEX, 777), (TICK_UPPER_INDEX, -777)],
+        );
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        let tick_array_reward_extension_info = tick_array_reward_extension.account_info();
+        let accounts = Some(vec![
+            solve_reward_extension_info.clone(),
+            position_reward_extension.account_info(),
+            tick_array_reward_extension_info.clone(),
+        ]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_modify_liquidity(
+                &solve,
+                &position,
+                &tick_array_info,
+                &tick_array_info,
+                LIQUIDITY as i128,
+                100,
+            )
+            .unwrap();
+
+        let tick_array_reward_extension =
+            load_tick_array_reward_extension(&tick_array_reward_extension_info).unwrap();
+        assert_eq!(
+            tick_array_reward_extension
+                .get_liquidity_boost_net(TICK_LOWER_INDEX, TICK_SPACING)
+                .unwrap(),
+            LIQUIDITY as i128
+        );
+        assert_eq!(
+            tick_array_reward_extension
+                .get_liquidity_boost_net(TICK_UPPER_INDEX, TICK_SPACING)
+                .unwrap(),
+            -(LIQUIDITY as i128)
+        );
+        // The position is in range
+        assert_eq!(
+            load_solve_reward_extension(&solve_reward_extension_info).liquidity_boost,
+            LIQUIDITY
+        );
+    }
+
+    #[test]
+    fn test_update_for_open_limit_order_initializes_ticks() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        // The ticks of the order are not initialized, but hold a stale boost
+        let mut tick_array = tick_array_account(&solve, false);
+        let tick_array_info = tick_array.account_info();
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let mut tick_array_reward_extension = tick_array_reward_extension_account(
+            solve_key,
+            &[(TICK_LOWER_INDEX, 777), (TICK_UPPER_INDEX, -777)],
+        );
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        let tick_array_reward_extension_info = tick_array_reward_extension.account_info();
+        let accounts = Some(vec![
+            solve_reward_extension_info.clone(),
+            tick_array_reward_extension_info.clone(),
+        ]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_open_limit_order(
+                &solve,
+                &tick_array_info,
+                TICK_LOWER_INDEX,
+                TICK_UPPER_INDEX,
+                LIQUIDITY as i128,
+                100,
+            )
+            .unwrap();
+
+        let extension = load_solve_reward_extension(&solve_reward_extension_info);
+        let growth_global_x64 = (100u128 << 64) / LIQUIDITY;
+        assert_eq!(
+            extension.reward_infos[0].growth_global_x64,
+            growth_global_x64
+        );
+        assert_eq!(extension.liquidity_boost, 0);
+
+        // All prior growth is assumed to happen below each tick
+        let tick_array_reward_extension =
+            load_tick_array_reward_extension(&tick_array_reward_extension_info).unwrap();
+        assert_eq!(
+            tick_array_reward_extension
+                .get_reward_growths_outside(TICK_LOWER_INDEX, TICK_SPACING)
+                .unwrap()[0],
+            growth_global_x64
+        );
+        assert_eq!(
+            tick_array_reward_extension
+                .get_reward_growths_outside(TICK_UPPER_INDEX, TICK_SPACING)
+                .unwrap()[0],
+            0
+        );
+        for tick_index in [TICK_LOWER_INDEX, TICK_UPPER_INDEX] {
+            assert_eq!(
+                tick_array_reward_extension
+                    .get_liquidity_boost_net(tick_index, TICK_SPACING)
+                    .unwrap(),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_for_decrease_liquidity_without_position_extension() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array = tick_array_account(&solve, true);
+        let tick_array_info = tick_array.account_info();
+        let mut position = position_account(solve_key, LIQUIDITY, Position::LEN);
+        let position_info = position.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+
+        // Only the SolveRewardExtension is needed to accrue the global growths
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        let accounts = Some(vec![solve_reward_extension_info.clone()]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_decrease_liquidity(
+                &solve,
+                &position,
+                &tick_array_info,
+                &tick_array_info,
+                -(LIQUIDITY as i128),
+                100,
+            )
+            .unwrap();
+
+        let extension = load_solve_reward_extension(&solve_reward_extension_info);
+        assert_eq!(
+            extension.reward_infos[0].growth_global_x64,
+            (100u128 << 64) / LIQUIDITY
+        );
+        assert_eq!(extension.reward_last_updated_timestamp, 100);
+    }
+
+    #[test]
+    fn test_update_for_decrease_liquidity_requires_position_extension() {
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account(solve_key, true);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array = tick_array_account(&solve, true);
+        let tick_array_info = tick_array.account_info();
+        let mut position = position_account(
+            solve_key,
+            LIQUIDITY,
+            Position::LEN + Position::REWARD_EXTENSION_MARKER_LEN,
+        );
+        let position_info = position.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let accounts = Some(vec![solve_reward_extension.account_info()]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reward_extension
+                .update_for_decrease_liquidity(
+                    &solve,
+                    &position,
+                    &tick_array_info,
+                    &tick_array_info,
+                    -(LIQUIDITY as i128),
+                    100,
+                )
+                .err()
+                .unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+    }
+
+    #[test]
+    fn test_close_position_reward_extension() {
+        let solve_key = Pubkey::new_unique();
+        let mut receiver = TestAccount::new(Pubkey::new_unique(), vec![]);
+        let receiver_info = receiver.account_info();
+
+        // Positions without an extension are closed as before
+        let mut position = position_account(solve_key, 0, Position::LEN);
+        close_position_reward_extension(&position.account_info(), &[], &receiver_info).unwrap();
+
+        let mut position = position_account(
+            solve_key,
+            0,
+            Position::LEN + Position::REWARD_EXTENSION_MARKER_LEN,
+        );
+        let position_info = position.account_info();
+        assert_eq!(
+            close_position_reward_extension(&position_info, &[], &receiver_info)
+                .err()
+                .unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+
+        // The extension of another position is rejected
+        let mut other_extension = position_reward_extension_account(Pubkey::new_unique(), 0);
+        assert_eq!(
+            close_position_reward_extension(
+                &position_info,
+                &[other_extension.account_info()],
+                &receiver_info
+            )
+            .err()
+            .unwrap(),
+            ErrorCode::InvalidRewardExtensionAccount.into()
+        );
+
+        // Extension rewards must be collected first
+        let mut extension = position_reward_extension_account(*position_info.key, 0);
+        let extension_info = extension.account_info();
+        {
+            let mut position_extension = load_position_reward_extension(&extension_info);
+            position_extension.update_reward_owed(1, 10);
+            serialize_account(&extension_info, &position_extension).unwrap();
+        }
+        assert_eq!(
+            close_position_reward_extension(&position_info, &[extension_info], &receiver_info)
+                .err()
+                .unwrap(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+    }
+
+    #[test]
+    fn test_update_for_modify_liquidity_accrues_boosted_rewards() {
+        // The position holds half of the liquidity in range and is boosted by 100%
+        let solve_key = Pubkey::new_unique();
+        let mut solve = solve_account_with_liquidity(solve_key, true, LIQUIDITY * 2);
+        let solve_info = solve.account_info();
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array = tick_array_account(&solve, true);
+        let tick_array_info = tick_array.account_info();
+        let mut position = position_account(solve_key, LIQUIDITY, Position::LEN);
+        let position_info = position.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+
+        // The first reward is boosted, the second one is not
+        let mut solve_reward_extension = solve_reward_extension_account(solve_key);
+        let solve_reward_extension_info = solve_reward_extension.account_info();
+        {
+            let mut extension = load_solve_reward_extension(&solve_reward_extension_info);
+            extension.reward_infos[1].mint = Pubkey::new_unique();
+            extension.reward_infos[1].emissions_per_second_x64 = 1 << 64;
+            extension.update_reward_boosted(0, true).unwrap();
+            extension.update_liquidity_boost(LIQUIDITY);
+            serialize_account(&solve_reward_extension_info, &extension).unwrap();
+        }
+        let mut position_reward_extension =
+            position_reward_extension_account(position.key(), LOCK_BOOST_BPS_DENOMINATOR);
+        let mut tick_array_reward_extension = tick_array_reward_extension_account(
+            solve_key,
+            &[
+                (TICK_LOWER_INDEX, LIQUIDITY as i128),
+                (TICK_UPPER_INDEX, -(LIQUIDITY as i128)),
+            ],
+        );
+        let position_reward_extension_info = position_reward_extension.account_info();
+        let accounts = Some(vec![
+            solve_reward_extension_info.clone(),
+            position_reward_extension_info.clone(),
+            tick_array_reward_extension.account_info(),
+        ]);
+
+        let mut reward_extension = RewardExtensionAccounts::load(&solve, &accounts)
+            .unwrap()
+            .unwrap();
+        reward_extension
+            .update_for_modify_liquidity(
+                &solve,
+                &position,
+                &tick_array_info,
+                &tick_array_info,
+                -(LIQUIDITY as i128) / 2,
+                100,
+            )
+            .unwrap();
+
+        let extension = load_solve_reward_extension(&solve_reward_extension_info);
+        assert_eq!(
+            extension.reward_infos[0].growth_global_x64,
+            (100u128 << 64) / (LIQUIDITY * 3)
+        );
+        assert_eq!(
+            extension.reward_infos[1].growth_global_x64,
+            (100u128 << 64) / (LIQUIDITY * 2)
+        );
+
+        // 2/3 of the boosted reward, and 1/2 of the other reward, rounded down
+        let position_extension = load_position_reward_extension(&position_reward_extension_info);
+        assert_eq!(position_extension.reward_infos[0].amount_owed, 66);
+        assert_eq!(position_extension.reward_infos[1].amount_owed, 49);
+
+        // The boost follows the liquidity of the position
+        assert_eq!(extension.liquidity_boost, LIQUIDITY / 2);
+    }
+
+    #[test]
+    fn test_verify_position_not_boosted() {
+        let solve_key = Pubkey::new_unique();
+
+        // Positions without an extension have no boost
+        let mut position = position_account(solve_key, 0, Position::LEN);
+        verify_position_not_boosted(&position.account_info(), &[]).unwrap();
+
+        let mut position = position_account(
+            solve_key,
+            0,
+            Position::LEN + Position::REWARD_EXTENSION_MARKER_LEN,
+        );
+        let position_info = position.account_info();
+        assert_eq!(
+            verify_position_not_boosted(&position_info, &[])
+                .err()
+                .unwrap(),
+            ErrorCode::RewardExtensionRequired.into()
+        );
+
+        let mut extension = position_reward_extension_account(*position_info.key, 5_000);
+        assert_eq!(
+            verify_position_not_boosted(&position_info, &[extension.account_info()])
+                .err()
+                .unwrap(),
+            ErrorCode::PositionBoosted.into()
+        );
+
+        let mut extension = position_reward_extension_account(*position_info.key, 0);
+        verify_position_not_boosted(&position_info, &[extension.account_info()]).unwrap();
+    }
+}
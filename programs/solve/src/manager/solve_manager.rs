@@ -4,37 +4,95 @@ use crate::state::*;
 
 // Calculates the next global reward growth variables based on the given timestamp.
 // The provided timestamp must be greater than or equal to the last updated timestamp.
+// Rewards only accrue within the emission window of their schedule.
 pub fn next_solve_reward_infos(
     solve: &Solve,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     next_timestamp: u64,
 ) -> Result<[SolveRewardInfo; NUM_REWARDS], ErrorCode> {
-    let curr_timestamp = solve.reward_last_updated_timestamp;
+    next_reward_infos(
+        &solve.reward_infos,
+        reward_schedules,
+        solve.liquidity,
+        solve.reward_last_updated_timestamp,
+        next_timestamp,
+    )
+}
+
+// Calculates the next global reward growth variables of the rewards tracked by a SolveRewardExtension.
+// The liquidity of the solve must not have been updated since the extension was last updated.
+// Boosted rewards are shared by the liquidity of the solve plus the liquidity boost of the extension.
+pub fn next_solve_extension_reward_infos(
+    solve: &Solve,
+    reward_extension: &SolveRewardExtension,
+    next_timestamp: u64,
+) -> Result<[SolveRewardInfo; NUM_EXTENSION_REWARDS], ErrorCode> {
+    // The rewards of the reward extension are not scheduled
+    let reward_schedules = [RewardSchedule::default(); NUM_EXTENSION_REWARDS];
+    let reward_infos = next_reward_infos(
+        &reward_extension.reward_infos,
+        &reward_schedules,
+        solve.liquidity,
+        reward_extension.reward_last_updated_timestamp,
+        next_timestamp,
+    )?;
+    if !reward_extension.is_boosted() {
+        return Ok(reward_infos);
+    }
+
+    let boosted_liquidity = solve
+        .liquidity
+        .checked_add(reward_extension.liquidity_boost)
+        .ok_or(ErrorCode::LiquidityOverflow)?;
+    let boosted_reward_infos = next_reward_infos(
+        &reward_extension.reward_infos,
+        &reward_schedules,
+        boosted_liquidity,
+        reward_extension.reward_last_updated_timestamp,
+        next_timestamp,
+    )?;
+
+    Ok(std::array::from_fn(|i| {
+        if reward_extension.reward_boosted[i] {
+            boosted_reward_infos[i]
+        } else {
+            reward_infos[i]
+        }
+    }))
+}
+
+fn next_reward_infos<const N: usize>(
+    reward_infos: &[SolveRewardInfo; N],
+    reward_schedules: &[RewardSchedule; N],
+    liquidity: u128,
+    curr_timestamp: u64,
+    next_timestamp: u64,
+) -> Result<[SolveRewardInfo; N], ErrorCode> {
     if next_timestamp < curr_timestamp {
         return Err(ErrorCode::InvalidTimestamp);
     }
 
     // No-op if no liquidity or no change in timestamp
-    if solve.liquidity == 0 || next_timestamp == curr_timestamp {
-        return Ok(solve.reward_infos);
+    if liquidity == 0 || next_timestamp == curr_timestamp {
+        return Ok(*reward_infos);
     }
 
     // Calculate new global reward growth
-    let mut next_reward_infos = solve.reward_infos;
-    let time_delta = u128::from(next_timestamp - curr_timestamp);
-    for reward_info in next_reward_infos.iter_mut() {
+    let mut next_reward_infos = *reward_infos;
+    for (reward_info, reward_schedule) in next_reward_infos.iter_mut().zip(reward_schedules) {
         if !reward_info.initialized() {
             continue;
         }
 
+        let time_delta =
+            u128::from(reward_schedule.emission_duration(curr_timestamp, next_timestamp));
+
         // Calculate the new reward growth delta.
         // If the calculation overflows, set the delta value to zero.
         // This will halt reward distributions for this reward.
-        let reward_growth_delta = checked_mul_div(
-            time_delta,
-            reward_info.emissions_per_second_x64,
-            solve.liquidity,
-        )
-        .unwrap_or(0);
+        let reward_growth_delta =
+            checked_mul_div(time_delta, reward_info.emissions_per_second_x64, liquidity)
+                .unwrap_or(0);
 
         // Add the reward growth delta to the global reward growth.
         let curr_growth_global = reward_info.growth_global_x64;
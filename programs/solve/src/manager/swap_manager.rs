@@ -21,6 +21,8 @@ pub struct PostSwapUpdate {
     pub next_reward_infos: [SolveRewardInfo; NUM_REWARDS],
     pub next_protocol_fee: u64,
     pub next_adaptive_fee_info: Option<AdaptiveFeeInfo>,
+    // Initialized ticks crossed by the swap, used to update the reward extension
+    pub crossed_tick_indexes: Vec<i32>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -31,6 +33,7 @@ pub fn swap(
     sqrt_price_limit: u128,
     amount_specified_is_input: bool,
     a_to_b: bool,
+    reward_schedules: &[RewardSchedule; NUM_REWARDS],
     timestamp: u64,
     adaptive_fee_info: &Option<AdaptiveFeeInfo>,
 ) -> Result<Box<PostSwapUpdate>> {
@@ -61,7 +64,7 @@ pub fn swap(
     let tick_spacing = solve.tick_spacing;
     let fee_rate = solve.fee_rate;
     let protocol_fee_rate = solve.protocol_fee_rate;
-    let next_reward_infos = next_solve_reward_infos(solve, timestamp)?;
+    let next_reward_infos = next_solve_reward_infos(solve, reward_schedules, timestamp)?;
 
     let mut amount_remaining: u64 = amount;
     let mut amount_calculated: u64 = 0;
@@ -76,6 +79,7 @@ pub fn swap(
         solve.fee_growth_global_b
     };
     let mut fee_sum: u64 = 0;
+    let mut crossed_tick_indexes: Vec<i32> = Vec::new();
 
     let mut fee_rate_manager = FeeRateManager::new(
         a_to_b,
@@ -180,6 +184,7 @@ pub fn swap(
                         tick_spacing,
                         &update,
                     )?;
+                    crossed_tick_indexes.push(next_tick_index);
                 }
 
                 let tick_offset = swap_tick_sequence.get_tick_offset(
@@ -259,10 +264,11 @@ pub fn swap(
         next_reward_infos,
         next_protocol_fee: curr_protocol_fee,
         next_adaptive_fee_info: fee_rate_manager.get_next_adaptive_fee_info(),
+        crossed_tick_indexes,
     }))
 }
 
-fn calculate_fees(
+pub fn calculate_fees(
     fee_amount: u64,
     protocol_fee_rate: u16,
     curr_liquidity: u128,
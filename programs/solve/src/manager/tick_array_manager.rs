@@ -1,8 +1,11 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 use solana_program::{program::invoke, system_instruction};
 
 use crate::errors::ErrorCode;
-use crate::state::{DynamicTick, Position, PositionUpdate, Tick, TickUpdate};
+use crate::state::{
+    DynamicTick, DynamicTickArray, DynamicTickArrayLoader, LimitOrder, Position, PositionUpdate,
+    Tick, TickUpdate,
+};
 const TICK_INITIALIZATION_SIZE: usize =
     DynamicTick::INITIALIZED_LEN - DynamicTick::UNINITIALIZED_LEN;
 
@@ -17,7 +20,7 @@ pub enum TickArrayRentTransfer {
 impl TickArrayRentTransfer {
     fn execute<'info>(
         &self,
-        position: &Account<'info, Position>,
+        position: &impl ToAccountInfo<'info>,
         tick_array: &AccountInfo<'info>,
         amount: u64,
     ) -> Result<()> {
@@ -120,6 +123,23 @@ pub fn collect_rent_for_ticks_in_position<'info>(
     funder: &Signer<'info>,
     position: &Account<'info, Position>,
     system_program: &Program<'info, System>,
+) -> Result<()> {
+    collect_rent_for_ticks(funder, position, system_program)
+}
+
+// Limit orders hold the rent for their two ticks in the same way as positions.
+pub fn collect_rent_for_ticks_in_limit_order<'info>(
+    funder: &Signer<'info>,
+    limit_order: &Account<'info, LimitOrder>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    collect_rent_for_ticks(funder, limit_order, system_program)
+}
+
+fn collect_rent_for_ticks<'info>(
+    funder: &Signer<'info>,
+    position: &impl ToAccountInfo<'info>,
+    system_program: &Program<'info, System>,
 ) -> Result<()> {
     let rent_amount = get_tick_rent_amount()? * 2;
 
@@ -135,8 +155,9 @@ pub fn collect_rent_for_ticks_in_position<'info>(
     Ok(())
 }
 
+// The position can be any account holding the rent for the ticks (e.g. Position, LimitOrder).
 pub fn update_tick_array_accounts<'info>(
-    position: &Account<'info, Position>,
+    position: &impl ToAccountInfo<'info>,
     lower_tick_array: AccountInfo<'info>,
     upper_tick_array: AccountInfo<'info>,
     lower_tick_array_update: &TickArrayUpdate,
@@ -144,13 +165,7 @@ pub fn update_tick_array_accounts<'info>(
 ) -> Result<()> {
     let tick_rent_amount = get_tick_rent_amount()?;
 
-    lower_tick_array_update
-        .transfer_rent
-        .execute(position, &lower_tick_array, tick_rent_amount)?;
-    upper_tick_array_update
-        .transfer_rent
-        .execute(position, &upper_tick_array, tick_rent_amount)?;
-
+    // Resize first, so that the rent refunded to the position is based on the final size
     lower_tick_array_update
         .size_update
         .execute(&lower_tick_array)?;
@@ -158,6 +173,13 @@ pub fn update_tick_array_accounts<'info>(
         .size_update
         .execute(&upper_tick_array)?;
 
+    lower_tick_array_update
+        .transfer_rent
+        .execute(position, &lower_tick_array, tick_rent_amount)?;
+    upper_tick_array_update
+        .transfer_rent
+        .execute(position, &upper_tick_array, tick_rent_amount)?;
+
     // Verify that the tick arrays are rent-exempt
     verify_rent_exempt(&position.to_account_info())?;
     verify_rent_exempt(&lower_tick_array)?;
@@ -168,7 +190,7 @@ pub fn update_tick_array_accounts<'info>(
 
 fn transfer_rent_to_tick_array<'info>(
     tick_array_account: &AccountInfo<'info>,
-    position: &Account<'info, Position>,
+    position: &impl ToAccountInfo<'info>,
     amount: u64,
 ) -> Result<()> {
     let position_account = position.to_account_info();
@@ -185,9 +207,23 @@ fn transfer_rent_to_tick_array<'info>(
 
 fn transfer_rent_to_position<'info>(
     tick_array_account: &AccountInfo<'info>,
-    position: &Account<'info, Position>,
+    position: &impl ToAccountInfo<'info>,
     amount: u64,
 ) -> Result<()> {
+    // Tick arrays migrated from FixedTickArray don't hold the rent of the positions opened
+    // before the migration (they keep it themselves), so only the surplus over the rent-exempt
+    // minimum is refunded. Other tick arrays always hold the amount.
+    let amount = if is_migrated_tick_array(tick_array_account)? {
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(tick_array_account.data_len());
+        amount.min(
+            tick_array_account
+                .lamports()
+                .saturating_sub(rent_exempt_lamports),
+        )
+    } else {
+        amount
+    };
+
     let position_account = position.to_account_info();
     let mut position_lamports = position_account.try_borrow_mut_lamports()?;
     let mut tick_array_lamports = tick_array_account.try_borrow_mut_lamports()?;
@@ -200,6 +236,13 @@ fn transfer_rent_to_position<'info>(
     Ok(())
 }
 
+fn is_migrated_tick_array(tick_array_account: &AccountInfo) -> Result<bool> {
+    let data = tick_array_account.try_borrow_data()?;
+    Ok(data.len() >= DynamicTickArray::MIN_LEN
+        && data[0..8] == DynamicTickArray::DISCRIMINATOR
+        && DynamicTickArrayLoader::load(&data[8..]).is_migrated())
+}
+
 fn increase_tick_array_size(tick_array_account: &AccountInfo) -> Result<()> {
     let tick_array_account_info = tick_array_account.to_account_info();
     let required_size = tick_array_account_info.data_len() + TICK_INITIALIZATION_SIZE;
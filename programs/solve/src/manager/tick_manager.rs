@@ -1,7 +1,7 @@
 use crate::{
     errors::ErrorCode,
     math::add_liquidity_delta,
-    state::{SolveRewardInfo, Tick, TickUpdate, NUM_REWARDS},
+    state::{SolveRewardInfo, Tick, TickUpdate, NUM_EXTENSION_REWARDS, NUM_REWARDS},
 };
 
 pub fn next_tick_cross_update(
@@ -14,17 +14,31 @@ pub fn next_tick_cross_update(
 
     update.fee_growth_outside_a = fee_growth_global_a.wrapping_sub(tick.fee_growth_outside_a);
     update.fee_growth_outside_b = fee_growth_global_b.wrapping_sub(tick.fee_growth_outside_b);
+    update.reward_growths_outside =
+        next_reward_growths_outside_cross(&{ tick.reward_growths_outside }, reward_infos);
+
+    Ok(update)
+}
+
+// Calculates the reward growths outside of a tick after it is crossed.
+// Used for both the rewards of the solve and the rewards of its reward extension.
+pub fn next_reward_growths_outside_cross<const N: usize>(
+    reward_growths_outside: &[u128; N],
+    reward_infos: &[SolveRewardInfo; N],
+) -> [u128; N] {
+    let mut next_reward_growths_outside = *reward_growths_outside;
 
     for (i, reward_info) in reward_infos.iter().enumerate() {
         if !reward_info.initialized() {
             continue;
         }
 
-        update.reward_growths_outside[i] = reward_info
+        next_reward_growths_outside[i] = reward_info
             .growth_global_x64
-            .wrapping_sub(tick.reward_growths_outside[i]);
+            .wrapping_sub(reward_growths_outside[i]);
     }
-    Ok(update)
+
+    next_reward_growths_outside
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -90,6 +104,62 @@ pub fn next_tick_modify_liquidity_update(
     })
 }
 
+// Calculates the reward growths outside of a tick for the rewards of the reward extension,
+// following the same rules as next_tick_modify_liquidity_update. The tick must be the one
+// before the liquidity is modified.
+pub fn next_extension_reward_growths_outside_modify_liquidity(
+    tick: &Tick,
+    tick_index: i32,
+    tick_current_index: i32,
+    reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    reward_infos: &[SolveRewardInfo; NUM_EXTENSION_REWARDS],
+    liquidity_delta: i128,
+) -> Result<[u128; NUM_EXTENSION_REWARDS], ErrorCode> {
+    if liquidity_delta == 0 {
+        return Ok(*reward_growths_outside);
+    }
+
+    let liquidity_gross = add_liquidity_delta(tick.liquidity_gross, liquidity_delta)?;
+    if liquidity_gross == 0 {
+        return Ok([0; NUM_EXTENSION_REWARDS]);
+    }
+
+    if tick.liquidity_gross != 0 {
+        return Ok(*reward_growths_outside);
+    }
+
+    // By convention, assume all prior growth happened below the tick
+    if tick_current_index >= tick_index {
+        Ok(reward_infos.map(|reward_info| reward_info.growth_global_x64))
+    } else {
+        Ok([0; NUM_EXTENSION_REWARDS])
+    }
+}
+
+// Removes the liquidity of a filled limit order from one of its ticks.
+// Unlike next_tick_modify_liquidity_update, the tick stays initialized even if no liquidity remains,
+// because a swap cannot resize dynamic tick arrays. The tick is cleared when the limit order is closed.
+pub fn next_tick_fill_limit_order_update(
+    tick: &Tick,
+    liquidity: i128,
+    is_upper_tick: bool,
+) -> Result<TickUpdate, ErrorCode> {
+    let mut update = TickUpdate::from(*tick);
+
+    update.liquidity_gross = add_liquidity_delta(tick.liquidity_gross, -liquidity)?;
+    update.liquidity_net = if is_upper_tick {
+        tick.liquidity_net
+            .checked_add(liquidity)
+            .ok_or(ErrorCode::LiquidityNetError)?
+    } else {
+        tick.liquidity_net
+            .checked_sub(liquidity)
+            .ok_or(ErrorCode::LiquidityNetError)?
+    };
+
+    Ok(update)
+}
+
 // Calculates the fee growths inside of tick_lower and tick_upper based on their
 // index relative to tick_current_index.
 pub fn next_fee_growths_inside(
@@ -151,33 +221,81 @@ pub fn next_reward_growths_inside(
     tick_upper_index: i32,
     reward_infos: &[SolveRewardInfo; NUM_REWARDS],
 ) -> [u128; NUM_REWARDS] {
-    let mut reward_growths_inside = [0; NUM_REWARDS];
+    reward_growths_inside(
+        tick_current_index,
+        tick_lower.initialized,
+        &{ tick_lower.reward_growths_outside },
+        tick_lower_index,
+        tick_upper.initialized,
+        &{ tick_upper.reward_growths_outside },
+        tick_upper_index,
+        reward_infos,
+    )
+}
+
+// Calculates the reward growths inside of tick_lower and tick_upper for the rewards of the
+// reward extension. The reward growths outside of the ticks are stored in TickArrayRewardExtension.
+#[allow(clippy::too_many_arguments)]
+pub fn next_extension_reward_growths_inside(
+    tick_current_index: i32,
+    tick_lower: &Tick,
+    tick_lower_reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    tick_lower_index: i32,
+    tick_upper: &Tick,
+    tick_upper_reward_growths_outside: &[u128; NUM_EXTENSION_REWARDS],
+    tick_upper_index: i32,
+    reward_infos: &[SolveRewardInfo; NUM_EXTENSION_REWARDS],
+) -> [u128; NUM_EXTENSION_REWARDS] {
+    reward_growths_inside(
+        tick_current_index,
+        tick_lower.initialized,
+        tick_lower_reward_growths_outside,
+        tick_lower_index,
+        tick_upper.initialized,
+        tick_upper_reward_growths_outside,
+        tick_upper_index,
+        reward_infos,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn reward_growths_inside<const N: usize>(
+    tick_current_index: i32,
+    tick_lower_initialized: bool,
+    tick_lower_reward_growths_outside: &[u128; N],
+    tick_lower_index: i32,
+    tick_upper_initialized: bool,
+    tick_upper_reward_growths_outside: &[u128; N],
+    tick_upper_index: i32,
+    reward_infos: &[SolveRewardInfo; N],
+) -> [u128; N] {
+    let mut reward_growths_inside = [0; N];
 
-    for i in 0..NUM_REWARDS {
+    for i in 0..N {
         if !reward_infos[i].initialized() {
             continue;
         }
 
         // By convention, assume all prior growth happened below the tick
-        let reward_growths_below = if !tick_lower.initialized {
+        let reward_growths_below = if !tick_lower_initialized {
             reward_infos[i].growth_global_x64
         } else if tick_current_index < tick_lower_index {
             reward_infos[i]
                 .growth_global_x64
-                .wrapping_sub(tick_lower.reward_growths_outside[i])
+                .wrapping_sub(tick_lower_reward_growths_outside[i])
         } else {
-            tick_lower.reward_growths_outside[i]
+            tick_lower_reward_growths_outside[i]
         };
 
         // By convention, assume all prior growth happened below the tick, not above
-        let reward_growths_above = if !tick_upper.initialized {
+        let reward_growths_above = if !tick_upper_initialized {
             0
         } else if tick_current_index < tick_upper_index {
-            tick_upper.reward_growths_outside[i]
+            tick_upper_reward_growths_outside[i]
         } else {
             reward_infos[i]
                 .growth_global_x64
-                .wrapping_sub(tick_upper.reward_growths_outside[i])
+                .wrapping_sub(tick_upper_reward_growths_outside[i])
         };
 
         reward_growths_inside[i] = reward_infos[i]
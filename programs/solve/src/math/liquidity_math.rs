@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::math::{increasing_price_order, mul_u256, U256Muldiv, Q64_RESOLUTION};
 
 // Adds a signed liquidity delta to a given integer liquidity amount.
 // Errors on overflow or underflow.
@@ -34,3 +35,51 @@ pub fn convert_to_liquidity_delta(
         -(liquidity_amount as i128)
     })
 }
+
+// Calculates the maximum liquidity that can be deposited with amount_a of token A
+// over the price range [sqrt_price_lower, sqrt_price_upper). Rounds down.
+//
+// liquidity = amount_a * (sqrt_price_lower * sqrt_price_upper) / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    // Q64.64, rounded down
+    let sqrt_price_product = mul_u256(sqrt_price_lower, sqrt_price_upper)
+        .shift_word_right()
+        .try_into_u128()?;
+
+    let (quotient, _) = mul_u256(amount_a as u128, sqrt_price_product)
+        .div(U256Muldiv::new(0, sqrt_price_diff), false);
+
+    quotient
+        .try_into_u128()
+        .map_err(|_| ErrorCode::LiquidityOverflow)
+}
+
+// Calculates the maximum liquidity that can be deposited with amount_b of token B
+// over the price range [sqrt_price_lower, sqrt_price_upper). Rounds down.
+//
+// liquidity = amount_b / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128, ErrorCode> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(ErrorCode::DivideByZero);
+    }
+
+    Ok(((amount_b as u128) << Q64_RESOLUTION) / sqrt_price_diff)
+}
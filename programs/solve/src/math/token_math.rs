@@ -24,6 +24,15 @@ pub const MAX_PROTOCOL_FEE_RATE: u16 = 2_500;
 // We want PROTOCOL_FEE_RATE_MUL_VALUE = 1/PROTOCOL_FEE_UNIT, so 1e4
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+// Referral fee rate is represented as a basis point.
+// Referral fee amount = swap_input_amount * referral_fee_rate / 10_000, charged on top of the swap input.
+// Max referral fee rate supported is 1% of the swap input amount.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 100;
+
+// Assuming that REFERRAL_FEE_RATE is represented as a basis point
+// We want REFERRAL_FEE_RATE_MUL_VALUE = 1/REFERRAL_FEE_UNIT, so 1e4
+pub const REFERRAL_FEE_RATE_MUL_VALUE: u128 = 10_000;
+
 #[derive(Debug)]
 pub enum AmountDeltaU64 {
     Valid(u64),
@@ -178,6 +187,19 @@ pub fn try_get_amount_delta_b(
     }
 }
 
+pub fn get_referral_fee_amount(swap_input_amount: u64, referral_fee_rate: u16) -> u64 {
+    // referral_fee_rate is less than REFERRAL_FEE_RATE_MUL_VALUE, so the result fits in u64
+    ((swap_input_amount as u128) * (referral_fee_rate as u128) / REFERRAL_FEE_RATE_MUL_VALUE) as u64
+}
+
+// Returns the swap input amount that can be paid with amount, including the referral fee on it.
+// swap_input_amount + get_referral_fee_amount(swap_input_amount) never exceeds amount.
+pub fn get_referral_fee_excluded_amount(amount: u64, referral_fee_rate: u16) -> u64 {
+    // The result is less than or equal to amount, so it fits in u64
+    ((amount as u128) * REFERRAL_FEE_RATE_MUL_VALUE
+        / (REFERRAL_FEE_RATE_MUL_VALUE + referral_fee_rate as u128)) as u64
+}
+
 pub fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128, u128) {
     if sqrt_price_0 > sqrt_price_1 {
         (sqrt_price_1, sqrt_price_0)
@@ -349,3 +371,51 @@ pub fn get_next_sqrt_price(
         )
     }
 }
+
+#[cfg(test)]
+mod referral_fee_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_referral_fee_amount() {
+        assert_eq!(get_referral_fee_amount(1_000_000, 0), 0);
+        assert_eq!(get_referral_fee_amount(1_000_000, 30), 3_000);
+        assert_eq!(
+            get_referral_fee_amount(1_000_000, MAX_REFERRAL_FEE_RATE),
+            10_000
+        );
+        // Rounded down
+        assert_eq!(get_referral_fee_amount(99, MAX_REFERRAL_FEE_RATE), 0);
+        assert_eq!(
+            get_referral_fee_amount(u64::MAX, MAX_REFERRAL_FEE_RATE),
+            u64::MAX / 100
+        );
+    }
+
+    #[test]
+    fn test_get_referral_fee_excluded_amount() {
+        assert_eq!(get_referral_fee_excluded_amount(1_000_000, 0), 1_000_000);
+        assert_eq!(
+            get_referral_fee_excluded_amount(1_010_000, MAX_REFERRAL_FEE_RATE),
+            1_000_000
+        );
+        assert_eq!(
+            get_referral_fee_excluded_amount(0, MAX_REFERRAL_FEE_RATE),
+            0
+        );
+    }
+
+    #[test]
+    fn test_referral_fee_included_amount_bound() {
+        // The swap input and its referral fee never exceed the amount paid by the user
+        for referral_fee_rate in [0, 1, 30, 99, MAX_REFERRAL_FEE_RATE] {
+            for amount in (0..20_000).chain([u64::MAX - 1, u64::MAX]) {
+                let swap_input_amount = get_referral_fee_excluded_amount(amount, referral_fee_rate);
+                let referral_fee = get_referral_fee_amount(swap_input_amount, referral_fee_rate);
+                assert!(swap_input_amount as u128 + referral_fee as u128 <= amount as u128);
+                // At most the rounding of both amounts is left to the user
+                assert!(amount - swap_input_amount - referral_fee <= 2);
+            }
+        }
+    }
+}
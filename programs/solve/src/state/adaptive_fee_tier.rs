@@ -29,11 +29,15 @@ pub struct AdaptiveFeeTier {
     pub max_volatility_accumulator: u32,
     pub tick_group_size: u16,
     pub major_swap_threshold_ticks: u16,
-    // 128 RESERVE
+
+    // authorities nominated by the fee authority, waiting to be accepted by the new keys
+    pub pending_initialize_pool_authority: Pubkey,
+    pub pending_delegated_fee_authority: Pubkey,
+    // 64 RESERVE
 }
 
 impl AdaptiveFeeTier {
-    pub const LEN: usize = 8 + 32 + 2 + 2 + 32 + 32 + 2 + 2 + 2 + 2 + 4 + 4 + 2 + 2 + 128;
+    pub const LEN: usize = 8 + 32 + 2 + 2 + 32 + 32 + 2 + 2 + 2 + 2 + 4 + 4 + 2 + 2 + 32 + 32 + 64;
 
     #[allow(clippy::too_many_arguments)]
     pub fn initialize(
@@ -92,6 +96,40 @@ impl AdaptiveFeeTier {
         self.delegated_fee_authority = delegated_fee_authority;
     }
 
+    // No one can accept the default pubkey, so making the adaptive fee tier permission-less
+    // takes effect immediately. It also cancels a pending nomination.
+    pub fn update_pending_initialize_pool_authority(
+        &mut self,
+        pending_initialize_pool_authority: Pubkey,
+    ) {
+        if pending_initialize_pool_authority == Pubkey::default() {
+            self.initialize_pool_authority = Pubkey::default();
+        }
+        self.pending_initialize_pool_authority = pending_initialize_pool_authority;
+    }
+
+    // No one can accept the default pubkey, so revoking the delegation takes effect immediately.
+    // It also cancels a pending nomination.
+    pub fn update_pending_delegated_fee_authority(
+        &mut self,
+        pending_delegated_fee_authority: Pubkey,
+    ) {
+        if pending_delegated_fee_authority == Pubkey::default() {
+            self.delegated_fee_authority = Pubkey::default();
+        }
+        self.pending_delegated_fee_authority = pending_delegated_fee_authority;
+    }
+
+    pub fn accept_initialize_pool_authority(&mut self) {
+        self.initialize_pool_authority = self.pending_initialize_pool_authority;
+        self.pending_initialize_pool_authority = Pubkey::default();
+    }
+
+    pub fn accept_delegated_fee_authority(&mut self) {
+        self.delegated_fee_authority = self.pending_delegated_fee_authority;
+        self.pending_delegated_fee_authority = Pubkey::default();
+    }
+
     pub fn update_default_base_fee_rate(&mut self, default_base_fee_rate: u16) -> Result<()> {
         if default_base_fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
@@ -148,4 +186,3 @@ impl AdaptiveFeeTier {
         self.initialize_pool_authority != Pubkey::default()
     }
 }
-
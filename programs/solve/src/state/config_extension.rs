@@ -1,20 +1,69 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::state::validate_pause_flags;
+
+pub const MAX_PROTOCOL_FEE_RECIPIENTS: usize = 4;
+
+// Protocol fee share is represented as a basis point.
+// The shares of the recipients must add up to 10_000.
+pub const PROTOCOL_FEE_SHARE_MUL_VALUE: u16 = 10_000;
+
+// Upper bound of the admin timelock delay, so that parameter changes cannot be frozen indefinitely.
+pub const MAX_ADMIN_TIMELOCK_DELAY: u64 = 60 * 60 * 24 * 30; // 30 days
+
 #[account]
 pub struct SolvesConfigExtension {
     pub solves_config: Pubkey,              // 32
     pub config_extension_authority: Pubkey, // 32
     pub token_badge_authority: Pubkey,      // 32
-                                            // 512 RESERVE
+    pub protocol_fee_recipients: [ProtocolFeeRecipient; MAX_PROTOCOL_FEE_RECIPIENTS], // 34 * 4
+
+    // Authorities nominated by the current authorities, waiting to be accepted by the new keys.
+    // SolvesConfig has no reserved space, so its pending authorities are also kept here.
+    pub pending_fee_authority: Pubkey,                    // 32
+    pub pending_collect_protocol_fees_authority: Pubkey,  // 32
+    pub pending_reward_emissions_super_authority: Pubkey, // 32
+    pub pending_config_extension_authority: Pubkey,       // 32
+    pub pending_token_badge_authority: Pubkey,            // 32
+
+    // Minimum delay in seconds before a queued parameter change can be executed.
+    // Zero disables the timelock.
+    pub admin_timelock_delay: u64, // 8
+
+    // authority who can pause the pools in this config
+    pub pause_authority: Pubkey,         // 32
+    pub pending_pause_authority: Pubkey, // 32
+    // pause flags applied to all pools in this config
+    pub pause_flags: u8, // 1
+
+    // receives the rent of closed tick arrays
+    pub tick_array_rent_recipient: Pubkey, // 32
+                                           // 111 RESERVE
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct ProtocolFeeRecipient {
+    // owner of the token accounts receiving the protocol fees
+    pub recipient: Pubkey,
+    pub share: u16,
+}
+
+impl ProtocolFeeRecipient {
+    pub fn is_set(&self) -> bool {
+        self.share > 0
+    }
 }
 
 impl SolvesConfigExtension {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 512;
+    pub const LEN: usize =
+        8 + 32 + 32 + 32 + 34 * MAX_PROTOCOL_FEE_RECIPIENTS + 32 * 5 + 8 + 32 + 32 + 1 + 32 + 111;
 
     pub fn initialize(&mut self, solves_config: Pubkey, default_authority: Pubkey) -> Result<()> {
         self.solves_config = solves_config;
         self.config_extension_authority = default_authority;
         self.token_badge_authority = default_authority;
+        self.pause_authority = default_authority;
         Ok(())
     }
 
@@ -25,4 +74,273 @@ impl SolvesConfigExtension {
     pub fn update_token_badge_authority(&mut self, token_badge_authority: Pubkey) {
         self.token_badge_authority = token_badge_authority;
     }
+
+    pub fn update_pending_fee_authority(&mut self, pending_fee_authority: Pubkey) {
+        self.pending_fee_authority = pending_fee_authority;
+    }
+
+    pub fn update_pending_collect_protocol_fees_authority(
+        &mut self,
+        pending_collect_protocol_fees_authority: Pubkey,
+    ) {
+        self.pending_collect_protocol_fees_authority = pending_collect_protocol_fees_authority;
+    }
+
+    pub fn update_pending_reward_emissions_super_authority(
+        &mut self,
+        pending_reward_emissions_super_authority: Pubkey,
+    ) {
+        self.pending_reward_emissions_super_authority = pending_reward_emissions_super_authority;
+    }
+
+    pub fn update_pending_config_extension_authority(
+        &mut self,
+        pending_config_extension_authority: Pubkey,
+    ) {
+        self.pending_config_extension_authority = pending_config_extension_authority;
+    }
+
+    pub fn update_pending_token_badge_authority(&mut self, pending_token_badge_authority: Pubkey) {
+        self.pending_token_badge_authority = pending_token_badge_authority;
+    }
+
+    pub fn update_admin_timelock_delay(&mut self, admin_timelock_delay: u64) -> Result<()> {
+        if admin_timelock_delay > MAX_ADMIN_TIMELOCK_DELAY {
+            return Err(ErrorCode::AdminTimelockDelayMaxExceeded.into());
+        }
+        self.admin_timelock_delay = admin_timelock_delay;
+
+        Ok(())
+    }
+
+    pub fn is_admin_timelock_enabled(&self) -> bool {
+        self.admin_timelock_delay > 0
+    }
+
+    pub fn update_pending_pause_authority(&mut self, pending_pause_authority: Pubkey) {
+        self.pending_pause_authority = pending_pause_authority;
+    }
+
+    pub fn update_pause_flags(&mut self, pause_flags: u8) -> Result<()> {
+        validate_pause_flags(pause_flags)?;
+        self.pause_flags = pause_flags;
+        Ok(())
+    }
+
+    pub fn update_tick_array_rent_recipient(&mut self, tick_array_rent_recipient: Pubkey) {
+        self.tick_array_rent_recipient = tick_array_rent_recipient;
+    }
+
+    pub fn accept_config_extension_authority(&mut self) {
+        self.config_extension_authority = self.pending_config_extension_authority;
+        self.pending_config_extension_authority = Pubkey::default();
+    }
+
+    pub fn accept_token_badge_authority(&mut self) {
+        self.token_badge_authority = self.pending_token_badge_authority;
+        self.pending_token_badge_authority = Pubkey::default();
+    }
+
+    pub fn accept_pause_authority(&mut self) {
+        self.pause_authority = self.pending_pause_authority;
+        self.pending_pause_authority = Pubkey::default();
+    }
+
+    // Loads the config extension account, which may be uninitialized.
+    // The address of the account must be verified by the caller.
+    pub fn try_load(config_extension_account_info: &AccountInfo) -> Result<Option<Self>> {
+        // uninitialized account (owned by system program and its data size is zero)
+        if config_extension_account_info.owner == &System::id()
+            && config_extension_account_info.data_is_empty()
+        {
+            return Ok(None);
+        }
+
+        if config_extension_account_info.owner != &crate::ID {
+            return Err(
+                Error::from(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+                    .with_pubkeys((*config_extension_account_info.owner, crate::ID)),
+            );
+        }
+
+        let data = config_extension_account_info.try_borrow_data()?;
+        Ok(Some(SolvesConfigExtension::try_deserialize(
+            &mut data.as_ref(),
+        )?))
+    }
+
+    // Writes back a config extension loaded with try_load, the account must be writable.
+    pub fn store(&self, config_extension_account_info: &AccountInfo) -> Result<()> {
+        if !config_extension_account_info.is_writable {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+
+        let mut data = config_extension_account_info.try_borrow_mut_data()?;
+        self.try_serialize(&mut data.as_mut())?;
+        Ok(())
+    }
+
+    // Returns the pause flags of the config extension account, which may be uninitialized.
+    // The address of the account must be verified by the caller.
+    pub fn load_pause_flags(config_extension_account_info: &AccountInfo) -> Result<u8> {
+        Ok(Self::try_load(config_extension_account_info)?
+            .map(|config_extension| config_extension.pause_flags)
+            .unwrap_or_default())
+    }
+
+    // An empty list clears the fee distribution table
+    pub fn update_protocol_fee_recipients(
+        &mut self,
+        protocol_fee_recipients: &[ProtocolFeeRecipient],
+    ) -> Result<()> {
+        if protocol_fee_recipients.len() > MAX_PROTOCOL_FEE_RECIPIENTS {
+            return Err(ErrorCode::InvalidProtocolFeeRecipients.into());
+        }
+
+        let mut total_share: u32 = 0;
+        for (i, protocol_fee_recipient) in protocol_fee_recipients.iter().enumerate() {
+            if !protocol_fee_recipient.is_set()
+                || protocol_fee_recipients[..i]
+                    .iter()
+                    .any(|other| other.recipient == protocol_fee_recipient.recipient)
+            {
+                return Err(ErrorCode::InvalidProtocolFeeRecipients.into());
+            }
+            total_share += protocol_fee_recipient.share as u32;
+        }
+        if !protocol_fee_recipients.is_empty() && total_share != PROTOCOL_FEE_SHARE_MUL_VALUE as u32
+        {
+            return Err(ErrorCode::InvalidProtocolFeeRecipients.into());
+        }
+
+        self.protocol_fee_recipients =
+            [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS];
+        self.protocol_fee_recipients[..protocol_fee_recipients.len()]
+            .copy_from_slice(protocol_fee_recipients);
+        Ok(())
+    }
+
+    pub fn get_protocol_fee_recipients(&self) -> Vec<ProtocolFeeRecipient> {
+        self.protocol_fee_recipients
+            .iter()
+            .filter(|protocol_fee_recipient| protocol_fee_recipient.is_set())
+            .copied()
+            .collect()
+    }
+
+    // Splits the amount by the shares of the recipients.
+    // The last recipient also receives the remainder, so that the whole amount is distributed.
+    pub fn split_protocol_fee(&self, amount: u64) -> Vec<u64> {
+        let protocol_fee_recipients = self.get_protocol_fee_recipients();
+        let mut remaining_amount = amount;
+        protocol_fee_recipients
+            .iter()
+            .enumerate()
+            .map(|(i, protocol_fee_recipient)| {
+                let share_amount = if i == protocol_fee_recipients.len() - 1 {
+                    remaining_amount
+                } else {
+                    // share is less than PROTOCOL_FEE_SHARE_MUL_VALUE, so the result fits in u64
+                    ((amount as u128) * (protocol_fee_recipient.share as u128)
+                        / PROTOCOL_FEE_SHARE_MUL_VALUE as u128) as u64
+                };
+                remaining_amount -= share_amount;
+                share_amount
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod config_extension_tests {
+    use super::*;
+
+    fn config_extension() -> SolvesConfigExtension {
+        SolvesConfigExtension {
+            solves_config: Pubkey::new_unique(),
+            config_extension_authority: Pubkey::default(),
+            token_badge_authority: Pubkey::default(),
+            protocol_fee_recipients: [ProtocolFeeRecipient::default(); MAX_PROTOCOL_FEE_RECIPIENTS],
+            pending_fee_authority: Pubkey::default(),
+            pending_collect_protocol_fees_authority: Pubkey::default(),
+            pending_reward_emissions_super_authority: Pubkey::default(),
+            pending_config_extension_authority: Pubkey::default(),
+            pending_token_badge_authority: Pubkey::default(),
+            admin_timelock_delay: 0,
+            pause_authority: Pubkey::default(),
+            pending_pause_authority: Pubkey::default(),
+            pause_flags: 0,
+            tick_array_rent_recipient: Pubkey::default(),
+        }
+    }
+
+    fn recipient(share: u16) -> ProtocolFeeRecipient {
+        ProtocolFeeRecipient {
+            recipient: Pubkey::new_unique(),
+            share,
+        }
+    }
+
+    #[test]
+    fn test_update_protocol_fee_recipients() {
+        let mut config_extension = config_extension();
+        let recipients = [recipient(2_500), recipient(7_500)];
+        config_extension
+            .update_protocol_fee_recipients(&recipients)
+            .unwrap();
+        assert_eq!(config_extension.get_protocol_fee_recipients(), recipients);
+
+        // An empty list clears the table
+        config_extension
+            .update_protocol_fee_recipients(&[])
+            .unwrap();
+        assert!(config_extension.get_protocol_fee_recipients().is_empty());
+    }
+
+    #[test]
+    fn test_update_protocol_fee_recipients_invalid() {
+        let mut config_extension = config_extension();
+        let duplicated = recipient(5_000);
+        for recipients in [
+            // shares do not add up to 10000
+            vec![recipient(2_500), recipient(7_000)],
+            // a recipient without share
+            vec![recipient(10_000), recipient(0)],
+            // duplicated recipient
+            vec![duplicated, duplicated],
+            // too many recipients
+            vec![recipient(2_000); MAX_PROTOCOL_FEE_RECIPIENTS + 1],
+        ] {
+            assert_eq!(
+                config_extension
+                    .update_protocol_fee_recipients(&recipients)
+                    .err()
+                    .unwrap(),
+                ErrorCode::InvalidProtocolFeeRecipients.into()
+            );
+        }
+        assert!(config_extension.get_protocol_fee_recipients().is_empty());
+    }
+
+    #[test]
+    fn test_split_protocol_fee() {
+        let mut config_extension = config_extension();
+        config_extension
+            .update_protocol_fee_recipients(&[recipient(3_333), recipient(3_333), recipient(3_334)])
+            .unwrap();
+
+        // The last recipient receives the remainder
+        assert_eq!(config_extension.split_protocol_fee(10), vec![3, 3, 4]);
+        assert_eq!(
+            config_extension.split_protocol_fee(1_000_000),
+            vec![333_300, 333_300, 333_400]
+        );
+        assert_eq!(config_extension.split_protocol_fee(0), vec![0, 0, 0]);
+
+        let amounts = config_extension.split_protocol_fee(u64::MAX);
+        assert_eq!(
+            amounts.iter().map(|&amount| amount as u128).sum::<u128>(),
+            u64::MAX as u128
+        );
+    }
 }
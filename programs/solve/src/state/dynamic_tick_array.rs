@@ -77,6 +77,7 @@ pub struct DynamicTickArray {
     pub start_tick_index: i32, // 4 bytes
     pub solve: Pubkey,         // 32 bytes
     // 0: uninitialized, 1: initialized
+    // The highest bit is set for tick arrays migrated from FixedTickArray
     pub tick_bitmap: u128, // 16 bytes
     pub ticks: [DynamicTick; TICK_ARRAY_SIZE_USIZE],
 }
@@ -137,6 +138,10 @@ impl DynamicTickArrayLoader {
     const TICK_BITMAP_OFFSET: usize = Self::POOL_OFFSET + 32;
     const TICK_DATA_OFFSET: usize = Self::TICK_BITMAP_OFFSET + 16;
 
+    // The bits of the tick bitmap above TICK_ARRAY_SIZE are not used by the ticks.
+    // The highest one marks the tick arrays migrated from FixedTickArray.
+    const MIGRATED_FLAG: u128 = 1 << 127;
+
     pub fn initialize(&mut self, solve: &Account<Solve>, start_tick_index: i32) -> Result<()> {
         if !Tick::check_is_valid_start_tick(start_tick_index, solve.tick_spacing) {
             return Err(ErrorCode::InvalidStartTick.into());
@@ -149,6 +154,36 @@ impl DynamicTickArrayLoader {
         Ok(())
     }
 
+    // Initialize with the ticks of a FixedTickArray (see migrate_tick_array).
+    // The tick data must be zeroed and large enough to hold all initialized ticks.
+    // The tick array is marked as migrated, as it does not hold the rent for the ticks
+    // of the positions opened before the migration.
+    pub fn initialize_with_ticks(
+        &mut self,
+        solve: &Account<Solve>,
+        start_tick_index: i32,
+        ticks: &[Tick],
+    ) -> Result<()> {
+        self.initialize(solve, start_tick_index)?;
+
+        let mut tick_bitmap: u128 = Self::MIGRATED_FLAG;
+        let mut tick_data = self.tick_data_mut();
+        for (tick_offset, tick) in ticks.iter().take(TICK_ARRAY_SIZE_USIZE).enumerate() {
+            if tick.initialized {
+                tick_bitmap |= 1 << tick_offset;
+            }
+            DynamicTick::from(&TickUpdate::from(*tick)).serialize(&mut tick_data)?;
+        }
+
+        self.0[Self::TICK_BITMAP_OFFSET..Self::TICK_BITMAP_OFFSET + 16]
+            .copy_from_slice(&tick_bitmap.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.tick_bitmap() & Self::MIGRATED_FLAG != 0
+    }
+
     fn tick_data(&self) -> &[u8] {
         &self.0[Self::TICK_DATA_OFFSET..]
     }
@@ -275,6 +310,10 @@ impl TickArrayType for DynamicTickArrayLoader {
 
         Ok(())
     }
+
+    fn has_initialized_ticks(&self) -> bool {
+        self.tick_bitmap() & !Self::MIGRATED_FLAG != 0
+    }
 }
 
 impl DynamicTickArrayLoader {
@@ -314,3 +353,157 @@ impl DynamicTickArrayLoader {
     }
 }
 
+#[cfg(test)]
+mod dynamic_tick_array_tests {
+    use super::*;
+
+    const TICK_SPACING: u16 = 64;
+    const START_TICK_INDEX: i32 = -(TICK_ARRAY_SIZE * TICK_SPACING as i32);
+    const INITIALIZED_TICK_OFFSETS: [usize; 3] = [0, 10, TICK_ARRAY_SIZE_USIZE - 1];
+
+    fn solve_data() -> Vec<u8> {
+        let solve = Solve {
+            tick_spacing: TICK_SPACING,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Solve::LEN];
+        solve.try_serialize(&mut data.as_mut_slice()).unwrap();
+        data
+    }
+
+    fn ticks() -> Vec<Tick> {
+        let mut ticks = vec![Tick::default(); TICK_ARRAY_SIZE_USIZE];
+        for (i, tick_offset) in INITIALIZED_TICK_OFFSETS.into_iter().enumerate() {
+            ticks[tick_offset] = Tick {
+                initialized: true,
+                liquidity_net: -(i as i128 + 1) * 1_000,
+                liquidity_gross: (i as u128 + 1) * 1_000,
+                fee_growth_outside_a: i as u128 + 10,
+                fee_growth_outside_b: i as u128 + 20,
+                reward_growths_outside: [i as u128 + 30, i as u128 + 40, i as u128 + 50],
+            };
+        }
+        ticks
+    }
+
+    fn tick_index(tick_offset: usize) -> i32 {
+        START_TICK_INDEX + tick_offset as i32 * TICK_SPACING as i32
+    }
+
+    #[test]
+    fn test_initialize_with_ticks() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let ticks = ticks();
+        let mut tick_array_data = vec![0u8; DynamicTickArray::MAX_LEN];
+        let tick_array = DynamicTickArrayLoader::load_mut(&mut tick_array_data);
+        tick_array
+            .initialize_with_ticks(&solve, START_TICK_INDEX, &ticks)
+            .unwrap();
+
+        assert_eq!(tick_array.start_tick_index(), START_TICK_INDEX);
+        assert_eq!(tick_array.solve(), key);
+        assert!(tick_array.is_migrated());
+        assert!(tick_array.has_initialized_ticks());
+        for (tick_offset, tick) in ticks.iter().enumerate() {
+            assert_eq!(
+                tick_array
+                    .get_tick(tick_index(tick_offset), TICK_SPACING)
+                    .unwrap(),
+                *tick
+            );
+        }
+        assert_eq!(
+            tick_array
+                .get_next_init_tick_index(tick_index(9), TICK_SPACING, false)
+                .unwrap(),
+            Some(tick_index(10))
+        );
+
+        // The migrated flag is not a tick
+        for tick_offset in INITIALIZED_TICK_OFFSETS {
+            tick_array
+                .update_tick(
+                    tick_index(tick_offset),
+                    TICK_SPACING,
+                    &TickUpdate::default(),
+                )
+                .unwrap();
+        }
+        assert!(tick_array.is_migrated());
+        assert!(!tick_array.has_initialized_ticks());
+        assert_eq!(
+            tick_array
+                .get_next_init_tick_index(tick_index(TICK_ARRAY_SIZE_USIZE - 1), TICK_SPACING, true)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_initialize_with_ticks_invalid_start_tick() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array_data = vec![0u8; DynamicTickArray::MAX_LEN];
+        let tick_array = DynamicTickArrayLoader::load_mut(&mut tick_array_data);
+        assert_eq!(
+            tick_array
+                .initialize_with_ticks(&solve, START_TICK_INDEX + 1, &ticks())
+                .unwrap_err(),
+            ErrorCode::InvalidStartTick.into()
+        );
+    }
+
+    #[test]
+    fn test_initialize_is_not_migrated() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = solve_data();
+        let owner = crate::ID;
+        let solve_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let solve: Account<Solve> = Account::try_from(&solve_info).unwrap();
+
+        let mut tick_array_data = vec![0u8; DynamicTickArray::MAX_LEN];
+        let tick_array = DynamicTickArrayLoader::load_mut(&mut tick_array_data);
+        tick_array.initialize(&solve, START_TICK_INDEX).unwrap();
+        assert!(!tick_array.is_migrated());
+        assert!(!tick_array.has_initialized_ticks());
+    }
+}
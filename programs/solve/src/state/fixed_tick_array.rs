@@ -51,6 +51,11 @@ impl TickArray {
         self.start_tick_index = start_tick_index;
         Ok(())
     }
+
+    /// Copy all ticks of this array to the heap. The array is too large to be copied onto the stack.
+    pub fn ticks_to_vec(&self) -> Vec<Tick> {
+        self.ticks.to_vec()
+    }
 }
 
 impl TickArrayType for TickArray {
@@ -167,5 +172,10 @@ impl TickArrayType for TickArray {
         self.ticks.get_mut(offset as usize).unwrap().update(update);
         Ok(())
     }
+
+    fn has_initialized_ticks(&self) -> bool {
+        // index one by one to avoid copying the whole array onto the stack
+        (0..TICK_ARRAY_SIZE_USIZE).any(|i| self.ticks[i].initialized)
+    }
 }
 
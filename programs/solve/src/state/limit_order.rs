@@ -0,0 +1,369 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD;
+use crate::state::{
+    Observation, Oracle, PositionRewardInfo, NUM_REWARDS, OBSERVATIONS_OFFSET, TICK_ARRAY_SIZE,
+};
+
+use super::Tick;
+
+// Upper bound of the limit orders per pool.
+// Every swap scans the limit orders of the pool, so the number must be kept small.
+pub const MAX_LIMIT_ORDERS: u16 = 64;
+
+// Lamports deposited into a LimitOrder account on top of its rent, refunded when the order is closed.
+// Makes it costly to keep the limit order slots of a pool filled with dust orders.
+pub const LIMIT_ORDER_DEPOSIT: u64 = 100_000_000; // 0.1 SOL
+
+/// A single tick spacing range order.
+///
+/// An order of `a_to_b` deposits token A into `[tick_lower_index, tick_lower_index + tick_spacing)`
+/// while the price is below the range, and it is filled (all token B) once the price crosses the upper tick.
+/// An order of `!a_to_b` deposits token B while the price is above the range,
+/// and it is filled (all token A) once the price crosses the lower tick.
+#[account]
+#[derive(Default)]
+pub struct LimitOrder {
+    pub solve: Pubkey,         // 32
+    pub owner: Pubkey,         // 32
+    pub tick_lower_index: i32, // 4
+    pub a_to_b: bool,          // 1
+    pub liquidity: u128,       // 16
+}
+
+impl LimitOrder {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 1 + 16;
+
+    pub fn open_limit_order(
+        &mut self,
+        solve: Pubkey,
+        owner: Pubkey,
+        tick_lower_index: i32,
+        a_to_b: bool,
+        liquidity: u128,
+    ) {
+        self.solve = solve;
+        self.owner = owner;
+        self.tick_lower_index = tick_lower_index;
+        self.a_to_b = a_to_b;
+        self.liquidity = liquidity;
+    }
+}
+
+/// Checks that the range `[tick_lower_index, tick_lower_index + tick_spacing)` can be used for a limit order.
+/// Both ticks must be stored in the same tick array so that a swap can fill the order with the tick arrays it already holds.
+pub fn validate_limit_order_tick_index(tick_lower_index: i32, tick_spacing: u16) -> Result<()> {
+    // On tick spacing >= 2^15, should only be able to open full range positions
+    if tick_spacing >= FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD {
+        return Err(ErrorCode::FullRangeOnlyPool.into());
+    }
+
+    let tick_upper_index = tick_lower_index + tick_spacing as i32;
+    if !Tick::check_is_usable_tick(tick_lower_index, tick_spacing)
+        || !Tick::check_is_usable_tick(tick_upper_index, tick_spacing)
+    {
+        return Err(ErrorCode::InvalidLimitOrderTickIndex.into());
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    if tick_lower_index.div_euclid(ticks_in_array) != tick_upper_index.div_euclid(ticks_in_array) {
+        return Err(ErrorCode::InvalidLimitOrderTickIndex.into());
+    }
+
+    Ok(())
+}
+
+// Limit orders of a pool are stored right after the observation slots in the Oracle account data,
+// so that every swap (which always receives the Oracle account) can fill them.
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct LimitOrderEntry {
+    // LimitOrder account of this entry
+    pub limit_order: Pubkey,
+    pub tick_lower_index: i32,
+    // 1 if the order sells token A for token B
+    pub a_to_b: u8,
+    // 1 once a swap has crossed the far tick of the range
+    pub filled: u8,
+    pub liquidity: u128,
+    // Q64.64
+    pub fee_growth_checkpoint_a: u128,
+    // Q64.64
+    pub fee_growth_checkpoint_b: u128,
+    // Fees earned until the order was filled
+    pub fee_owed_a: u64,
+    pub fee_owed_b: u64,
+    // Q64.64
+    pub reward_growth_checkpoints: [u128; NUM_REWARDS],
+    // Rewards emitted to the liquidity of the order until it was filled.
+    // Limit orders do not earn rewards, these are released to the reward authority when the order is closed.
+    pub reward_owed: [u64; NUM_REWARDS],
+}
+
+// Safety: LimitOrderEntry is packed (no padding) and consists of integers only, so any bit pattern is valid.
+unsafe impl bytemuck::Pod for LimitOrderEntry {}
+unsafe impl bytemuck::Zeroable for LimitOrderEntry {}
+
+impl LimitOrderEntry {
+    pub const LEN: usize =
+        32 + 4 + 1 + 1 + 16 + 16 + 16 + 8 + 8 + 16 * NUM_REWARDS + 8 * NUM_REWARDS;
+
+    pub fn is_a_to_b(&self) -> bool {
+        self.a_to_b != 0
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.filled != 0
+    }
+
+    pub fn tick_upper_index(&self, tick_spacing: u16) -> i32 {
+        self.tick_lower_index + tick_spacing as i32
+    }
+
+    /// Returns the reward checkpoints of the order in the form used by positions.
+    pub fn reward_infos(&self) -> [PositionRewardInfo; NUM_REWARDS] {
+        let reward_growth_checkpoints = self.reward_growth_checkpoints;
+        let reward_owed = self.reward_owed;
+        std::array::from_fn(|i| PositionRewardInfo {
+            growth_inside_checkpoint: reward_growth_checkpoints[i],
+            amount_owed: reward_owed[i],
+        })
+    }
+
+    pub fn update_reward_infos(&mut self, reward_infos: &[PositionRewardInfo; NUM_REWARDS]) {
+        self.reward_growth_checkpoints = reward_infos.map(|info| info.growth_inside_checkpoint);
+        self.reward_owed = reward_infos.map(|info| info.amount_owed);
+    }
+
+    /// Returns true if the price has crossed the far tick of an order that is not filled yet.
+    pub fn is_fillable(&self, tick_current_index: i32, tick_spacing: u16) -> bool {
+        if self.is_filled() {
+            return false;
+        }
+
+        if self.is_a_to_b() {
+            tick_current_index >= self.tick_upper_index(tick_spacing)
+        } else {
+            tick_current_index < self.tick_lower_index
+        }
+    }
+}
+
+/// Splits the data of an Oracle account into the Oracle struct and its limit order slots in use.
+/// The caller is responsible for checking the discriminator.
+pub fn load_oracle_with_limit_orders(data: &[u8]) -> Result<(&Oracle, &[LimitOrderEntry])> {
+    if data.len() < OBSERVATIONS_OFFSET {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let (oracle_data, trailing_data) = data.split_at(OBSERVATIONS_OFFSET);
+    let oracle: &Oracle = bytemuck::from_bytes(&oracle_data[8..]);
+
+    let offset = oracle.limit_orders_offset() - OBSERVATIONS_OFFSET;
+    let end = offset + oracle.limit_order_count as usize * LimitOrderEntry::LEN;
+    if trailing_data.len() < end {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let limit_orders: &[LimitOrderEntry] = bytemuck::cast_slice(&trailing_data[offset..end]);
+    Ok((oracle, limit_orders))
+}
+
+/// Mutable version of `load_oracle_with_limit_orders`.
+/// All allocated slots are returned, only the first `limit_order_count` slots are in use.
+pub fn load_oracle_with_limit_orders_mut(
+    data: &mut [u8],
+) -> Result<(&mut Oracle, &mut [LimitOrderEntry])> {
+    if data.len() < OBSERVATIONS_OFFSET {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let (oracle_data, trailing_data) = data.split_at_mut(OBSERVATIONS_OFFSET);
+    let oracle: &mut Oracle = bytemuck::from_bytes_mut(&mut oracle_data[8..]);
+
+    let offset = oracle.limit_orders_offset() - OBSERVATIONS_OFFSET;
+    let end = offset + oracle.limit_order_capacity as usize * LimitOrderEntry::LEN;
+    if trailing_data.len() < end {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let limit_orders: &mut [LimitOrderEntry] =
+        bytemuck::cast_slice_mut(&mut trailing_data[offset..end]);
+    Ok((oracle, limit_orders))
+}
+
+impl Oracle {
+    pub fn limit_orders_offset(&self) -> usize {
+        OBSERVATIONS_OFFSET + self.observation_cardinality_next as usize * Observation::LEN
+    }
+
+    pub fn space(observation_cardinality_next: u16, limit_order_capacity: u16) -> usize {
+        Oracle::observations_space(observation_cardinality_next)
+            + limit_order_capacity as usize * LimitOrderEntry::LEN
+    }
+
+    pub fn find_limit_order(
+        &self,
+        limit_orders: &[LimitOrderEntry],
+        limit_order: Pubkey,
+    ) -> Result<usize> {
+        limit_orders[..self.limit_order_count as usize]
+            .iter()
+            .position(|entry| entry.limit_order == limit_order)
+            .ok_or(ErrorCode::LimitOrderNotFound.into())
+    }
+
+    /// Appends an entry. The caller must have allocated a free slot.
+    pub fn add_limit_order(
+        &mut self,
+        limit_orders: &mut [LimitOrderEntry],
+        entry: LimitOrderEntry,
+    ) -> Result<()> {
+        if self.limit_order_count >= self.limit_order_capacity {
+            return Err(ErrorCode::LimitOrderCapacityExceeded.into());
+        }
+
+        limit_orders[self.limit_order_count as usize] = entry;
+        self.limit_order_count += 1;
+        Ok(())
+    }
+
+    /// Removes an entry by moving the last entry in use into its slot.
+    pub fn remove_limit_order(&mut self, limit_orders: &mut [LimitOrderEntry], index: usize) {
+        let last = self.limit_order_count as usize - 1;
+        limit_orders[index] = limit_orders[last];
+        limit_orders[last] = LimitOrderEntry::default();
+        self.limit_order_count -= 1;
+    }
+}
+
+#[cfg(test)]
+mod limit_order_tests {
+    use super::*;
+
+    const TICK_SPACING: u16 = 64;
+
+    fn entry(limit_order: Pubkey, tick_lower_index: i32, a_to_b: bool) -> LimitOrderEntry {
+        LimitOrderEntry {
+            limit_order,
+            tick_lower_index,
+            a_to_b: a_to_b as u8,
+            liquidity: 1_000,
+            ..Default::default()
+        }
+    }
+
+    fn oracle_data(observation_cardinality_next: u16, limit_order_capacity: u16) -> Vec<u8> {
+        let mut data = vec![0u8; Oracle::space(observation_cardinality_next, limit_order_capacity)];
+        let oracle: &mut Oracle = bytemuck::from_bytes_mut(&mut data[8..OBSERVATIONS_OFFSET]);
+        oracle.observation_cardinality_next = observation_cardinality_next;
+        oracle.limit_order_capacity = limit_order_capacity;
+        data
+    }
+
+    #[test]
+    fn test_validate_limit_order_tick_index() {
+        assert!(validate_limit_order_tick_index(0, TICK_SPACING).is_ok());
+        assert!(validate_limit_order_tick_index(-2 * TICK_SPACING as i32, TICK_SPACING).is_ok());
+        // not a multiple of the tick spacing
+        assert_eq!(
+            validate_limit_order_tick_index(1, TICK_SPACING).unwrap_err(),
+            ErrorCode::InvalidLimitOrderTickIndex.into()
+        );
+        // the upper tick is the first tick of the next tick array
+        let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+        assert_eq!(
+            validate_limit_order_tick_index(ticks_in_array - TICK_SPACING as i32, TICK_SPACING)
+                .unwrap_err(),
+            ErrorCode::InvalidLimitOrderTickIndex.into()
+        );
+        assert_eq!(
+            validate_limit_order_tick_index(0, FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD).unwrap_err(),
+            ErrorCode::FullRangeOnlyPool.into()
+        );
+    }
+
+    #[test]
+    fn test_is_fillable() {
+        let tick_upper_index = TICK_SPACING as i32;
+
+        // a_to_b is filled once the price reaches the upper tick
+        let mut a_to_b = entry(Pubkey::new_unique(), 0, true);
+        assert!(!a_to_b.is_fillable(tick_upper_index - 1, TICK_SPACING));
+        assert!(a_to_b.is_fillable(tick_upper_index, TICK_SPACING));
+        a_to_b.filled = 1;
+        assert!(!a_to_b.is_fillable(tick_upper_index, TICK_SPACING));
+
+        // b_to_a is filled once the price moves below the lower tick
+        let b_to_a = entry(Pubkey::new_unique(), 0, false);
+        assert!(!b_to_a.is_fillable(0, TICK_SPACING));
+        assert!(b_to_a.is_fillable(-1, TICK_SPACING));
+    }
+
+    #[test]
+    fn test_reward_infos() {
+        let mut entry = entry(Pubkey::new_unique(), 0, true);
+        let reward_infos = std::array::from_fn(|i| PositionRewardInfo {
+            growth_inside_checkpoint: (i as u128 + 1) << 64,
+            amount_owed: i as u64 + 10,
+        });
+        entry.update_reward_infos(&reward_infos);
+        assert_eq!(entry.reward_infos(), reward_infos);
+    }
+
+    #[test]
+    fn test_add_and_remove_limit_orders() {
+        let mut data = oracle_data(2, 2);
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        {
+            let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data).unwrap();
+            oracle
+                .add_limit_order(limit_orders, entry(first, 0, true))
+                .unwrap();
+            oracle
+                .add_limit_order(limit_orders, entry(second, 64, false))
+                .unwrap();
+            assert_eq!(
+                oracle
+                    .add_limit_order(limit_orders, entry(Pubkey::new_unique(), 0, true))
+                    .unwrap_err(),
+                ErrorCode::LimitOrderCapacityExceeded.into()
+            );
+        }
+
+        // The limit orders are stored after the observation slots
+        {
+            let (oracle, limit_orders) = load_oracle_with_limit_orders(&data).unwrap();
+            assert_eq!(limit_orders.len(), 2);
+            assert_eq!(oracle.find_limit_order(limit_orders, second).unwrap(), 1);
+            let offset = oracle.limit_orders_offset();
+            assert_eq!(
+                bytemuck::from_bytes::<LimitOrderEntry>(
+                    &data[offset..offset + LimitOrderEntry::LEN]
+                )
+                .limit_order,
+                first
+            );
+        }
+
+        // Removing an entry moves the last entry into its slot
+        {
+            let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data).unwrap();
+            let index = oracle.find_limit_order(limit_orders, first).unwrap();
+            oracle.remove_limit_order(limit_orders, index);
+            assert_eq!({ oracle.limit_order_count }, 1);
+            assert_eq!({ limit_orders[0].limit_order }, second);
+            assert_eq!(limit_orders[1], LimitOrderEntry::default());
+        }
+
+        let (oracle, limit_orders) = load_oracle_with_limit_orders(&data).unwrap();
+        assert_eq!(limit_orders.len(), 1);
+        assert_eq!(
+            oracle.find_limit_order(limit_orders, first).unwrap_err(),
+            ErrorCode::LimitOrderNotFound.into()
+        );
+    }
+}
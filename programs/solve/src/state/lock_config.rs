@@ -1,19 +1,33 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::math::{div_round_up_if_u256, mul_u256, U256Muldiv};
+
 #[account]
 pub struct LockConfig {
-    pub position: Pubkey,       // 32
-    pub position_owner: Pubkey, // 32
-    pub solve: Pubkey,          // 32
-    pub locked_timestamp: u64,  // 8
-    pub lock_type: LockTypeLabel, // 1
-                                // 128 RESERVE
+    pub position: Pubkey,             // 32
+    pub position_owner: Pubkey,       // 32
+    pub solve: Pubkey,                // 32
+    pub locked_timestamp: u64,        // 8
+    pub lock_type: LockTypeLabel,     // 1
+    pub unlock_timestamp: u64,        // 8 (Timed, Vesting)
+    pub vesting_start_timestamp: u64, // 8 (Vesting only)
+    pub locked_liquidity: u128,       // 16 (Vesting only)
+                                      // 96 RESERVE
 }
 
 #[non_exhaustive]
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum LockType {
     Permanent,
+    Timed {
+        unlock_timestamp: u64,
+    },
+    // liquidity is released linearly from start_timestamp to end_timestamp
+    Vesting {
+        start_timestamp: u64,
+        end_timestamp: u64,
+    },
 }
 
 // To avoid storing an enum that may be extended in the future to the account, separate the variant label and value. The value is added flatly to the account.
@@ -21,10 +35,12 @@ pub enum LockType {
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum LockTypeLabel {
     Permanent,
+    Timed,
+    Vesting,
 }
 
 impl LockConfig {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 128;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + 8 + 8 + 16 + 96;
 
     pub fn initialize(
         &mut self,
@@ -32,6 +48,7 @@ impl LockConfig {
         position_owner: Pubkey,
         solve: Pubkey,
         locked_timestamp: u64,
+        locked_liquidity: u128,
         lock_type: LockType,
     ) -> Result<()> {
         self.position = position;
@@ -40,12 +57,191 @@ impl LockConfig {
         self.locked_timestamp = locked_timestamp;
         match lock_type {
             LockType::Permanent => self.lock_type = LockTypeLabel::Permanent,
+            LockType::Timed { unlock_timestamp } => {
+                if unlock_timestamp <= locked_timestamp {
+                    return Err(ErrorCode::InvalidUnlockTimestamp.into());
+                }
+                self.lock_type = LockTypeLabel::Timed;
+                self.unlock_timestamp = unlock_timestamp;
+            }
+            LockType::Vesting {
+                start_timestamp,
+                end_timestamp,
+            } => {
+                if end_timestamp <= locked_timestamp || end_timestamp <= start_timestamp {
+                    return Err(ErrorCode::InvalidUnlockTimestamp.into());
+                }
+                self.lock_type = LockTypeLabel::Vesting;
+                self.unlock_timestamp = end_timestamp;
+                self.vesting_start_timestamp = start_timestamp;
+                self.locked_liquidity = locked_liquidity;
+            }
         }
         Ok(())
     }
 
+    pub fn is_unlockable(&self, timestamp: u64) -> bool {
+        match self.lock_type {
+            LockTypeLabel::Permanent => false,
+            LockTypeLabel::Timed | LockTypeLabel::Vesting => timestamp >= self.unlock_timestamp,
+        }
+    }
+
+    /// Returns the liquidity that must remain in the position at the given timestamp.
+    /// None if the lock does not release liquidity over time.
+    pub fn get_unvested_liquidity(&self, timestamp: u64) -> Result<Option<u128>> {
+        if self.lock_type != LockTypeLabel::Vesting {
+            return Ok(None);
+        }
+
+        if timestamp <= self.vesting_start_timestamp {
+            return Ok(Some(self.locked_liquidity));
+        }
+        if timestamp >= self.unlock_timestamp {
+            return Ok(Some(0));
+        }
+
+        // round up to keep the unvested liquidity on the safe side
+        let remaining_duration = self.unlock_timestamp - timestamp;
+        let vesting_duration = self.unlock_timestamp - self.vesting_start_timestamp;
+        let unvested_liquidity = div_round_up_if_u256(
+            mul_u256(self.locked_liquidity, remaining_duration as u128),
+            U256Muldiv::new(0, vesting_duration as u128),
+            true,
+        )?;
+        Ok(Some(unvested_liquidity))
+    }
+
     pub fn update_position_owner(&mut self, position_owner: Pubkey) {
         self.position_owner = position_owner;
     }
 }
 
+#[cfg(test)]
+mod lock_config_tests {
+    use super::*;
+
+    const LOCKED_TIMESTAMP: u64 = 1_000;
+
+    fn new_lock_config(lock_type: LockType) -> Result<LockConfig> {
+        let mut lock_config = LockConfig {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            solve: Pubkey::default(),
+            locked_timestamp: 0,
+            lock_type: LockTypeLabel::Permanent,
+            unlock_timestamp: 0,
+            vesting_start_timestamp: 0,
+            locked_liquidity: 0,
+        };
+        lock_config.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            LOCKED_TIMESTAMP,
+            1_000,
+            lock_type,
+        )?;
+        Ok(lock_config)
+    }
+
+    #[test]
+    fn test_permanent_lock() {
+        let lock_config = new_lock_config(LockType::Permanent).unwrap();
+        assert_eq!(lock_config.lock_type, LockTypeLabel::Permanent);
+        assert!(!lock_config.is_unlockable(LOCKED_TIMESTAMP));
+        assert!(!lock_config.is_unlockable(u64::MAX));
+    }
+
+    #[test]
+    fn test_timed_lock() {
+        let lock_config = new_lock_config(LockType::Timed {
+            unlock_timestamp: LOCKED_TIMESTAMP + 100,
+        })
+        .unwrap();
+        assert_eq!(lock_config.lock_type, LockTypeLabel::Timed);
+        assert_eq!(lock_config.unlock_timestamp, LOCKED_TIMESTAMP + 100);
+        assert!(!lock_config.is_unlockable(LOCKED_TIMESTAMP));
+        assert!(!lock_config.is_unlockable(LOCKED_TIMESTAMP + 99));
+        assert!(lock_config.is_unlockable(LOCKED_TIMESTAMP + 100));
+        assert!(lock_config.is_unlockable(u64::MAX));
+    }
+
+    #[test]
+    fn test_timed_lock_invalid_unlock_timestamp() {
+        for unlock_timestamp in [0, LOCKED_TIMESTAMP - 1, LOCKED_TIMESTAMP] {
+            assert_eq!(
+                new_lock_config(LockType::Timed { unlock_timestamp })
+                    .err()
+                    .unwrap(),
+                ErrorCode::InvalidUnlockTimestamp.into()
+            );
+        }
+    }
+    #[test]
+    fn test_vesting_lock_invalid_timestamps() {
+        for (start_timestamp, end_timestamp) in [
+            (0, LOCKED_TIMESTAMP),
+            (LOCKED_TIMESTAMP + 100, LOCKED_TIMESTAMP + 100),
+            (LOCKED_TIMESTAMP + 200, LOCKED_TIMESTAMP + 100),
+        ] {
+            assert_eq!(
+                new_lock_config(LockType::Vesting {
+                    start_timestamp,
+                    end_timestamp,
+                })
+                .err()
+                .unwrap(),
+                ErrorCode::InvalidUnlockTimestamp.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_unvested_liquidity() {
+        // The locked liquidity is 1_000, released from LOCKED_TIMESTAMP + 100 to LOCKED_TIMESTAMP + 500
+        let lock_config = new_lock_config(LockType::Vesting {
+            start_timestamp: LOCKED_TIMESTAMP + 100,
+            end_timestamp: LOCKED_TIMESTAMP + 500,
+        })
+        .unwrap();
+        for (timestamp, unvested_liquidity) in [
+            // before the start
+            (LOCKED_TIMESTAMP, 1_000),
+            (LOCKED_TIMESTAMP + 100, 1_000),
+            // mid-vest, rounded up
+            (LOCKED_TIMESTAMP + 101, 998),
+            (LOCKED_TIMESTAMP + 300, 500),
+            (LOCKED_TIMESTAMP + 301, 498),
+            (LOCKED_TIMESTAMP + 499, 3),
+            // after the end
+            (LOCKED_TIMESTAMP + 500, 0),
+            (u64::MAX, 0),
+        ] {
+            assert_eq!(
+                lock_config.get_unvested_liquidity(timestamp).unwrap(),
+                Some(unvested_liquidity)
+            );
+        }
+        assert!(!lock_config.is_unlockable(LOCKED_TIMESTAMP + 499));
+        assert!(lock_config.is_unlockable(LOCKED_TIMESTAMP + 500));
+    }
+
+    #[test]
+    fn test_get_unvested_liquidity_without_vesting() {
+        for lock_type in [
+            LockType::Permanent,
+            LockType::Timed {
+                unlock_timestamp: LOCKED_TIMESTAMP + 100,
+            },
+        ] {
+            let lock_config = new_lock_config(lock_type).unwrap();
+            assert_eq!(
+                lock_config
+                    .get_unvested_liquidity(LOCKED_TIMESTAMP)
+                    .unwrap(),
+                None
+            );
+        }
+    }
+}
@@ -4,11 +4,18 @@ pub mod config_extension;
 pub mod dynamic_tick_array;
 pub mod fee_tier;
 pub mod fixed_tick_array;
+pub mod limit_order;
 pub mod lock_config;
 pub mod oracle;
+pub mod oracle_observation;
+pub mod pause;
 pub mod position;
 pub mod position_bundle;
+pub mod position_keeper;
+pub mod queued_change;
+pub mod reward_extension;
 pub mod solve;
+pub mod solve_tail;
 pub mod tick;
 pub mod tick_array;
 pub mod token_badge;
@@ -21,10 +28,17 @@ pub use config_extension::*;
 pub use dynamic_tick_array::*;
 pub use fee_tier::*;
 pub use fixed_tick_array::*;
+pub use limit_order::*;
 pub use lock_config::*;
 pub use oracle::*;
+pub use oracle_observation::*;
+pub use pause::*;
 pub use position::*;
 pub use position_bundle::*;
+pub use position_keeper::*;
+pub use queued_change::*;
+pub use reward_extension::*;
+pub use solve_tail::*;
 pub use tick::*;
 pub use tick_array::*;
 pub use token_badge::*;
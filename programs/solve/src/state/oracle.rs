@@ -4,7 +4,10 @@ use crate::state::Solve;
 use anchor_lang::prelude::*;
 use std::cell::{Ref, RefMut};
 
-use super::TICK_ARRAY_SIZE;
+use super::{
+    load_oracle_with_limit_orders, load_oracle_with_limit_orders_mut,
+    load_oracle_with_observations_mut, LimitOrderEntry, TICK_ARRAY_SIZE,
+};
 
 pub const MAX_TRADE_ENABLE_TIMESTAMP_DELTA: u64 = 60 * 60 * 72; // 72 hours
 
@@ -258,8 +261,18 @@ pub struct Oracle {
     pub trade_enable_timestamp: u64,
     pub adaptive_fee_constants: AdaptiveFeeConstants,
     pub adaptive_fee_variables: AdaptiveFeeVariables,
+    // Index of the most recently written observation
+    pub observation_index: u16,
+    // Number of observations in use (0 means observations are disabled)
+    pub observation_cardinality: u16,
+    // Number of observation slots allocated after this struct
+    pub observation_cardinality_next: u16,
+    // Number of limit orders in use (open or filled but not yet closed)
+    pub limit_order_count: u16,
+    // Number of limit order slots allocated after the observation slots
+    pub limit_order_capacity: u16,
     // Reserved for future use
-    pub reserved: [u8; 128],
+    pub reserved: [u8; 118],
 }
 
 impl Default for Oracle {
@@ -269,13 +282,28 @@ impl Default for Oracle {
             trade_enable_timestamp: 0,
             adaptive_fee_constants: AdaptiveFeeConstants::default(),
             adaptive_fee_variables: AdaptiveFeeVariables::default(),
-            reserved: [0u8; 128],
+            observation_index: 0,
+            observation_cardinality: 0,
+            observation_cardinality_next: 0,
+            limit_order_count: 0,
+            limit_order_capacity: 0,
+            reserved: [0u8; 118],
         }
     }
 }
 
 impl Oracle {
-    pub const LEN: usize = 8 + 32 + 8 + AdaptiveFeeConstants::LEN + AdaptiveFeeVariables::LEN + 128;
+    pub const LEN: usize = 8
+        + 32
+        + 8
+        + AdaptiveFeeConstants::LEN
+        + AdaptiveFeeVariables::LEN
+        + 2
+        + 2
+        + 2
+        + 2
+        + 2
+        + 118;
 
     #[allow(clippy::too_many_arguments)]
     pub fn initialize(
@@ -311,6 +339,15 @@ impl Oracle {
         Ok(())
     }
 
+    // Oracle for the pool initialized with FeeTier (static fee).
+    // Adaptive fee constants are left zeroed because they are never used for such pools.
+    pub fn initialize_without_adaptive_fee(&mut self, solve: Pubkey) {
+        self.solve = solve;
+        self.trade_enable_timestamp = 0;
+        self.adaptive_fee_constants = AdaptiveFeeConstants::default();
+        self.reset_adaptive_fee_variables();
+    }
+
     pub fn initialize_adaptive_fee_constants(
         &mut self,
         constants: AdaptiveFeeConstants,
@@ -346,6 +383,7 @@ impl Oracle {
 pub struct OracleAccessor<'info> {
     oracle_account_info: AccountInfo<'info>,
     oracle_account_initialized: bool,
+    adaptive_fee_enabled: bool,
 }
 
 impl<'info> OracleAccessor<'info> {
@@ -355,9 +393,13 @@ impl<'info> OracleAccessor<'info> {
     ) -> Result<Self> {
         let oracle_account_initialized =
             Self::is_oracle_account_initialized(&oracle_account_info, solve.key())?;
+        // Pools initialized with FeeTier may have an Oracle account only for observations
+        let adaptive_fee_enabled =
+            oracle_account_initialized && solve.is_initialized_with_adaptive_fee_tier();
         Ok(Self {
             oracle_account_info,
             oracle_account_initialized,
+            adaptive_fee_enabled,
         })
     }
 
@@ -370,8 +412,17 @@ impl<'info> OracleAccessor<'info> {
         Ok(oracle.trade_enable_timestamp <= current_timestamp)
     }
 
-    pub fn get_adaptive_fee_info(&self) -> Result<Option<AdaptiveFeeInfo>> {
+    pub fn get_limit_order_count(&self) -> Result<u16> {
         if !self.oracle_account_initialized {
+            return Ok(0);
+        }
+
+        let oracle = self.load()?;
+        Ok(oracle.limit_order_count)
+    }
+
+    pub fn get_adaptive_fee_info(&self) -> Result<Option<AdaptiveFeeInfo>> {
+        if !self.adaptive_fee_enabled {
             return Ok(None);
         }
 
@@ -389,19 +440,85 @@ impl<'info> OracleAccessor<'info> {
         // If the Oracle account is not initialized, load_mut access will be skipped.
         // In other words, no need for writable flag on the Oracle account if it is not initialized.
 
-        match (self.oracle_account_initialized, adaptive_fee_info) {
-            // Oracle account has been initialized and adaptive fee info is provided
+        match (self.adaptive_fee_enabled, adaptive_fee_info) {
+            // Adaptive fee is enabled and adaptive fee info is provided
             (true, Some(adaptive_fee_info)) => {
                 let mut oracle = self.load_mut()?;
                 oracle.update_adaptive_fee_variables(adaptive_fee_info.variables);
                 Ok(())
             }
-            // Oracle account has not been initialized and adaptive fee info is not provided
+            // Adaptive fee is not enabled and adaptive fee info is not provided
             (false, None) => Ok(()),
             _ => unreachable!(),
         }
     }
 
+    // Record the pool state before the swap is applied.
+    // If observations are not enabled, no need for writable flag on the Oracle account.
+    pub fn record_observation(
+        &self,
+        current_timestamp: u64,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) -> Result<()> {
+        if !self.oracle_account_initialized || !self.load()?.is_observation_enabled() {
+            return Ok(());
+        }
+
+        // account must be writable
+        if !self.oracle_account_info.is_writable {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+
+        let mut data = self.oracle_account_info.try_borrow_mut_data()?;
+        let (oracle, observations) = load_oracle_with_observations_mut(&mut data)?;
+        oracle.write_observation(
+            observations,
+            current_timestamp,
+            tick_current_index,
+            liquidity,
+        )
+    }
+
+    // Fill the limit orders whose far tick has been crossed, at the current or a previous swap.
+    // Orders are filled lazily: if the Oracle account is not writable (e.g. swap v1 clients), the orders are
+    // left in the ticks and filled by a later swap, or settled at the current price when they are closed.
+    // `fill` returns false to leave an order to a later swap.
+    pub fn fill_limit_orders(
+        &self,
+        tick_current_index: i32,
+        tick_spacing: u16,
+        mut fill: impl FnMut(&mut LimitOrderEntry) -> Result<bool>,
+    ) -> Result<()> {
+        if !self.oracle_account_initialized || !self.oracle_account_info.is_writable {
+            return Ok(());
+        }
+
+        {
+            let data = self.oracle_account_info.try_borrow_data()?;
+            let (_, limit_orders) = load_oracle_with_limit_orders(&data)?;
+            if !limit_orders
+                .iter()
+                .any(|entry| entry.is_fillable(tick_current_index, tick_spacing))
+            {
+                return Ok(());
+            }
+        }
+
+        let mut data = self.oracle_account_info.try_borrow_mut_data()?;
+        let (oracle, limit_orders) = load_oracle_with_limit_orders_mut(&mut data)?;
+        for entry in limit_orders[..oracle.limit_order_count as usize]
+            .iter_mut()
+            .filter(|entry| entry.is_fillable(tick_current_index, tick_spacing))
+        {
+            if fill(entry)? {
+                entry.filled = 1;
+            }
+        }
+
+        Ok(())
+    }
+
     fn is_oracle_account_initialized(
         oracle_account_info: &AccountInfo<'info>,
         solve: Pubkey,
@@ -479,4 +596,3 @@ impl<'info> OracleAccessor<'info> {
         Ok(oracle_refmut)
     }
 }
-
@@ -0,0 +1,428 @@
+use crate::errors::ErrorCode;
+use crate::math::U256Muldiv;
+use crate::state::Oracle;
+use anchor_lang::prelude::*;
+use solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+// Observations are stored as a ring buffer right after the Oracle struct in the Oracle account data.
+// The buffer is grown by reallocating the Oracle account (see increase_observation_cardinality).
+pub const OBSERVATIONS_OFFSET: usize = 8 + std::mem::size_of::<Oracle>();
+
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Observation {
+    // Block time (in seconds) when this observation was written (0 means the slot is not written yet)
+    pub block_timestamp: u64,
+    // Accumulated tick_current_index * elapsed seconds since the first observation
+    pub tick_cumulative: i64,
+    // Accumulated elapsed seconds / max(1, liquidity) since the first observation (Q64.64)
+    pub seconds_per_liquidity_cumulative_x64: u128,
+}
+
+// Observation slots are accessed as a slice (bytemuck::cast_slice) rather than through AccountLoader.
+// Safety: Observation is packed (no padding) and consists of integers only, so any bit pattern is valid.
+unsafe impl bytemuck::Pod for Observation {}
+unsafe impl bytemuck::Zeroable for Observation {}
+
+impl Observation {
+    pub const LEN: usize = 8 + 8 + 16;
+
+    pub fn new(block_timestamp: u64) -> Self {
+        Self {
+            block_timestamp,
+            tick_cumulative: 0,
+            seconds_per_liquidity_cumulative_x64: 0,
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.block_timestamp != 0
+    }
+
+    /// Returns a new observation extrapolated from this one to `block_timestamp`,
+    /// assuming `tick_current_index` and `liquidity` were active for the whole period.
+    ///
+    /// Cumulative values wrap on overflow. Only differences between two observations are meaningful.
+    pub fn transform(&self, block_timestamp: u64, tick_current_index: i32, liquidity: u128) -> Self {
+        let elapsed = block_timestamp - self.block_timestamp;
+        let tick_cumulative = self
+            .tick_cumulative
+            .wrapping_add(i64::from(tick_current_index).wrapping_mul(elapsed as i64));
+        let seconds_per_liquidity_cumulative_x64 = self
+            .seconds_per_liquidity_cumulative_x64
+            .wrapping_add(((elapsed as u128) << 64) / liquidity.max(1));
+
+        Self {
+            block_timestamp,
+            tick_cumulative,
+            seconds_per_liquidity_cumulative_x64,
+        }
+    }
+}
+
+/// Splits the data of an Oracle account into the Oracle struct and its observation slots.
+/// The caller is responsible for checking the discriminator.
+pub fn load_oracle_with_observations(data: &[u8]) -> Result<(&Oracle, &[Observation])> {
+    if data.len() < OBSERVATIONS_OFFSET {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let (oracle_data, observations_data) = data.split_at(OBSERVATIONS_OFFSET);
+    let oracle: &Oracle = bytemuck::from_bytes(&oracle_data[8..]);
+
+    // limit orders (if any) are stored after the observation slots
+    let num_observations = oracle.observation_cardinality_next as usize;
+    if observations_data.len() < num_observations * Observation::LEN {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let observations: &[Observation] =
+        bytemuck::cast_slice(&observations_data[..num_observations * Observation::LEN]);
+    Ok((oracle, observations))
+}
+
+/// Mutable version of `load_oracle_with_observations`.
+pub fn load_oracle_with_observations_mut(
+    data: &mut [u8],
+) -> Result<(&mut Oracle, &mut [Observation])> {
+    if data.len() < OBSERVATIONS_OFFSET {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let (oracle_data, observations_data) = data.split_at_mut(OBSERVATIONS_OFFSET);
+    let oracle: &mut Oracle = bytemuck::from_bytes_mut(&mut oracle_data[8..]);
+
+    // limit orders (if any) are stored after the observation slots
+    let num_observations = oracle.observation_cardinality_next as usize;
+    if observations_data.len() < num_observations * Observation::LEN {
+        return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    }
+
+    let observations: &mut [Observation] =
+        bytemuck::cast_slice_mut(&mut observations_data[..num_observations * Observation::LEN]);
+    Ok((oracle, observations))
+}
+
+// An account can grow by at most MAX_PERMITTED_DATA_INCREASE bytes per instruction,
+// so a large cardinality must be reached over several calls.
+pub fn verify_oracle_size_increase(current_size: usize, required_size: usize) -> Result<()> {
+    if required_size.saturating_sub(current_size) > MAX_PERMITTED_DATA_INCREASE {
+        return Err(ErrorCode::ObservationCardinalityIncreaseTooLarge.into());
+    }
+    Ok(())
+}
+
+impl Oracle {
+    pub fn is_observation_enabled(&self) -> bool {
+        self.observation_cardinality > 0
+    }
+
+    pub fn observations_space(observation_cardinality: u16) -> usize {
+        Oracle::LEN + observation_cardinality as usize * Observation::LEN
+    }
+
+    pub fn update_observation_cardinality_next(
+        &mut self,
+        observation_cardinality_next: u16,
+    ) -> Result<()> {
+        if observation_cardinality_next <= self.observation_cardinality_next {
+            return Err(ErrorCode::InvalidObservationCardinality.into());
+        }
+        self.observation_cardinality_next = observation_cardinality_next;
+        Ok(())
+    }
+
+    /// Writes the first observation. Observations are disabled until this is called.
+    pub fn initialize_observations(
+        &mut self,
+        observations: &mut [Observation],
+        timestamp: u64,
+    ) -> Result<()> {
+        if self.is_observation_enabled() || observations.is_empty() {
+            return Err(ErrorCode::InvalidObservationCardinality.into());
+        }
+
+        observations[0] = Observation::new(timestamp);
+        self.observation_index = 0;
+        self.observation_cardinality = 1;
+        Ok(())
+    }
+
+    /// Records the state of the pool before it is modified by a swap.
+    /// At most one observation is written per block time.
+    pub fn write_observation(
+        &mut self,
+        observations: &mut [Observation],
+        timestamp: u64,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) -> Result<()> {
+        if !self.is_observation_enabled() {
+            return Ok(());
+        }
+
+        let last = observations[self.observation_index as usize];
+        if last.block_timestamp == timestamp {
+            return Ok(());
+        }
+        if timestamp < last.block_timestamp {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+
+        // extend the ring buffer only once the last allocated slot has been filled
+        let cardinality = if self.observation_cardinality_next > self.observation_cardinality
+            && self.observation_index == self.observation_cardinality - 1
+        {
+            self.observation_cardinality_next
+        } else {
+            self.observation_cardinality
+        };
+
+        let index = (self.observation_index + 1) % cardinality;
+        observations[index as usize] = last.transform(timestamp, tick_current_index, liquidity);
+
+        self.observation_index = index;
+        self.observation_cardinality = cardinality;
+        Ok(())
+    }
+
+    /// Returns the cumulative values at `timestamp - seconds_ago`.
+    /// `tick_current_index` and `liquidity` must be the current state of the pool.
+    pub fn observe_single(
+        &self,
+        observations: &[Observation],
+        timestamp: u64,
+        seconds_ago: u32,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) -> Result<Observation> {
+        if !self.is_observation_enabled() {
+            return Err(ErrorCode::ObservationsNotInitialized.into());
+        }
+
+        let target = timestamp
+            .checked_sub(seconds_ago as u64)
+            .ok_or(ErrorCode::InvalidObservationWindow)?;
+
+        let cardinality = self.observation_cardinality as usize;
+        let newest = observations[self.observation_index as usize];
+        if target >= newest.block_timestamp {
+            if target == newest.block_timestamp {
+                return Ok(newest);
+            }
+            return Ok(newest.transform(target, tick_current_index, liquidity));
+        }
+
+        let mut oldest = observations[(self.observation_index as usize + 1) % cardinality];
+        if !oldest.is_initialized() {
+            // the ring buffer has not wrapped around yet
+            oldest = observations[0];
+        }
+        if target < oldest.block_timestamp {
+            return Err(ErrorCode::ObservationTooOld.into());
+        }
+
+        let (before, at_or_after) = self.binary_search(observations, target);
+        if target == before.block_timestamp {
+            return Ok(before);
+        }
+        if target == at_or_after.block_timestamp {
+            return Ok(at_or_after);
+        }
+
+        // interpolate between the two surrounding observations
+        let observation_time_delta = at_or_after.block_timestamp - before.block_timestamp;
+        let target_delta = target - before.block_timestamp;
+
+        let tick_cumulative = before.tick_cumulative.wrapping_add(
+            at_or_after
+                .tick_cumulative
+                .wrapping_sub(before.tick_cumulative)
+                / observation_time_delta as i64
+                * target_delta as i64,
+        );
+
+        let seconds_per_liquidity_delta = at_or_after
+            .seconds_per_liquidity_cumulative_x64
+            .wrapping_sub(before.seconds_per_liquidity_cumulative_x64);
+        let seconds_per_liquidity_cumulative_x64 =
+            before.seconds_per_liquidity_cumulative_x64.wrapping_add(
+                U256Muldiv::new(0, seconds_per_liquidity_delta)
+                    .mul(U256Muldiv::new(0, target_delta as u128))
+                    .div(U256Muldiv::new(0, observation_time_delta as u128), false)
+                    .0
+                    .try_into_u128()?,
+            );
+
+        Ok(Observation {
+            block_timestamp: target,
+            tick_cumulative,
+            seconds_per_liquidity_cumulative_x64,
+        })
+    }
+
+    /// Returns the time-weighted average tick over the window
+    /// `[timestamp - seconds_ago_start, timestamp - seconds_ago_end]` (rounded to negative infinity).
+    pub fn get_time_weighted_average_tick(
+        &self,
+        observations: &[Observation],
+        timestamp: u64,
+        seconds_ago_start: u32,
+        seconds_ago_end: u32,
+        tick_current_index: i32,
+        liquidity: u128,
+    ) -> Result<i32> {
+        if seconds_ago_start <= seconds_ago_end {
+            return Err(ErrorCode::InvalidObservationWindow.into());
+        }
+
+        let start = self.observe_single(
+            observations,
+            timestamp,
+            seconds_ago_start,
+            tick_current_index,
+            liquidity,
+        )?;
+        let end = self.observe_single(
+            observations,
+            timestamp,
+            seconds_ago_end,
+            tick_current_index,
+            liquidity,
+        )?;
+
+        let window = i64::from(seconds_ago_start - seconds_ago_end);
+        let tick_cumulative_delta = end.tick_cumulative.wrapping_sub(start.tick_cumulative);
+
+        let mut average_tick = tick_cumulative_delta / window;
+        if tick_cumulative_delta < 0 && tick_cumulative_delta % window != 0 {
+            average_tick -= 1;
+        }
+
+        Ok(average_tick as i32)
+    }
+
+    // Find the observations surrounding target.
+    // The caller must guarantee oldest.block_timestamp <= target < newest.block_timestamp.
+    fn binary_search(&self, observations: &[Observation], target: u64) -> (Observation, Observation) {
+        let cardinality = self.observation_cardinality as usize;
+        let mut l = (self.observation_index as usize + 1) % cardinality; // oldest
+        let mut r = l + cardinality - 1; // newest
+
+        loop {
+            let i = (l + r) / 2;
+
+            let before = observations[i % cardinality];
+            if !before.is_initialized() {
+                // not yet written slot, move towards more recent observations
+                l = i + 1;
+                continue;
+            }
+
+            let at_or_after = observations[(i + 1) % cardinality];
+            let target_at_or_after = before.block_timestamp <= target;
+
+            if target_at_or_after && target <= at_or_after.block_timestamp {
+                return (before, at_or_after);
+            }
+
+            if !target_at_or_after {
+                r = i - 1;
+            } else {
+                l = i + 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod oracle_observation_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_oracle_size_increase() {
+        let current_size = Oracle::observations_space(0);
+        let max_cardinality = (MAX_PERMITTED_DATA_INCREASE / Observation::LEN) as u16;
+        assert!(verify_oracle_size_increase(
+            current_size,
+            Oracle::observations_space(max_cardinality)
+        )
+        .is_ok());
+        assert_eq!(
+            verify_oracle_size_increase(
+                current_size,
+                Oracle::observations_space(max_cardinality + 1)
+            )
+            .err()
+            .unwrap(),
+            ErrorCode::ObservationCardinalityIncreaseTooLarge.into()
+        );
+        // The limit applies per call
+        assert!(verify_oracle_size_increase(
+            Oracle::observations_space(max_cardinality),
+            Oracle::observations_space(max_cardinality * 2)
+        )
+        .is_ok());
+    }
+
+    fn oracle_with_observations(cardinality_next: u16) -> (Oracle, Vec<Observation>) {
+        let oracle = Oracle::default();
+        let observations = vec![Observation::default(); cardinality_next as usize];
+        (oracle, observations)
+    }
+
+    #[test]
+    fn test_time_weighted_average_tick() {
+        let (mut oracle, mut observations) = oracle_with_observations(4);
+        oracle.update_observation_cardinality_next(4).unwrap();
+        oracle
+            .initialize_observations(&mut observations, 1_000)
+            .unwrap();
+
+        // tick 100 for 10 seconds, tick -50 for 30 seconds, tick 20 until now
+        oracle
+            .write_observation(&mut observations, 1_010, 100, 1_000)
+            .unwrap();
+        oracle
+            .write_observation(&mut observations, 1_040, -50, 1_000)
+            .unwrap();
+        // same block time is ignored
+        oracle
+            .write_observation(&mut observations, 1_040, 0, 1_000)
+            .unwrap();
+        assert_eq!({ oracle.observation_index }, 2);
+        assert_eq!({ oracle.observation_cardinality }, 4);
+
+        let now = 1_050;
+        assert_eq!(
+            oracle
+                .get_time_weighted_average_tick(&observations, now, 10, 0, 20, 1_000)
+                .unwrap(),
+            20
+        );
+        assert_eq!(
+            oracle
+                .get_time_weighted_average_tick(&observations, now, 50, 40, 20, 1_000)
+                .unwrap(),
+            100
+        );
+        // (100 * 10 - 50 * 30 + 20 * 10) / 50 = -6
+        assert_eq!(
+            oracle
+                .get_time_weighted_average_tick(&observations, now, 50, 0, 20, 1_000)
+                .unwrap(),
+            -6
+        );
+        // interpolated: (100 * 5 - 50 * 10) / 15 = 0
+        assert_eq!(
+            oracle
+                .get_time_weighted_average_tick(&observations, now, 45, 30, 20, 1_000)
+                .unwrap(),
+            0
+        );
+        assert!(oracle
+            .get_time_weighted_average_tick(&observations, now, 51, 0, 20, 1_000)
+            .is_err());
+    }
+}
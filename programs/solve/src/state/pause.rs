@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Pause flags are stored as a bitmask on SolvesConfigExtension (all pools in the config)
+// and in the SolveTail (a single pool). An operation is paused if the flag is set on either of them.
+// Instructions that existed before pausing was introduced do not take the config extension, so that
+// their account lists are unchanged. They read the config flags from the copy in the SolveTail,
+// which anyone can sync with sync_pool_pause_flags.
+pub const PAUSE_FLAG_SWAP: u8 = 1 << 0;
+pub const PAUSE_FLAG_INCREASE_LIQUIDITY: u8 = 1 << 1;
+// Withdrawals (decrease liquidity, collect, close) are never paused
+pub const PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS: u8 = 1 << 2;
+
+pub const PAUSE_FLAGS_MASK: u8 =
+    PAUSE_FLAG_SWAP | PAUSE_FLAG_INCREASE_LIQUIDITY | PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PausableOperation {
+    Swap,
+    IncreaseLiquidity,
+    // operations other than swaps, liquidity increases and withdrawals
+    Other,
+}
+
+impl PausableOperation {
+    pub fn verify_not_paused(&self, pause_flags: u8) -> Result<()> {
+        if pause_flags & PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS != 0 {
+            return Err(ErrorCode::OperationPaused.into());
+        }
+
+        match self {
+            PausableOperation::Swap if pause_flags & PAUSE_FLAG_SWAP != 0 => {
+                Err(ErrorCode::SwapPaused.into())
+            }
+            PausableOperation::IncreaseLiquidity
+                if pause_flags & PAUSE_FLAG_INCREASE_LIQUIDITY != 0 =>
+            {
+                Err(ErrorCode::IncreaseLiquidityPaused.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn validate_pause_flags(pause_flags: u8) -> Result<()> {
+    if pause_flags & !PAUSE_FLAGS_MASK != 0 {
+        return Err(ErrorCode::InvalidPauseFlags.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod pause_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_not_paused() {
+        assert!(PausableOperation::Swap.verify_not_paused(0).is_ok());
+        assert_eq!(
+            PausableOperation::Swap
+                .verify_not_paused(PAUSE_FLAG_SWAP)
+                .err()
+                .unwrap(),
+            ErrorCode::SwapPaused.into()
+        );
+        // Only the paused operation is rejected
+        assert!(PausableOperation::IncreaseLiquidity
+            .verify_not_paused(PAUSE_FLAG_SWAP)
+            .is_ok());
+        assert!(PausableOperation::Other
+            .verify_not_paused(PAUSE_FLAG_SWAP | PAUSE_FLAG_INCREASE_LIQUIDITY)
+            .is_ok());
+        assert_eq!(
+            PausableOperation::IncreaseLiquidity
+                .verify_not_paused(PAUSE_FLAG_INCREASE_LIQUIDITY)
+                .err()
+                .unwrap(),
+            ErrorCode::IncreaseLiquidityPaused.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_not_paused_all_except_withdrawals() {
+        for operation in [
+            PausableOperation::Swap,
+            PausableOperation::IncreaseLiquidity,
+            PausableOperation::Other,
+        ] {
+            assert_eq!(
+                operation
+                    .verify_not_paused(PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS)
+                    .err()
+                    .unwrap(),
+                ErrorCode::OperationPaused.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_pause_flags() {
+        assert!(validate_pause_flags(0).is_ok());
+        assert!(validate_pause_flags(PAUSE_FLAGS_MASK).is_ok());
+        assert_eq!(
+            validate_pause_flags(1 << 3).err().unwrap(),
+            ErrorCode::InvalidPauseFlags.into()
+        );
+    }
+}
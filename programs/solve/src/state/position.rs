@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, math::FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD, state::NUM_REWARDS};
+use crate::{
+    errors::ErrorCode,
+    math::{div_round_up_if_u256, mul_u256, U256Muldiv, FULL_RANGE_ONLY_TICK_SPACING_THRESHOLD},
+    state::NUM_REWARDS,
+};
 
 use super::{Solve, Tick};
 
@@ -37,6 +41,14 @@ pub struct Position {
 impl Position {
     pub const LEN: usize = 8 + 136 + 72;
 
+    // Position has no reserved space, so a position with a PositionRewardExtension is marked
+    // by one extra byte allocated when the extension is initialized.
+    pub const REWARD_EXTENSION_MARKER_LEN: usize = 1;
+
+    pub fn has_reward_extension(position_account_info: &AccountInfo) -> bool {
+        position_account_info.data_len() > Self::LEN
+    }
+
     pub fn is_position_empty(position: &Position) -> bool {
         let fees_not_owed = position.fee_owed_a == 0 && position.fee_owed_b == 0;
         let mut rewards_not_owed = true;
@@ -97,6 +109,123 @@ impl Position {
             return Err(ErrorCode::SameTickRangeNotAllowed.into());
         }
 
+        // fee_owed and rewards.amount_owed should be zero due to the check above
+        self.set_position_range(solve, new_tick_lower_index, new_tick_upper_index)
+    }
+
+    // Moves a position whose liquidity has been withdrawn and whose fees have been collected to a new range.
+    // Unlike reset_position_range, rewards owed are kept because they do not depend on the range.
+    pub fn reposition_range(
+        &mut self,
+        solve: &Account<Solve>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+    ) -> Result<()> {
+        if self.liquidity != 0 || self.fee_owed_a != 0 || self.fee_owed_b != 0 {
+            return Err(ErrorCode::ClosePositionNotEmpty.into());
+        }
+
+        if new_tick_lower_index == self.tick_lower_index
+            && new_tick_upper_index == self.tick_upper_index
+        {
+            return Err(ErrorCode::SameTickRangeNotAllowed.into());
+        }
+
+        self.set_position_range(solve, new_tick_lower_index, new_tick_upper_index)
+    }
+
+    // Moves liquidity_amount and a pro-rata share of the fees and rewards owed into new_position.
+    // new_position must be opened with the same range. The checkpoints are copied so both positions
+    // keep accruing from the same growth. Ticks are not touched because the total liquidity is unchanged.
+    pub fn split(&mut self, new_position: &mut Position, liquidity_amount: u128) -> Result<()> {
+        if liquidity_amount == 0 {
+            return Err(ErrorCode::LiquidityZero.into());
+        }
+        if liquidity_amount > self.liquidity {
+            return Err(ErrorCode::LiquidityUnderflow.into());
+        }
+        if new_position.tick_lower_index != self.tick_lower_index
+            || new_position.tick_upper_index != self.tick_upper_index
+        {
+            return Err(ErrorCode::InvalidTickIndex.into());
+        }
+
+        let fee_owed_a = split_amount_owed(self.fee_owed_a, liquidity_amount, self.liquidity)?;
+        let fee_owed_b = split_amount_owed(self.fee_owed_b, liquidity_amount, self.liquidity)?;
+
+        new_position.liquidity = liquidity_amount;
+        new_position.fee_growth_checkpoint_a = self.fee_growth_checkpoint_a;
+        new_position.fee_owed_a = fee_owed_a;
+        new_position.fee_growth_checkpoint_b = self.fee_growth_checkpoint_b;
+        new_position.fee_owed_b = fee_owed_b;
+
+        for i in 0..NUM_REWARDS {
+            let amount_owed = split_amount_owed(
+                self.reward_infos[i].amount_owed,
+                liquidity_amount,
+                self.liquidity,
+            )?;
+            new_position.reward_infos[i] = PositionRewardInfo {
+                growth_inside_checkpoint: self.reward_infos[i].growth_inside_checkpoint,
+                amount_owed,
+            };
+            self.reward_infos[i].amount_owed -= amount_owed;
+        }
+
+        self.liquidity -= liquidity_amount;
+        self.fee_owed_a -= fee_owed_a;
+        self.fee_owed_b -= fee_owed_b;
+
+        Ok(())
+    }
+
+    // Adds the liquidity and the fees and rewards owed of other into this position.
+    // Both positions must have the same range and be checkpointed at the same time.
+    pub fn merge(&mut self, other: &Position) -> Result<()> {
+        if other.tick_lower_index != self.tick_lower_index
+            || other.tick_upper_index != self.tick_upper_index
+        {
+            return Err(ErrorCode::PositionsNotMergeable.into());
+        }
+
+        // The checkpoints of a position without liquidity are not updated
+        if self.liquidity == 0 {
+            self.fee_growth_checkpoint_a = other.fee_growth_checkpoint_a;
+            self.fee_growth_checkpoint_b = other.fee_growth_checkpoint_b;
+            for i in 0..NUM_REWARDS {
+                self.reward_infos[i].growth_inside_checkpoint =
+                    other.reward_infos[i].growth_inside_checkpoint;
+            }
+        }
+
+        self.liquidity = self
+            .liquidity
+            .checked_add(other.liquidity)
+            .ok_or(ErrorCode::LiquidityOverflow)?;
+        self.fee_owed_a = self
+            .fee_owed_a
+            .checked_add(other.fee_owed_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        self.fee_owed_b = self
+            .fee_owed_b
+            .checked_add(other.fee_owed_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        for i in 0..NUM_REWARDS {
+            self.reward_infos[i].amount_owed = self.reward_infos[i]
+                .amount_owed
+                .checked_add(other.reward_infos[i].amount_owed)
+                .ok_or(ErrorCode::AmountCalcOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    fn set_position_range(
+        &mut self,
+        solve: &Account<Solve>,
+        new_tick_lower_index: i32,
+        new_tick_upper_index: i32,
+    ) -> Result<()> {
         validate_tick_range_for_solve(solve, new_tick_lower_index, new_tick_upper_index)?;
 
         // Wihle we could theoretically update the solve here
@@ -111,7 +240,6 @@ impl Position {
         self.fee_growth_checkpoint_a = 0;
         self.fee_growth_checkpoint_b = 0;
 
-        // fee_owed and rewards.amount_owed should be zero due to the check above
         for i in 0..NUM_REWARDS {
             self.reward_infos[i].growth_inside_checkpoint = 0;
         }
@@ -120,6 +248,18 @@ impl Position {
     }
 }
 
+// Rounds down so the split never moves more than the original position owes
+fn split_amount_owed(amount_owed: u64, liquidity_amount: u128, liquidity: u128) -> Result<u64> {
+    let split_amount = div_round_up_if_u256(
+        mul_u256(amount_owed as u128, liquidity_amount),
+        U256Muldiv::new(0, liquidity),
+        false,
+    )?;
+    split_amount
+        .try_into()
+        .map_err(|_| ErrorCode::NumberCastError.into())
+}
+
 fn validate_tick_range_for_solve(
     solve: &Account<Solve>,
     tick_lower_index: i32,
@@ -160,4 +300,174 @@ pub struct PositionUpdate {
     pub fee_growth_checkpoint_b: u128,
     pub fee_owed_b: u64,
     pub reward_infos: [PositionRewardInfo; NUM_REWARDS],
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod position_tests {
+    use super::*;
+
+    const TICK_LOWER_INDEX: i32 = -128;
+    const TICK_UPPER_INDEX: i32 = 128;
+
+    fn new_position(
+        liquidity: u128,
+        fee_owed: (u64, u64),
+        rewards_owed: [u64; NUM_REWARDS],
+    ) -> Position {
+        Position {
+            liquidity,
+            tick_lower_index: TICK_LOWER_INDEX,
+            tick_upper_index: TICK_UPPER_INDEX,
+            fee_growth_checkpoint_a: 10,
+            fee_owed_a: fee_owed.0,
+            fee_growth_checkpoint_b: 20,
+            fee_owed_b: fee_owed.1,
+            reward_infos: rewards_owed.map(|amount_owed| PositionRewardInfo {
+                growth_inside_checkpoint: 30,
+                amount_owed,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn new_empty_position() -> Position {
+        Position {
+            tick_lower_index: TICK_LOWER_INDEX,
+            tick_upper_index: TICK_UPPER_INDEX,
+            ..Default::default()
+        }
+    }
+
+    fn rewards_owed(position: &Position) -> [u64; NUM_REWARDS] {
+        position
+            .reward_infos
+            .map(|reward_info| reward_info.amount_owed)
+    }
+
+    #[test]
+    fn test_split_pro_rata() {
+        let mut position = new_position(1_000, (100, 7), [30, 1, 0]);
+        let mut new_position = new_empty_position();
+        position.split(&mut new_position, 250).unwrap();
+
+        // The amounts owed of the new position are rounded down
+        assert_eq!(new_position.liquidity, 250);
+        assert_eq!(new_position.fee_owed_a, 25);
+        assert_eq!(new_position.fee_owed_b, 1);
+        assert_eq!(rewards_owed(&new_position), [7, 0, 0]);
+
+        // The rest stays in the position, so nothing is lost
+        assert_eq!(position.liquidity, 750);
+        assert_eq!(position.fee_owed_a, 75);
+        assert_eq!(position.fee_owed_b, 6);
+        assert_eq!(rewards_owed(&position), [23, 1, 0]);
+
+        // Both positions keep the same checkpoints
+        assert_eq!(new_position.fee_growth_checkpoint_a, 10);
+        assert_eq!(new_position.fee_growth_checkpoint_b, 20);
+        for reward_info in new_position.reward_infos {
+            assert_eq!(reward_info.growth_inside_checkpoint, 30);
+        }
+        assert_eq!(position.fee_growth_checkpoint_a, 10);
+        assert_eq!(position.fee_growth_checkpoint_b, 20);
+    }
+
+    #[test]
+    fn test_split_all_liquidity() {
+        let mut position = new_position(1_000, (100, 7), [30, 1, 0]);
+        let mut new_position = new_empty_position();
+        position.split(&mut new_position, 1_000).unwrap();
+
+        assert_eq!(new_position.liquidity, 1_000);
+        assert_eq!(new_position.fee_owed_a, 100);
+        assert_eq!(new_position.fee_owed_b, 7);
+        assert_eq!(rewards_owed(&new_position), [30, 1, 0]);
+        assert_eq!(position.liquidity, 0);
+        assert_eq!(position.fee_owed_a, 0);
+        assert_eq!(position.fee_owed_b, 0);
+        assert_eq!(rewards_owed(&position), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_split_invalid() {
+        let mut position = new_position(1_000, (100, 7), [30, 1, 0]);
+        let mut new_position = new_empty_position();
+        assert_eq!(
+            position.split(&mut new_position, 0).unwrap_err(),
+            ErrorCode::LiquidityZero.into()
+        );
+        assert_eq!(
+            position.split(&mut new_position, 1_001).unwrap_err(),
+            ErrorCode::LiquidityUnderflow.into()
+        );
+
+        let mut new_position = Position {
+            tick_lower_index: TICK_LOWER_INDEX,
+            tick_upper_index: TICK_UPPER_INDEX + 64,
+            ..Default::default()
+        };
+        assert_eq!(
+            position.split(&mut new_position, 500).unwrap_err(),
+            ErrorCode::InvalidTickIndex.into()
+        );
+        assert_eq!(position.liquidity, 1_000);
+        assert_eq!(position.fee_owed_a, 100);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut position = new_position(1_000, (100, 7), [30, 1, 0]);
+        let other = new_position(250, (25, 1), [7, 0, 5]);
+        position.merge(&other).unwrap();
+
+        assert_eq!(position.liquidity, 1_250);
+        assert_eq!(position.fee_owed_a, 125);
+        assert_eq!(position.fee_owed_b, 8);
+        assert_eq!(rewards_owed(&position), [37, 1, 5]);
+    }
+
+    #[test]
+    fn test_merge_checkpoints() {
+        // A position with liquidity keeps its checkpoints, as both positions are checkpointed at the same time
+        let mut position = new_position(1_000, (0, 0), [0; NUM_REWARDS]);
+        let mut other = new_position(250, (0, 0), [0; NUM_REWARDS]);
+        other.fee_growth_checkpoint_a = 11;
+        position.merge(&other).unwrap();
+        assert_eq!(position.fee_growth_checkpoint_a, 10);
+
+        // The checkpoints of a position without liquidity are not updated, so they are taken from the other position
+        let mut position = new_empty_position();
+        let other = new_position(250, (25, 1), [7, 0, 5]);
+        position.merge(&other).unwrap();
+        assert_eq!(position.liquidity, 250);
+        assert_eq!(position.fee_growth_checkpoint_a, 10);
+        assert_eq!(position.fee_growth_checkpoint_b, 20);
+        for reward_info in position.reward_infos {
+            assert_eq!(reward_info.growth_inside_checkpoint, 30);
+        }
+        assert_eq!(position.fee_owed_a, 25);
+    }
+
+    #[test]
+    fn test_merge_invalid() {
+        let mut position = new_position(1_000, (100, 7), [30, 1, 0]);
+        let mut other = new_position(250, (25, 1), [7, 0, 5]);
+        other.tick_upper_index = TICK_UPPER_INDEX + 64;
+        assert_eq!(
+            position.merge(&other).unwrap_err(),
+            ErrorCode::PositionsNotMergeable.into()
+        );
+
+        let other = new_position(u128::MAX, (0, 0), [0; NUM_REWARDS]);
+        assert_eq!(
+            position.merge(&other).unwrap_err(),
+            ErrorCode::LiquidityOverflow.into()
+        );
+
+        let other = new_position(1, (u64::MAX, 0), [0; NUM_REWARDS]);
+        assert_eq!(
+            position.merge(&other).unwrap_err(),
+            ErrorCode::AmountCalcOverflow.into()
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+
+// Authorizes a keeper to compound the fees of a position on behalf of its owner.
+// The authorization is bound to the owner of the position token account at the time of authorization,
+// so it becomes ineffective once the position is transferred to another owner.
+// The keeper can only compound while the pool price is within the price bounds set by the owner,
+// and the swap of the excess fees cannot move the price out of them.
+#[account]
+pub struct PositionKeeper {
+    pub position: Pubkey,             // 32
+    pub position_owner: Pubkey,       // 32
+    pub keeper: Pubkey,               // 32
+    pub sqrt_price_lower_bound: u128, // 16
+    pub sqrt_price_upper_bound: u128, // 16
+                                      // 96 RESERVE
+}
+
+impl PositionKeeper {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 16 + 16 + 96;
+
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        position_owner: Pubkey,
+        keeper: Pubkey,
+        sqrt_price_lower_bound: u128,
+        sqrt_price_upper_bound: u128,
+    ) -> Result<()> {
+        if sqrt_price_lower_bound < MIN_SQRT_PRICE_X64
+            || sqrt_price_upper_bound > MAX_SQRT_PRICE_X64
+            || sqrt_price_lower_bound >= sqrt_price_upper_bound
+        {
+            return Err(ErrorCode::InvalidPositionKeeperPriceBounds.into());
+        }
+
+        self.position = position;
+        self.position_owner = position_owner;
+        self.keeper = keeper;
+        self.sqrt_price_lower_bound = sqrt_price_lower_bound;
+        self.sqrt_price_upper_bound = sqrt_price_upper_bound;
+
+        Ok(())
+    }
+
+    pub fn is_authorized(&self, keeper: &Pubkey, position_owner: &Pubkey) -> bool {
+        self.keeper == *keeper && self.position_owner == *position_owner
+    }
+
+    pub fn verify_sqrt_price(&self, sqrt_price: u128) -> Result<()> {
+        if sqrt_price < self.sqrt_price_lower_bound || sqrt_price > self.sqrt_price_upper_bound {
+            return Err(ErrorCode::PriceOutOfPositionKeeperBounds.into());
+        }
+        Ok(())
+    }
+
+    // Returns the tighter of the requested limit and the price bound in the swap direction
+    pub fn bound_sqrt_price_limit(&self, sqrt_price_limit: u128, a_to_b: bool) -> u128 {
+        if a_to_b {
+            sqrt_price_limit.max(self.sqrt_price_lower_bound)
+        } else if sqrt_price_limit == 0 {
+            // NO_EXPLICIT_SQRT_PRICE_LIMIT
+            self.sqrt_price_upper_bound
+        } else {
+            sqrt_price_limit.min(self.sqrt_price_upper_bound)
+        }
+    }
+}
+
+#[cfg(test)]
+mod position_keeper_tests {
+    use super::*;
+
+    const LOWER_BOUND: u128 = 1 << 63;
+    const UPPER_BOUND: u128 = 1 << 65;
+
+    fn position_keeper() -> PositionKeeper {
+        let mut position_keeper = PositionKeeper {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            keeper: Pubkey::default(),
+            sqrt_price_lower_bound: 0,
+            sqrt_price_upper_bound: 0,
+        };
+        position_keeper
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                LOWER_BOUND,
+                UPPER_BOUND,
+            )
+            .unwrap();
+        position_keeper
+    }
+
+    #[test]
+    fn test_initialize_invalid_bounds() {
+        let mut position_keeper = position_keeper();
+        for (lower, upper) in [
+            (MIN_SQRT_PRICE_X64 - 1, UPPER_BOUND),
+            (LOWER_BOUND, MAX_SQRT_PRICE_X64 + 1),
+            (UPPER_BOUND, LOWER_BOUND),
+            (LOWER_BOUND, LOWER_BOUND),
+        ] {
+            let result = position_keeper.initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                lower,
+                upper,
+            );
+            assert_eq!(
+                result.unwrap_err(),
+                ErrorCode::InvalidPositionKeeperPriceBounds.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let position_keeper = position_keeper();
+        let keeper = position_keeper.keeper;
+        let owner = position_keeper.position_owner;
+        assert!(position_keeper.is_authorized(&keeper, &owner));
+        assert!(!position_keeper.is_authorized(&Pubkey::new_unique(), &owner));
+        // The position has been transferred to another owner
+        assert!(!position_keeper.is_authorized(&keeper, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_verify_sqrt_price() {
+        let position_keeper = position_keeper();
+        assert!(position_keeper.verify_sqrt_price(LOWER_BOUND).is_ok());
+        assert!(position_keeper.verify_sqrt_price(1 << 64).is_ok());
+        assert!(position_keeper.verify_sqrt_price(UPPER_BOUND).is_ok());
+        for sqrt_price in [LOWER_BOUND - 1, UPPER_BOUND + 1] {
+            assert_eq!(
+                position_keeper.verify_sqrt_price(sqrt_price).unwrap_err(),
+                ErrorCode::PriceOutOfPositionKeeperBounds.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bound_sqrt_price_limit() {
+        let position_keeper = position_keeper();
+        // a to b
+        assert_eq!(position_keeper.bound_sqrt_price_limit(0, true), LOWER_BOUND);
+        assert_eq!(
+            position_keeper.bound_sqrt_price_limit(LOWER_BOUND - 1, true),
+            LOWER_BOUND
+        );
+        assert_eq!(
+            position_keeper.bound_sqrt_price_limit(LOWER_BOUND + 1, true),
+            LOWER_BOUND + 1
+        );
+        // b to a
+        assert_eq!(
+            position_keeper.bound_sqrt_price_limit(0, false),
+            UPPER_BOUND
+        );
+        assert_eq!(
+            position_keeper.bound_sqrt_price_limit(UPPER_BOUND + 1, false),
+            UPPER_BOUND
+        );
+        assert_eq!(
+            position_keeper.bound_sqrt_price_limit(UPPER_BOUND - 1, false),
+            UPPER_BOUND - 1
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::{MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE};
+use crate::state::MAX_ADMIN_TIMELOCK_DELAY;
+
+// A queued change must be executed within this window once it becomes executable.
+// After the window it can only be cancelled.
+pub const QUEUED_CHANGE_EXECUTION_WINDOW: u64 = 60 * 60 * 24 * 7;
+
+#[account]
+pub struct QueuedChange {
+    pub solves_config: Pubkey, // 32
+    // Solve for pool changes, FeeTier for fee tier changes, SolvesConfig for config changes
+    pub target: Pubkey,                         // 32
+    pub parameter_change: ParameterChangeLabel, // 1
+    pub value: u64,                             // 8
+    pub queued_timestamp: u64,                  // 8
+    // Estimated from the delay at queue time, the delay at execution time is applied at execution
+    pub execution_timestamp: u64, // 8
+                                  // 64 RESERVE
+}
+
+#[non_exhaustive]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ParameterChange {
+    FeeRate { fee_rate: u16 },
+    ProtocolFeeRate { protocol_fee_rate: u16 },
+    DefaultProtocolFeeRate { default_protocol_fee_rate: u16 },
+    AdminTimelockDelay { admin_timelock_delay: u64 },
+    DefaultFeeRate { default_fee_rate: u16 },
+}
+
+// To avoid storing an enum that may be extended in the future to the account, separate the variant label and value. The value is added flatly to the account.
+#[non_exhaustive]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ParameterChangeLabel {
+    FeeRate,
+    ProtocolFeeRate,
+    DefaultProtocolFeeRate,
+    AdminTimelockDelay,
+    DefaultFeeRate,
+}
+
+impl ParameterChange {
+    pub fn label(&self) -> ParameterChangeLabel {
+        match self {
+            ParameterChange::FeeRate { .. } => ParameterChangeLabel::FeeRate,
+            ParameterChange::ProtocolFeeRate { .. } => ParameterChangeLabel::ProtocolFeeRate,
+            ParameterChange::DefaultProtocolFeeRate { .. } => {
+                ParameterChangeLabel::DefaultProtocolFeeRate
+            }
+            ParameterChange::AdminTimelockDelay { .. } => ParameterChangeLabel::AdminTimelockDelay,
+            ParameterChange::DefaultFeeRate { .. } => ParameterChangeLabel::DefaultFeeRate,
+        }
+    }
+
+    // Used as a seed, so that a target can have one queued change per parameter
+    pub fn seed(&self) -> [u8; 1] {
+        [self.label() as u8]
+    }
+
+    pub fn value(&self) -> u64 {
+        match *self {
+            ParameterChange::FeeRate { fee_rate } => fee_rate as u64,
+            ParameterChange::ProtocolFeeRate { protocol_fee_rate } => protocol_fee_rate as u64,
+            ParameterChange::DefaultProtocolFeeRate {
+                default_protocol_fee_rate,
+            } => default_protocol_fee_rate as u64,
+            ParameterChange::AdminTimelockDelay {
+                admin_timelock_delay,
+            } => admin_timelock_delay,
+            ParameterChange::DefaultFeeRate { default_fee_rate } => default_fee_rate as u64,
+        }
+    }
+}
+
+impl ParameterChangeLabel {
+    pub fn is_pool_change(&self) -> bool {
+        matches!(
+            self,
+            ParameterChangeLabel::FeeRate | ParameterChangeLabel::ProtocolFeeRate
+        )
+    }
+
+    pub fn is_fee_tier_change(&self) -> bool {
+        matches!(self, ParameterChangeLabel::DefaultFeeRate)
+    }
+}
+
+impl QueuedChange {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 64;
+
+    pub fn initialize(
+        &mut self,
+        solves_config: Pubkey,
+        target: Pubkey,
+        parameter_change: ParameterChange,
+        queued_timestamp: u64,
+        admin_timelock_delay: u64,
+    ) -> Result<()> {
+        // Changes are applied directly while the timelock is disabled
+        if admin_timelock_delay == 0 {
+            return Err(ErrorCode::AdminTimelockDisabled.into());
+        }
+
+        // Fail early instead of at the execution
+        match parameter_change {
+            ParameterChange::FeeRate { fee_rate: rate }
+            | ParameterChange::DefaultFeeRate {
+                default_fee_rate: rate,
+            } => {
+                if rate > MAX_FEE_RATE {
+                    return Err(ErrorCode::FeeRateMaxExceeded.into());
+                }
+            }
+            ParameterChange::ProtocolFeeRate {
+                protocol_fee_rate: rate,
+            }
+            | ParameterChange::DefaultProtocolFeeRate {
+                default_protocol_fee_rate: rate,
+            } => {
+                if rate > MAX_PROTOCOL_FEE_RATE {
+                    return Err(ErrorCode::ProtocolFeeRateMaxExceeded.into());
+                }
+            }
+            ParameterChange::AdminTimelockDelay {
+                admin_timelock_delay,
+            } => {
+                if admin_timelock_delay > MAX_ADMIN_TIMELOCK_DELAY {
+                    return Err(ErrorCode::AdminTimelockDelayMaxExceeded.into());
+                }
+            }
+        }
+
+        self.solves_config = solves_config;
+        self.target = target;
+        self.parameter_change = parameter_change.label();
+        self.value = parameter_change.value();
+        self.queued_timestamp = queued_timestamp;
+        self.execution_timestamp = queued_timestamp
+            .checked_add(admin_timelock_delay)
+            .ok_or(ErrorCode::InvalidTimestamp)?;
+        Ok(())
+    }
+
+    // The current delay is applied, so that raising the delay also holds back the changes already queued.
+    pub fn verify_executable(&self, timestamp: u64, admin_timelock_delay: u64) -> Result<()> {
+        let executable_timestamp = self
+            .queued_timestamp
+            .checked_add(admin_timelock_delay)
+            .ok_or(ErrorCode::InvalidTimestamp)?;
+        if timestamp < executable_timestamp {
+            return Err(ErrorCode::QueuedChangeNotExecutable.into());
+        }
+        if timestamp - executable_timestamp > QUEUED_CHANGE_EXECUTION_WINDOW {
+            return Err(ErrorCode::QueuedChangeExpired.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod queued_change_tests {
+    use super::*;
+
+    fn queued_change(parameter_change: ParameterChange, admin_timelock_delay: u64) -> QueuedChange {
+        let mut queued_change = QueuedChange {
+            solves_config: Pubkey::default(),
+            target: Pubkey::default(),
+            parameter_change: ParameterChangeLabel::FeeRate,
+            value: 0,
+            queued_timestamp: 0,
+            execution_timestamp: 0,
+        };
+        queued_change
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                parameter_change,
+                1_000,
+                admin_timelock_delay,
+            )
+            .unwrap();
+        queued_change
+    }
+
+    #[test]
+    fn test_initialize() {
+        let queued_change = queued_change(
+            ParameterChange::DefaultFeeRate {
+                default_fee_rate: 3000,
+            },
+            100,
+        );
+        assert_eq!(
+            queued_change.parameter_change,
+            ParameterChangeLabel::DefaultFeeRate
+        );
+        assert_eq!(queued_change.value, 3000);
+        assert_eq!(queued_change.queued_timestamp, 1_000);
+        assert_eq!(queued_change.execution_timestamp, 1_100);
+    }
+
+    #[test]
+    fn test_initialize_timelock_disabled() {
+        let mut queued_change = queued_change(ParameterChange::FeeRate { fee_rate: 3000 }, 100);
+        assert_eq!(
+            queued_change
+                .initialize(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    ParameterChange::FeeRate { fee_rate: 3000 },
+                    1_000,
+                    0,
+                )
+                .err()
+                .unwrap(),
+            ErrorCode::AdminTimelockDisabled.into()
+        );
+    }
+
+    #[test]
+    fn test_initialize_rate_exceeded() {
+        let mut queued_change = queued_change(ParameterChange::FeeRate { fee_rate: 3000 }, 100);
+        assert_eq!(
+            queued_change
+                .initialize(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    ParameterChange::DefaultFeeRate {
+                        default_fee_rate: MAX_FEE_RATE + 1
+                    },
+                    1_000,
+                    100,
+                )
+                .err()
+                .unwrap(),
+            ErrorCode::FeeRateMaxExceeded.into()
+        );
+        assert_eq!(
+            queued_change
+                .initialize(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    ParameterChange::ProtocolFeeRate {
+                        protocol_fee_rate: MAX_PROTOCOL_FEE_RATE + 1
+                    },
+                    1_000,
+                    100,
+                )
+                .err()
+                .unwrap(),
+            ErrorCode::ProtocolFeeRateMaxExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_initialize_admin_timelock_delay_exceeded() {
+        let mut queued_change = queued_change(
+            ParameterChange::AdminTimelockDelay {
+                admin_timelock_delay: MAX_ADMIN_TIMELOCK_DELAY,
+            },
+            100,
+        );
+        assert_eq!(queued_change.value, MAX_ADMIN_TIMELOCK_DELAY);
+        assert_eq!(
+            queued_change
+                .initialize(
+                    Pubkey::new_unique(),
+                    Pubkey::new_unique(),
+                    ParameterChange::AdminTimelockDelay {
+                        admin_timelock_delay: MAX_ADMIN_TIMELOCK_DELAY + 1
+                    },
+                    1_000,
+                    100,
+                )
+                .err()
+                .unwrap(),
+            ErrorCode::AdminTimelockDelayMaxExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_executable() {
+        let queued_change = queued_change(ParameterChange::FeeRate { fee_rate: 3000 }, 100);
+        assert_eq!(
+            queued_change.verify_executable(1_099, 100).err().unwrap(),
+            ErrorCode::QueuedChangeNotExecutable.into()
+        );
+        assert!(queued_change.verify_executable(1_100, 100).is_ok());
+        assert!(queued_change
+            .verify_executable(1_100 + QUEUED_CHANGE_EXECUTION_WINDOW, 100)
+            .is_ok());
+        assert_eq!(
+            queued_change
+                .verify_executable(1_101 + QUEUED_CHANGE_EXECUTION_WINDOW, 100)
+                .err()
+                .unwrap(),
+            ErrorCode::QueuedChangeExpired.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_executable_delay_raised() {
+        // The delay raised after queueing holds back the queued change
+        let queued_change = queued_change(ParameterChange::FeeRate { fee_rate: 3000 }, 100);
+        assert_eq!(
+            queued_change.verify_executable(1_100, 500).err().unwrap(),
+            ErrorCode::QueuedChangeNotExecutable.into()
+        );
+        assert!(queued_change.verify_executable(1_500, 500).is_ok());
+    }
+}
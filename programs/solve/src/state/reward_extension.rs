@@ -0,0 +1,528 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::math::checked_mul_div;
+
+use super::{
+    LockConfig, LockTypeLabel, PositionRewardInfo, Solve, SolveRewardInfo, Tick, TICK_ARRAY_SIZE,
+    TICK_ARRAY_SIZE_USIZE,
+};
+
+// Number of rewards tracked by the reward extension, in addition to the NUM_REWARDS rewards of Solve.
+pub const NUM_EXTENSION_REWARDS: usize = 3;
+
+// Boosts are expressed in basis points of the liquidity of a position.
+pub const LOCK_BOOST_BPS_DENOMINATOR: u16 = 10_000;
+// A locked position earns boosted rewards with at most 4x its liquidity.
+pub const MAX_LOCK_BOOST_BPS: u16 = 30_000;
+
+// The reward extension is an optional set of accounts that tracks additional rewards for a pool.
+// Solve, Position and Tick have no room for more rewards, so each of them has a companion account:
+// * SolveRewardExtension: the global reward growths of the additional rewards (one per pool)
+// * PositionRewardExtension: the reward checkpoints and owed amounts of a position
+// * TickArrayRewardExtension: the reward growths outside of the ticks of a tick array
+//
+// All growths are measured from the moment the SolveRewardExtension is initialized.
+// At that point every global growth is zero, so zeros are valid reward growths outside for every tick.
+// The reward growths outside of a tick only change when the tick is crossed or initialized, and once
+// the extension is enabled both require the TickArrayRewardExtension of the tick. A tick array extension
+// that is initialized later therefore still holds the right values for its ticks: zeros.
+// Ticks uninitialized by a decrease of liquidity are reset when they are initialized again.
+// Position checkpoints start from zero for the same reason, see initialize_position_reward_extension.
+//
+// Rewards of the reward extension can be boosted for locked positions. A boosted reward is shared
+// by the boosted liquidity, which is the liquidity of the solve plus the liquidity boost of the locked
+// positions in range. The liquidity boost is tracked in the same way as the liquidity:
+// * SolveRewardExtension: the liquidity boost of the positions in range
+// * PositionRewardExtension: the boost of the position, set from its LockConfig
+// * TickArrayRewardExtension: the net liquidity boost of the ticks
+// Only the rewards of the reward extension can be boosted. The rewards of Solve stay shared by the liquidity,
+// as Solve and Tick have no room for the liquidity boost.
+#[account]
+#[derive(Default)]
+pub struct SolveRewardExtension {
+    pub solve: Pubkey,                                          // 32
+    pub reward_last_updated_timestamp: u64,                     // 8
+    pub reward_infos: [SolveRewardInfo; NUM_EXTENSION_REWARDS], // 128 * 3
+    pub liquidity_boost: u128,                                  // 16
+    pub lock_boost_config: LockBoostConfig,                     // 12
+    pub reward_boosted: [bool; NUM_EXTENSION_REWARDS],          // 1 * 3
+                                                                // 97 RESERVE
+}
+
+impl SolveRewardExtension {
+    pub const LEN: usize = 8 + 32 + 8 + 128 * NUM_EXTENSION_REWARDS + 128;
+
+    pub fn initialize(
+        &mut self,
+        solve: &Account<Solve>,
+        reward_last_updated_timestamp: u64,
+    ) -> Result<()> {
+        self.solve = solve.key();
+        self.reward_last_updated_timestamp = reward_last_updated_timestamp;
+        // Additional rewards are managed by the authority of the first reward by default
+        self.reward_infos =
+            [SolveRewardInfo::new(solve.reward_infos[0].authority); NUM_EXTENSION_REWARDS];
+        Ok(())
+    }
+
+    pub fn update_rewards(
+        &mut self,
+        reward_infos: [SolveRewardInfo; NUM_EXTENSION_REWARDS],
+        reward_last_updated_timestamp: u64,
+    ) {
+        self.reward_last_updated_timestamp = reward_last_updated_timestamp;
+        self.reward_infos = reward_infos;
+    }
+
+    pub fn update_reward_authority(&mut self, index: usize, authority: Pubkey) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.reward_infos[index].authority = authority;
+
+        Ok(())
+    }
+
+    pub fn update_emissions(
+        &mut self,
+        index: usize,
+        reward_infos: [SolveRewardInfo; NUM_EXTENSION_REWARDS],
+        timestamp: u64,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.update_rewards(reward_infos, timestamp);
+        self.reward_infos[index].emissions_per_second_x64 = emissions_per_second_x64;
+
+        Ok(())
+    }
+
+    pub fn initialize_reward(&mut self, index: usize, mint: Pubkey, vault: Pubkey) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+
+        let lowest_index = match self.reward_infos.iter().position(|r| !r.initialized()) {
+            Some(lowest_index) => lowest_index,
+            None => return Err(ErrorCode::InvalidRewardIndex.into()),
+        };
+
+        if lowest_index != index {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+
+        self.reward_infos[index].mint = mint;
+        self.reward_infos[index].vault = vault;
+
+        Ok(())
+    }
+
+    pub fn is_boosted(&self) -> bool {
+        self.reward_boosted.contains(&true)
+    }
+
+    pub fn update_liquidity_boost(&mut self, liquidity_boost: u128) {
+        self.liquidity_boost = liquidity_boost;
+    }
+
+    pub fn update_lock_boost_config(&mut self, lock_boost_config: LockBoostConfig) {
+        self.lock_boost_config = lock_boost_config;
+    }
+
+    // Growths of boosted and non boosted rewards are not comparable,
+    // so a reward can only be boosted or not before it has been emitted.
+    pub fn update_reward_boosted(&mut self, index: usize, boosted: bool) -> Result<()> {
+        if index >= NUM_EXTENSION_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        if self.reward_infos[index].growth_global_x64 != 0 {
+            return Err(ErrorCode::RewardBoostNotConfigurable.into());
+        }
+        self.reward_boosted[index] = boosted;
+
+        Ok(())
+    }
+}
+
+// Boost of the liquidity of locked positions, depending on the type of the lock.
+// Timed and vesting locks are boosted in proportion to their remaining locked duration, up to max_boost_duration,
+// so their boost decreases as they approach their unlock timestamp. Vesting locks only boost their unvested liquidity.
+// The boost of a position is a snapshot taken by update_position_boost, which anyone can invoke to refresh it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct LockBoostConfig {
+    pub permanent_boost_bps: u16, // 2
+    pub max_timed_boost_bps: u16, // 2
+    pub max_boost_duration: u64,  // 8
+}
+
+impl LockBoostConfig {
+    pub fn new(
+        permanent_boost_bps: u16,
+        max_timed_boost_bps: u16,
+        max_boost_duration: u64,
+    ) -> Result<Self> {
+        if permanent_boost_bps > MAX_LOCK_BOOST_BPS
+            || max_timed_boost_bps > MAX_LOCK_BOOST_BPS
+            || (max_timed_boost_bps > 0 && max_boost_duration == 0)
+        {
+            return Err(ErrorCode::InvalidLockBoostConfig.into());
+        }
+
+        Ok(Self {
+            permanent_boost_bps,
+            max_timed_boost_bps,
+            max_boost_duration,
+        })
+    }
+
+    // Locks that can be unlocked are not boosted anymore
+    pub fn get_boost_bps(
+        &self,
+        lock_config: &LockConfig,
+        position_liquidity: u128,
+        timestamp: u64,
+    ) -> Result<u16> {
+        if lock_config.is_unlockable(timestamp) {
+            return Ok(0);
+        }
+
+        match lock_config.lock_type {
+            LockTypeLabel::Permanent => Ok(self.permanent_boost_bps),
+            LockTypeLabel::Timed => Ok(self.get_timed_boost_bps(lock_config, timestamp)),
+            LockTypeLabel::Vesting => {
+                if position_liquidity == 0 {
+                    return Ok(0);
+                }
+                // The boost applies to the liquidity of the position,
+                // so it is scaled down to the part that is still locked
+                let unvested_liquidity = lock_config
+                    .get_unvested_liquidity(timestamp)?
+                    .unwrap_or(0)
+                    .min(position_liquidity);
+                // unvested_liquidity <= position_liquidity, so the boost fits in u16
+                Ok(checked_mul_div(
+                    u128::from(self.get_timed_boost_bps(lock_config, timestamp)),
+                    unvested_liquidity,
+                    position_liquidity,
+                )? as u16)
+            }
+        }
+    }
+
+    fn get_timed_boost_bps(&self, lock_config: &LockConfig, timestamp: u64) -> u16 {
+        if self.max_boost_duration == 0 {
+            return 0;
+        }
+        let remaining_lock_duration = lock_config
+            .unlock_timestamp
+            .saturating_sub(timestamp)
+            .min(self.max_boost_duration);
+        // remaining_lock_duration <= max_boost_duration, so the boost fits in u16
+        (u128::from(self.max_timed_boost_bps) * u128::from(remaining_lock_duration)
+            / u128::from(self.max_boost_duration)) as u16
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct PositionRewardExtension {
+    pub position: Pubkey,                                          // 32
+    pub reward_infos: [PositionRewardInfo; NUM_EXTENSION_REWARDS], // 24 * 3
+    pub boost_bps: u16,                                            // 2
+                                                                   // 62 RESERVE
+}
+
+impl PositionRewardExtension {
+    pub const LEN: usize = 8 + 32 + 24 * NUM_EXTENSION_REWARDS + 64;
+
+    pub fn initialize(&mut self, position: Pubkey) {
+        self.position = position;
+    }
+
+    pub fn update_reward_owed(&mut self, index: usize, amount_owed: u64) {
+        self.reward_infos[index].amount_owed = amount_owed;
+    }
+
+    pub fn update_boost_bps(&mut self, boost_bps: u16) {
+        self.boost_bps = boost_bps;
+    }
+}
+
+// Liquidity added to the liquidity of a position by its boost, rounded down
+pub fn liquidity_boost(liquidity: u128, boost_bps: u16) -> Result<u128> {
+    Ok(checked_mul_div(
+        liquidity,
+        u128::from(boost_bps),
+        u128::from(LOCK_BOOST_BPS_DENOMINATOR),
+    )?)
+}
+
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+pub struct TickArrayRewardExtension {
+    pub solve: Pubkey,
+    pub start_tick_index: i32,
+    // Indexed in the same way as the ticks of the tick array starting at start_tick_index
+    pub reward_growths_outside: [[u128; NUM_EXTENSION_REWARDS]; TICK_ARRAY_SIZE_USIZE],
+    pub liquidity_boost_nets: [i128; TICK_ARRAY_SIZE_USIZE],
+}
+
+impl Default for TickArrayRewardExtension {
+    fn default() -> Self {
+        Self {
+            solve: Pubkey::default(),
+            start_tick_index: 0,
+            reward_growths_outside: [[0; NUM_EXTENSION_REWARDS]; TICK_ARRAY_SIZE_USIZE],
+            liquidity_boost_nets: [0; TICK_ARRAY_SIZE_USIZE],
+        }
+    }
+}
+
+impl TickArrayRewardExtension {
+    pub const LEN: usize = 8
+        + 32
+        + 4
+        + 16 * NUM_EXTENSION_REWARDS * TICK_ARRAY_SIZE_USIZE
+        + 16 * TICK_ARRAY_SIZE_USIZE;
+
+    pub fn initialize(&mut self, solve: &Account<Solve>, start_tick_index: i32) -> Result<()> {
+        if !Tick::check_is_valid_start_tick(start_tick_index, solve.tick_spacing) {
+            return Err(ErrorCode::InvalidStartTick.into());
+        }
+
+        self.solve = solve.key();
+        self.start_tick_index = start_tick_index;
+        Ok(())
+    }
+
+    pub fn contains(&self, tick_index: i32, tick_spacing: u16) -> bool {
+        let start_tick_index = self.start_tick_index;
+        tick_index >= start_tick_index
+            && tick_index < start_tick_index + TICK_ARRAY_SIZE * tick_spacing as i32
+    }
+
+    fn offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        if !self.contains(tick_index, tick_spacing) {
+            return Err(ErrorCode::TickNotFound.into());
+        }
+        let start_tick_index = self.start_tick_index;
+        Ok(((tick_index - start_tick_index) / tick_spacing as i32) as usize)
+    }
+
+    pub fn get_reward_growths_outside(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+    ) -> Result<[u128; NUM_EXTENSION_REWARDS]> {
+        let offset = self.offset(tick_index, tick_spacing)?;
+        Ok(self.reward_growths_outside[offset])
+    }
+
+    pub fn update_reward_growths_outside(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        reward_growths_outside: [u128; NUM_EXTENSION_REWARDS],
+    ) -> Result<()> {
+        let offset = self.offset(tick_index, tick_spacing)?;
+        self.reward_growths_outside[offset] = reward_growths_outside;
+        Ok(())
+    }
+
+    pub fn get_liquidity_boost_net(&self, tick_index: i32, tick_spacing: u16) -> Result<i128> {
+        let offset = self.offset(tick_index, tick_spacing)?;
+        Ok(self.liquidity_boost_nets[offset])
+    }
+
+    pub fn update_liquidity_boost_net(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        liquidity_boost_net: i128,
+    ) -> Result<()> {
+        let offset = self.offset(tick_index, tick_spacing)?;
+        self.liquidity_boost_nets[offset] = liquidity_boost_net;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod reward_extension_tests {
+    use super::*;
+    use crate::state::LockType;
+
+    const LOCKED_TIMESTAMP: u64 = 1_000;
+
+    fn new_lock_config(lock_type: LockType) -> LockConfig {
+        let mut lock_config = LockConfig {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            solve: Pubkey::default(),
+            locked_timestamp: 0,
+            lock_type: LockTypeLabel::Permanent,
+            unlock_timestamp: 0,
+            vesting_start_timestamp: 0,
+            locked_liquidity: 0,
+        };
+        lock_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                LOCKED_TIMESTAMP,
+                1_000,
+                lock_type,
+            )
+            .unwrap();
+        lock_config
+    }
+
+    #[test]
+    fn test_lock_boost_config_new() {
+        assert!(LockBoostConfig::new(MAX_LOCK_BOOST_BPS, MAX_LOCK_BOOST_BPS, 100).is_ok());
+        // Timed locks cannot be boosted without a duration
+        assert!(LockBoostConfig::new(10_000, 0, 0).is_ok());
+        for (permanent_boost_bps, max_timed_boost_bps, max_boost_duration) in [
+            (MAX_LOCK_BOOST_BPS + 1, 0, 0),
+            (0, MAX_LOCK_BOOST_BPS + 1, 100),
+            (0, 10_000, 0),
+        ] {
+            assert_eq!(
+                LockBoostConfig::new(permanent_boost_bps, max_timed_boost_bps, max_boost_duration)
+                    .err()
+                    .unwrap(),
+                ErrorCode::InvalidLockBoostConfig.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_boost_bps_permanent() {
+        let lock_boost_config = LockBoostConfig::new(15_000, 10_000, 1_000).unwrap();
+        let lock_config = new_lock_config(LockType::Permanent);
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, u64::MAX)
+                .unwrap(),
+            15_000
+        );
+    }
+
+    #[test]
+    fn test_get_boost_bps_timed() {
+        let lock_boost_config = LockBoostConfig::new(15_000, 10_000, 1_000).unwrap();
+
+        // Boosted in proportion to the remaining lock duration
+        let lock_config = new_lock_config(LockType::Timed {
+            unlock_timestamp: LOCKED_TIMESTAMP + 250,
+        });
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP)
+                .unwrap(),
+            2_500
+        );
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP + 150)
+                .unwrap(),
+            1_000
+        );
+        // Not boosted anymore once it can be unlocked
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP + 250)
+                .unwrap(),
+            0
+        );
+
+        // The remaining duration is capped at max_boost_duration
+        let lock_config = new_lock_config(LockType::Timed {
+            unlock_timestamp: LOCKED_TIMESTAMP + 5_000,
+        });
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP)
+                .unwrap(),
+            10_000
+        );
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP + 4_500)
+                .unwrap(),
+            5_000
+        );
+
+        // Timed locks are not boosted without max_boost_duration
+        let lock_boost_config = LockBoostConfig::new(15_000, 0, 0).unwrap();
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_boost_bps_vesting() {
+        let lock_boost_config = LockBoostConfig::new(15_000, 10_000, 1_000).unwrap();
+
+        // The locked liquidity of the lock config is 1_000
+        let lock_config = new_lock_config(LockType::Vesting {
+            start_timestamp: LOCKED_TIMESTAMP + 100,
+            end_timestamp: LOCKED_TIMESTAMP + 500,
+        });
+        // Before the vesting starts, all the locked liquidity is boosted for the remaining duration
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP)
+                .unwrap(),
+            5_000
+        );
+        // Halfway through the vesting, half of the liquidity is still locked for 200 seconds
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP + 300)
+                .unwrap(),
+            1_000
+        );
+        // The liquidity added on top of the locked liquidity is not boosted
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 2_000, LOCKED_TIMESTAMP)
+                .unwrap(),
+            2_500
+        );
+        // The boost is limited to the liquidity of the position
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 500, LOCKED_TIMESTAMP)
+                .unwrap(),
+            5_000
+        );
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 0, LOCKED_TIMESTAMP)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            lock_boost_config
+                .get_boost_bps(&lock_config, 1_000, LOCKED_TIMESTAMP + 500)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_liquidity_boost() {
+        assert_eq!(liquidity_boost(1_000, 0).unwrap(), 0);
+        assert_eq!(liquidity_boost(1_000, 2_500).unwrap(), 250);
+        assert_eq!(liquidity_boost(1_000, MAX_LOCK_BOOST_BPS).unwrap(), 3_000);
+        // rounded down
+        assert_eq!(liquidity_boost(3, 5_000).unwrap(), 1);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{
     errors::ErrorCode,
     math::{
-        tick_index_from_sqrt_price, MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64,
-        MIN_SQRT_PRICE_X64,
+        checked_mul_shift_right_round_up_if, tick_index_from_sqrt_price, MAX_FEE_RATE,
+        MAX_PROTOCOL_FEE_RATE, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
     },
 };
 use anchor_lang::prelude::*;
@@ -209,6 +209,25 @@ impl Solve {
         Ok(())
     }
 
+    /// Returns the amount of each reward emitted to the liquidity of the Solve if its rewards
+    /// were updated to `next_reward_infos`. The amounts are rounded up, so that they are never
+    /// less than the total amount owed to positions for the update.
+    pub fn reward_emitted_amounts(
+        &self,
+        next_reward_infos: &[SolveRewardInfo; NUM_REWARDS],
+    ) -> [u64; NUM_REWARDS] {
+        let mut emitted_amounts = [0u64; NUM_REWARDS];
+        for (i, emitted_amount) in emitted_amounts.iter_mut().enumerate() {
+            let reward_growth_delta = next_reward_infos[i]
+                .growth_global_x64
+                .wrapping_sub(self.reward_infos[i].growth_global_x64);
+            *emitted_amount =
+                checked_mul_shift_right_round_up_if(self.liquidity, reward_growth_delta, true)
+                    .unwrap_or(u64::MAX);
+        }
+        emitted_amounts
+    }
+
     pub fn initialize_reward(&mut self, index: usize, mint: Pubkey, vault: Pubkey) -> Result<()> {
         if index >= NUM_REWARDS {
             return Err(ErrorCode::InvalidRewardIndex.into());
@@ -257,6 +276,19 @@ impl Solve {
         }
     }
 
+    pub fn update_after_flash_loan(
+        &mut self,
+        fee_growth_global_a: u128,
+        fee_growth_global_b: u128,
+        protocol_fee_a: u64,
+        protocol_fee_b: u64,
+    ) {
+        self.fee_growth_global_a = fee_growth_global_a;
+        self.fee_growth_global_b = fee_growth_global_b;
+        self.protocol_fee_owed_a = self.protocol_fee_owed_a.wrapping_add(protocol_fee_a);
+        self.protocol_fee_owed_b = self.protocol_fee_owed_b.wrapping_add(protocol_fee_b);
+    }
+
     pub fn update_fee_rate(&mut self, fee_rate: u16) -> Result<()> {
         if fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
@@ -0,0 +1,639 @@
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+use crate::errors::ErrorCode;
+
+use super::{validate_pause_flags, Solve, SolveRewardInfo, NUM_REWARDS};
+
+// Rewards without an end timestamp keep emitting, so this many seconds of their future emissions
+// are kept in the reward vault by withdraw_unallocated_reward.
+pub const UNSCHEDULED_REWARD_RESERVE_DURATION: u64 = 60 * 60 * 24;
+
+// Emission window of a reward of the Solve, indexed in the same way as Solve.reward_infos.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RewardSchedule {
+    // Rewards are not emitted before this timestamp (0 means no start)
+    pub start_timestamp: u64,
+    // Rewards are not emitted after this timestamp (0 means no end)
+    pub end_timestamp: u64,
+}
+
+impl RewardSchedule {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn new(start_timestamp: u64, end_timestamp: u64) -> Result<Self> {
+        if end_timestamp <= start_timestamp {
+            return Err(ErrorCode::InvalidRewardSchedule.into());
+        }
+        Ok(Self {
+            start_timestamp,
+            end_timestamp,
+        })
+    }
+
+    // Returns the number of seconds between curr_timestamp and next_timestamp during which the reward is emitted
+    pub fn emission_duration(&self, curr_timestamp: u64, next_timestamp: u64) -> u64 {
+        let start_timestamp = curr_timestamp.max(self.start_timestamp);
+        let end_timestamp = match self.end_timestamp {
+            0 => next_timestamp,
+            end_timestamp => next_timestamp.min(end_timestamp),
+        };
+        end_timestamp.saturating_sub(start_timestamp)
+    }
+
+    // Returns the number of seconds after timestamp during which the emissions are reserved in the vault
+    pub fn reserved_emission_duration(&self, timestamp: u64) -> u64 {
+        let end_timestamp = match self.end_timestamp {
+            0 => timestamp.saturating_add(UNSCHEDULED_REWARD_RESERVE_DURATION),
+            end_timestamp => end_timestamp,
+        };
+        self.emission_duration(timestamp, end_timestamp)
+    }
+}
+
+// Accounting of a reward of the Solve, indexed in the same way as Solve.reward_infos.
+// Used to find the reward tokens in the vault that are not owed to positions.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RewardAccounting {
+    // Whether all the emissions of the reward are accounted for.
+    // Emissions before the tail was allocated are unknown.
+    pub tracked: bool,
+    // Cumulative amount of rewards emitted to positions (rounded up)
+    pub emitted_amount: u64,
+    // Cumulative amount of rewards collected by positions
+    pub collected_amount: u64,
+}
+
+impl RewardAccounting {
+    pub const LEN: usize = 1 + 8 + 8;
+
+    // Upper bound of the rewards owed to positions that have not been collected yet
+    pub fn uncollected_amount(&self) -> u64 {
+        self.emitted_amount.saturating_sub(self.collected_amount)
+    }
+
+    pub fn record_emission(&mut self, amount: u64) {
+        if self.tracked {
+            self.emitted_amount = self.emitted_amount.saturating_add(amount);
+        }
+    }
+
+    pub fn record_collection(&mut self, amount: u64) {
+        if self.tracked {
+            self.collected_amount = self.collected_amount.saturating_add(amount);
+        }
+    }
+}
+
+// Per-pool data appended to the Solve account, right after Solve::LEN bytes.
+//
+// SolveRewardInfo and Solve have no spare bytes, and every instruction that accrues rewards takes
+// the Solve account but not necessarily any other account of the pool. Keeping this data in the
+// Solve account itself makes it visible to all of them without changing their account lists.
+//
+// The tail is allocated on demand (see SolveTail::allocate), the funder paying for the rent.
+// Until then, the Solve account has exactly Solve::LEN bytes and the default tail applies.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolveTail {
+    pub reward_schedules: [RewardSchedule; NUM_REWARDS], // 16 * 3
+    pub reward_accountings: [RewardAccounting; NUM_REWARDS], // 17 * 3
+    // Whether the pool tracks additional rewards in a SolveRewardExtension
+    pub reward_extension_enabled: bool, // 1
+    // Pause flags applied to this pool only
+    pub pause_flags: u8, // 1
+    // Copy of the pause flags of the config, synced by sync_pool_pause_flags
+    pub config_pause_flags: u8, // 1
+    // Set by flash_swap_v2 while the callback program is invoked
+    pub locked: bool,        // 1
+    pub reserved: [u8; 153], // 153
+}
+
+impl Default for SolveTail {
+    fn default() -> Self {
+        Self {
+            reward_schedules: [RewardSchedule::default(); NUM_REWARDS],
+            reward_accountings: [RewardAccounting::default(); NUM_REWARDS],
+            reward_extension_enabled: false,
+            pause_flags: 0,
+            config_pause_flags: 0,
+            locked: false,
+            reserved: [0u8; 153],
+        }
+    }
+}
+
+impl SolveTail {
+    pub const LEN: usize = RewardSchedule::LEN * NUM_REWARDS
+        + RewardAccounting::LEN * NUM_REWARDS
+        + 1
+        + 1
+        + 1
+        + 1
+        + 153;
+
+    // A reward is tracked from the start only if nothing has been emitted to positions yet
+    pub fn new(reward_infos: &[SolveRewardInfo; NUM_REWARDS]) -> Self {
+        Self {
+            reward_accountings: reward_infos.map(|reward_info| RewardAccounting {
+                tracked: reward_info.growth_global_x64 == 0,
+                emitted_amount: 0,
+                collected_amount: 0,
+            }),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_allocated(solve_account_info: &AccountInfo) -> bool {
+        solve_account_info.data_len() >= Solve::LEN + Self::LEN
+    }
+
+    // Returns the default tail if it has not been allocated yet.
+    pub fn load(solve_account_info: &AccountInfo) -> Result<Self> {
+        if !Self::is_allocated(solve_account_info) {
+            return Ok(Self::default());
+        }
+
+        let data = solve_account_info.try_borrow_data()?;
+        Ok(Self::deserialize(
+            &mut &data[Solve::LEN..Solve::LEN + Self::LEN],
+        )?)
+    }
+
+    // The tail must have been allocated, and the Solve account must be writable.
+    pub fn store(&self, solve_account_info: &AccountInfo) -> Result<()> {
+        if !solve_account_info.is_writable {
+            return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+        }
+        if !Self::is_allocated(solve_account_info) {
+            return Err(ErrorCode::SolveTailNotAllocated.into());
+        }
+
+        let mut data = solve_account_info.try_borrow_mut_data()?;
+        self.serialize(&mut &mut data[Solve::LEN..Solve::LEN + Self::LEN])?;
+        Ok(())
+    }
+
+    // Grows the Solve account to hold the tail. No-op if the tail is already allocated.
+    pub fn allocate<'info>(
+        solve: &Account<'info, Solve>,
+        funder: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let solve_account_info = solve.to_account_info();
+        if Self::is_allocated(&solve_account_info) {
+            return Ok(());
+        }
+
+        let required_size = Solve::LEN + Self::LEN;
+
+        // Rent for the tail is paid by the funder
+        let required_lamports = Rent::get()?
+            .minimum_balance(required_size)
+            .saturating_sub(solve_account_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    funder.key,
+                    solve_account_info.key,
+                    required_lamports,
+                ),
+                &[
+                    funder.clone(),
+                    solve_account_info.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+
+        solve_account_info.realloc(required_size, true)?;
+        Self::new(&solve.reward_infos).store(&solve_account_info)
+    }
+
+    pub fn record_reward_emissions(&mut self, emitted_amounts: &[u64; NUM_REWARDS]) {
+        for (reward_accounting, emitted_amount) in
+            self.reward_accountings.iter_mut().zip(emitted_amounts)
+        {
+            reward_accounting.record_emission(*emitted_amount);
+        }
+    }
+
+    pub fn record_reward_collection(&mut self, index: usize, collected_amount: u64) {
+        self.reward_accountings[index].record_collection(collected_amount);
+    }
+
+    pub fn update_pause_flags(&mut self, pause_flags: u8) -> Result<()> {
+        validate_pause_flags(pause_flags)?;
+        self.pause_flags = pause_flags;
+        Ok(())
+    }
+
+    // Instructions that modify the state of the pool must not run while a flash swap callback is in progress.
+    // Authority instructions and collections of owed amounts are not locked, the flash swap reloads the
+    // Solve after the callback so their changes are kept.
+    pub fn verify_not_locked(&self) -> Result<()> {
+        if self.locked {
+            return Err(ErrorCode::SolveLocked.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_config_pause_flags(&mut self, config_pause_flags: u8) -> Result<()> {
+        validate_pause_flags(config_pause_flags)?;
+        self.config_pause_flags = config_pause_flags;
+        Ok(())
+    }
+
+    // Must be called before the rewards of the solve are updated to next_reward_infos.
+    // If no tracked reward is emitted, the tail is left untouched.
+    pub fn track_reward_emissions(
+        solve: &Account<Solve>,
+        next_reward_infos: &[SolveRewardInfo; NUM_REWARDS],
+    ) -> Result<()> {
+        let solve_account_info = solve.to_account_info();
+        if !Self::is_allocated(&solve_account_info) {
+            return Ok(());
+        }
+
+        let emitted_amounts = solve.reward_emitted_amounts(next_reward_infos);
+        let mut solve_tail = Self::load(&solve_account_info)?;
+        if !solve_tail
+            .reward_accountings
+            .iter()
+            .zip(emitted_amounts)
+            .any(|(reward_accounting, emitted_amount)| {
+                reward_accounting.tracked && emitted_amount > 0
+            })
+        {
+            return Ok(());
+        }
+
+        solve_tail.record_reward_emissions(&emitted_amounts);
+        solve_tail.store(&solve_account_info)
+    }
+
+    pub fn track_reward_collection(
+        solve: &Account<Solve>,
+        index: usize,
+        collected_amount: u64,
+    ) -> Result<()> {
+        let solve_account_info = solve.to_account_info();
+        if !Self::is_allocated(&solve_account_info) || collected_amount == 0 {
+            return Ok(());
+        }
+
+        let mut solve_tail = Self::load(&solve_account_info)?;
+        if !solve_tail.reward_accountings[index].tracked {
+            return Ok(());
+        }
+
+        solve_tail.record_reward_collection(index, collected_amount);
+        solve_tail.store(&solve_account_info)
+    }
+}
+
+#[cfg(test)]
+mod solve_tail_tests {
+    use super::*;
+    use crate::manager::solve_manager::next_solve_reward_infos;
+    use crate::state::{
+        PausableOperation, PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS, PAUSE_FLAG_INCREASE_LIQUIDITY,
+        PAUSE_FLAG_SWAP,
+    };
+    use crate::util::{verify_pool_not_locked, verify_pool_not_paused};
+    use anchor_lang::Discriminator;
+
+    struct SolveAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl SolveAccount {
+        fn new(data_len: usize) -> Self {
+            Self {
+                key: Pubkey::new_unique(),
+                owner: crate::ID,
+                lamports: 0,
+                data: vec![0u8; data_len],
+            }
+        }
+
+        fn with_solve(solve: &Solve, tail: Option<&SolveTail>) -> Self {
+            let mut account = Self::new(0);
+            account.data.extend_from_slice(&Solve::DISCRIMINATOR);
+            solve.serialize(&mut account.data).unwrap();
+            if let Some(tail) = tail {
+                tail.serialize(&mut account.data).unwrap();
+            }
+            account
+        }
+
+        fn account_info(&mut self, is_writable: bool) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    #[test]
+    fn test_load_unallocated_tail() {
+        let mut solve = SolveAccount::new(Solve::LEN);
+        let account_info = solve.account_info(true);
+
+        assert!(!SolveTail::is_allocated(&account_info));
+        assert_eq!(
+            SolveTail::load(&account_info).unwrap(),
+            SolveTail::default()
+        );
+        assert!(SolveTail::default().store(&account_info).is_err());
+    }
+
+    #[test]
+    fn test_store_and_load_tail() {
+        let mut solve = SolveAccount::new(Solve::LEN + SolveTail::LEN);
+        let account_info = solve.account_info(true);
+
+        let mut tail = SolveTail::load(&account_info).unwrap();
+        tail.reward_schedules[1] = RewardSchedule::new(100, 200).unwrap();
+        tail.store(&account_info).unwrap();
+
+        let tail = SolveTail::load(&account_info).unwrap();
+        assert_eq!(tail.reward_schedules[0], RewardSchedule::default());
+        assert_eq!(
+            tail.reward_schedules[1],
+            RewardSchedule::new(100, 200).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_store_tail_requires_writable_solve() {
+        let mut solve = SolveAccount::new(Solve::LEN + SolveTail::LEN);
+        let account_info = solve.account_info(false);
+
+        assert!(SolveTail::default().store(&account_info).is_err());
+    }
+
+    #[test]
+    fn test_pool_pause_flags() {
+        let mut solve = SolveAccount::with_solve(&Solve::default(), None);
+        let account_info = solve.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+
+        // Pools without a tail are not paused
+        assert!(verify_pool_not_paused(&solve_account, PausableOperation::Swap).is_ok());
+
+        let mut tail = SolveTail::default();
+        tail.update_pause_flags(PAUSE_FLAG_SWAP).unwrap();
+        let mut solve = SolveAccount::with_solve(&Solve::default(), Some(&tail));
+        let account_info = solve.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+        assert_eq!(
+            verify_pool_not_paused(&solve_account, PausableOperation::Swap)
+                .err()
+                .unwrap(),
+            ErrorCode::SwapPaused.into()
+        );
+        assert!(verify_pool_not_paused(&solve_account, PausableOperation::Other).is_ok());
+
+        assert_eq!(
+            tail.update_pause_flags(1 << 7).err().unwrap(),
+            ErrorCode::InvalidPauseFlags.into()
+        );
+    }
+
+    fn verify_paused_by_synced_config_flags(
+        config_pause_flags: u8,
+        operation: PausableOperation,
+    ) -> Result<()> {
+        let mut tail = SolveTail::default();
+        tail.update_config_pause_flags(config_pause_flags).unwrap();
+        let mut solve = SolveAccount::with_solve(&Solve::default(), Some(&tail));
+        let account_info = solve.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+        verify_pool_not_paused(&solve_account, operation)
+    }
+
+    #[test]
+    fn test_synced_config_pause_flags_swap() {
+        assert_eq!(
+            verify_paused_by_synced_config_flags(PAUSE_FLAG_SWAP, PausableOperation::Swap)
+                .err()
+                .unwrap(),
+            ErrorCode::SwapPaused.into()
+        );
+        assert!(verify_paused_by_synced_config_flags(
+            PAUSE_FLAG_SWAP,
+            PausableOperation::IncreaseLiquidity
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_synced_config_pause_flags_increase_liquidity() {
+        assert_eq!(
+            verify_paused_by_synced_config_flags(
+                PAUSE_FLAG_INCREASE_LIQUIDITY,
+                PausableOperation::IncreaseLiquidity
+            )
+            .err()
+            .unwrap(),
+            ErrorCode::IncreaseLiquidityPaused.into()
+        );
+        assert!(verify_paused_by_synced_config_flags(
+            PAUSE_FLAG_INCREASE_LIQUIDITY,
+            PausableOperation::Swap
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_synced_config_pause_flags_all_except_withdrawals() {
+        for operation in [
+            PausableOperation::Swap,
+            PausableOperation::IncreaseLiquidity,
+            PausableOperation::Other,
+        ] {
+            assert_eq!(
+                verify_paused_by_synced_config_flags(PAUSE_FLAG_ALL_EXCEPT_WITHDRAWALS, operation)
+                    .err()
+                    .unwrap(),
+                ErrorCode::OperationPaused.into()
+            );
+        }
+        assert!(verify_paused_by_synced_config_flags(0, PausableOperation::Other).is_ok());
+    }
+
+    #[test]
+    fn test_locked_pool() {
+        let tail = SolveTail {
+            locked: true,
+            ..SolveTail::default()
+        };
+        let mut solve = SolveAccount::with_solve(&Solve::default(), Some(&tail));
+        let account_info = solve.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+
+        // Withdrawals are not paused, but they are locked
+        assert_eq!(
+            verify_pool_not_locked(&solve_account).err().unwrap(),
+            ErrorCode::SolveLocked.into()
+        );
+        for operation in [
+            PausableOperation::Swap,
+            PausableOperation::IncreaseLiquidity,
+            PausableOperation::Other,
+        ] {
+            assert_eq!(
+                verify_pool_not_paused(&solve_account, operation)
+                    .err()
+                    .unwrap(),
+                ErrorCode::SolveLocked.into()
+            );
+        }
+
+        let mut solve = SolveAccount::with_solve(&Solve::default(), None);
+        let account_info = solve.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+        assert!(verify_pool_not_locked(&solve_account).is_ok());
+    }
+
+    fn solve_with_rewards() -> Solve {
+        let mut solve = Solve {
+            liquidity: 1_000_000,
+            ..Default::default()
+        };
+        // 1 token per second for reward 0, reward 1 has emitted before the tail was allocated
+        solve.reward_infos[0].mint = Pubkey::new_unique();
+        solve.reward_infos[0].emissions_per_second_x64 = 1 << 64;
+        solve.reward_infos[1].mint = Pubkey::new_unique();
+        solve.reward_infos[1].emissions_per_second_x64 = 1 << 64;
+        solve.reward_infos[1].growth_global_x64 = 1 << 40;
+        solve
+    }
+
+    #[test]
+    fn test_new_tracks_rewards_without_emissions() {
+        let tail = SolveTail::new(&solve_with_rewards().reward_infos);
+        assert!(tail.reward_accountings[0].tracked);
+        assert!(!tail.reward_accountings[1].tracked);
+        // Rewards that are not initialized yet are tracked from the start
+        assert!(tail.reward_accountings[2].tracked);
+    }
+
+    #[test]
+    fn test_record_reward_emissions_and_collections() {
+        let mut tail = SolveTail::new(&solve_with_rewards().reward_infos);
+        tail.record_reward_emissions(&[100, 100, 0]);
+        tail.record_reward_collection(0, 40);
+        tail.record_reward_collection(1, 40);
+
+        assert_eq!(tail.reward_accountings[0].emitted_amount, 100);
+        assert_eq!(tail.reward_accountings[0].uncollected_amount(), 60);
+        assert_eq!(tail.reward_accountings[1].emitted_amount, 0);
+        assert_eq!(tail.reward_accountings[1].collected_amount, 0);
+
+        // Collections never make the uncollected amount wrap around
+        tail.record_reward_collection(0, 100);
+        assert_eq!(tail.reward_accountings[0].uncollected_amount(), 0);
+    }
+
+    #[test]
+    fn test_track_reward_emissions_on_liquidity_decrease() {
+        let solve = solve_with_rewards();
+        let tail = SolveTail::new(&solve.reward_infos);
+        let mut account = SolveAccount::with_solve(&solve, Some(&tail));
+        let account_info = account.account_info(true);
+        let mut solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+
+        // A decrease accrues the rewards of the solve before its liquidity is modified
+        let next_reward_infos =
+            next_solve_reward_infos(&solve_account, &tail.reward_schedules, 100).unwrap();
+        SolveTail::track_reward_emissions(&solve_account, &next_reward_infos).unwrap();
+        solve_account.update_rewards_and_liquidity(next_reward_infos, 500_000, 100);
+
+        let tail = SolveTail::load(&account_info).unwrap();
+        assert_eq!(tail.reward_accountings[0].emitted_amount, 100);
+        assert_eq!(tail.reward_accountings[1].emitted_amount, 0);
+
+        SolveTail::track_reward_collection(&solve_account, 0, 30).unwrap();
+        let tail = SolveTail::load(&account_info).unwrap();
+        assert_eq!(tail.reward_accountings[0].uncollected_amount(), 70);
+    }
+
+    #[test]
+    fn test_track_rewards_without_tail() {
+        let solve = solve_with_rewards();
+        let mut account = SolveAccount::with_solve(&solve, None);
+        let account_info = account.account_info(true);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+
+        let next_reward_infos =
+            next_solve_reward_infos(&solve_account, &[RewardSchedule::default(); 3], 100).unwrap();
+        SolveTail::track_reward_emissions(&solve_account, &next_reward_infos).unwrap();
+        SolveTail::track_reward_collection(&solve_account, 0, 30).unwrap();
+        assert_eq!(account_info.data_len(), Solve::LEN);
+    }
+
+    #[test]
+    fn test_track_reward_collection_requires_writable_solve() {
+        let solve = solve_with_rewards();
+        let mut tail = SolveTail::new(&solve.reward_infos);
+        tail.record_reward_emissions(&[100, 0, 0]);
+        let mut account = SolveAccount::with_solve(&solve, Some(&tail));
+        let account_info = account.account_info(false);
+        let solve_account: Account<Solve> = Account::try_from(&account_info).unwrap();
+
+        assert_eq!(
+            SolveTail::track_reward_collection(&solve_account, 0, 30)
+                .err()
+                .unwrap(),
+            anchor_lang::error::ErrorCode::AccountNotMutable.into()
+        );
+        let tail = SolveTail::load(&account_info).unwrap();
+        assert_eq!(tail.reward_accountings[0].uncollected_amount(), 100);
+    }
+
+    #[test]
+    fn test_emission_duration() {
+        let schedule = RewardSchedule::new(100, 200).unwrap();
+        assert_eq!(schedule.emission_duration(0, 50), 0);
+        assert_eq!(schedule.emission_duration(50, 150), 50);
+        assert_eq!(schedule.emission_duration(120, 180), 60);
+        assert_eq!(schedule.emission_duration(150, 300), 50);
+        assert_eq!(schedule.emission_duration(250, 300), 0);
+
+        let unbounded = RewardSchedule::default();
+        assert_eq!(unbounded.emission_duration(250, 300), 50);
+        assert!(RewardSchedule::new(200, 200).is_err());
+    }
+
+    #[test]
+    fn test_reserved_emission_duration() {
+        let schedule = RewardSchedule::new(100, 200).unwrap();
+        assert_eq!(schedule.reserved_emission_duration(50), 100);
+        assert_eq!(schedule.reserved_emission_duration(150), 50);
+        assert_eq!(schedule.reserved_emission_duration(250), 0);
+
+        let unbounded = RewardSchedule::default();
+        assert_eq!(
+            unbounded.reserved_emission_duration(250),
+            UNSCHEDULED_REWARD_RESERVE_DURATION
+        );
+        let not_started = RewardSchedule {
+            start_timestamp: 1_000,
+            end_timestamp: 0,
+        };
+        assert_eq!(
+            not_started.reserved_emission_duration(0),
+            UNSCHEDULED_REWARD_RESERVE_DURATION - 1_000
+        );
+    }
+}
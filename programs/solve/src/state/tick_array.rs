@@ -38,6 +38,9 @@ pub trait TickArrayType {
         update: &TickUpdate,
     ) -> Result<()>;
 
+    /// Checks if any tick in this array is initialized. An array without initialized ticks can be closed.
+    fn has_initialized_ticks(&self) -> bool;
+
     /// Checks that this array holds the next tick index for the current tick index, given the pool's tick spacing & search direction.
     ///
     /// unshifted checks on [start, start + TICK_ARRAY_SIZE * tick_spacing)
@@ -70,4 +70,8 @@ impl TickArrayType for ZeroedTickArray {
     ) -> Result<()> {
         panic!("ZeroedTickArray must not be updated");
     }
+
+    fn has_initialized_ticks(&self) -> bool {
+        false
+    }
 }
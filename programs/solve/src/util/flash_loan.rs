@@ -0,0 +1,345 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
+
+// solve is the first account of both flash_borrow and flash_repay
+const FLASH_LOAN_SOLVE_ACCOUNT_INDEX: usize = 0;
+
+/// Verify that a flash_repay instruction for the same solve and amounts follows the current
+/// flash_borrow instruction in this transaction, with no other flash_borrow for the solve in between.
+pub fn verify_flash_repay_follows(
+    instructions_sysvar: &AccountInfo<'_>,
+    solve: &Pubkey,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    let current_index = load_current_top_level_index(instructions_sysvar)?;
+
+    let mut index = current_index + 1;
+    // load_instruction_at_checked fails once index is out of range
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if is_flash_loan_instruction(
+            &instruction,
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            solve,
+        ) {
+            return Err(ErrorCode::FlashRepayNotFound.into());
+        }
+        if is_flash_loan_instruction(
+            &instruction,
+            crate::instruction::FlashRepay::DISCRIMINATOR,
+            solve,
+        ) {
+            return verify_flash_loan_amounts(&instruction, amount_a, amount_b);
+        }
+        index += 1;
+    }
+
+    Err(ErrorCode::FlashRepayNotFound.into())
+}
+
+/// Verify that a flash_borrow instruction for the same solve and amounts precedes the current
+/// flash_repay instruction in this transaction, with no other flash_repay for the solve in between.
+pub fn verify_flash_borrow_precedes(
+    instructions_sysvar: &AccountInfo<'_>,
+    solve: &Pubkey,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    let current_index = load_current_top_level_index(instructions_sysvar)?;
+
+    for index in (0..current_index).rev() {
+        let instruction = load_instruction_at_checked(index, instructions_sysvar)?;
+        if is_flash_loan_instruction(
+            &instruction,
+            crate::instruction::FlashRepay::DISCRIMINATOR,
+            solve,
+        ) {
+            return Err(ErrorCode::FlashBorrowNotFound.into());
+        }
+        if is_flash_loan_instruction(
+            &instruction,
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            solve,
+        ) {
+            return verify_flash_loan_amounts(&instruction, amount_a, amount_b);
+        }
+    }
+
+    Err(ErrorCode::FlashBorrowNotFound.into())
+}
+
+fn load_current_top_level_index(instructions_sysvar: &AccountInfo<'_>) -> Result<usize> {
+    // The instructions sysvar only contains top-level instructions,
+    // so the pairing cannot be verified for an instruction invoked via CPI.
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Err(ErrorCode::FlashLoanCpiNotAllowed.into());
+    }
+    Ok(load_current_index_checked(instructions_sysvar)? as usize)
+}
+
+fn is_flash_loan_instruction(
+    instruction: &Instruction,
+    discriminator: [u8; 8],
+    solve: &Pubkey,
+) -> bool {
+    instruction.program_id == crate::ID
+        && instruction.data.len() >= 8
+        && instruction.data[..8] == discriminator
+        && instruction
+            .accounts
+            .get(FLASH_LOAN_SOLVE_ACCOUNT_INDEX)
+            .is_some_and(|meta| meta.pubkey == *solve)
+}
+
+fn verify_flash_loan_amounts(
+    instruction: &Instruction,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    // both flash_borrow and flash_repay start with amount_a and amount_b
+    let mut data = &instruction.data[8..];
+    let paired_amount_a = u64::deserialize(&mut data)?;
+    let paired_amount_b = u64::deserialize(&mut data)?;
+    if paired_amount_a != amount_a || paired_amount_b != amount_b {
+        return Err(ErrorCode::FlashLoanAmountMismatch.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod flash_loan_tests {
+    use super::*;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_lang::solana_program::sysvar;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedAccountMeta, BorrowedInstruction,
+    };
+    use std::cell::Cell;
+    use std::sync::Once;
+
+    thread_local! {
+        static STACK_HEIGHT: Cell<u64> = const { Cell::new(TRANSACTION_LEVEL_STACK_HEIGHT as u64) };
+    }
+
+    struct StackHeightStubs;
+
+    impl SyscallStubs for StackHeightStubs {
+        fn sol_get_stack_height(&self) -> u64 {
+            STACK_HEIGHT.with(|stack_height| stack_height.get())
+        }
+    }
+
+    static SET_STUBS: Once = Once::new();
+
+    struct TestInstruction {
+        program_id: Pubkey,
+        solve: Pubkey,
+        data: Vec<u8>,
+    }
+
+    fn borrow(solve: Pubkey, amount_a: u64, amount_b: u64) -> TestInstruction {
+        flash_loan_instruction(
+            crate::instruction::FlashBorrow::DISCRIMINATOR,
+            solve,
+            amount_a,
+            amount_b,
+        )
+    }
+
+    fn repay(solve: Pubkey, amount_a: u64, amount_b: u64) -> TestInstruction {
+        flash_loan_instruction(
+            crate::instruction::FlashRepay::DISCRIMINATOR,
+            solve,
+            amount_a,
+            amount_b,
+        )
+    }
+
+    fn flash_loan_instruction(
+        discriminator: [u8; 8],
+        solve: Pubkey,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> TestInstruction {
+        let mut data = discriminator.to_vec();
+        data.extend_from_slice(&amount_a.to_le_bytes());
+        data.extend_from_slice(&amount_b.to_le_bytes());
+        // remaining_accounts_info: None
+        data.push(0);
+        TestInstruction {
+            program_id: crate::ID,
+            solve,
+            data,
+        }
+    }
+
+    fn instructions_sysvar_data(instructions: &[TestInstruction], current_index: u16) -> Vec<u8> {
+        let borrowed_instructions: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|instruction| BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: vec![BorrowedAccountMeta {
+                    pubkey: &instruction.solve,
+                    is_signer: false,
+                    is_writable: true,
+                }],
+                data: &instruction.data,
+            })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed_instructions);
+        store_current_index(&mut data, current_index);
+        data
+    }
+
+    fn with_instructions_sysvar<R>(
+        instructions: &[TestInstruction],
+        current_index: u16,
+        stack_height: usize,
+        f: impl FnOnce(&AccountInfo) -> R,
+    ) -> R {
+        SET_STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(StackHeightStubs));
+        });
+        STACK_HEIGHT.with(|height| height.set(stack_height as u64));
+
+        let key = sysvar::instructions::ID;
+        let owner = sysvar::ID;
+        let mut lamports = 0;
+        let mut data = instructions_sysvar_data(instructions, current_index);
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        f(&account_info)
+    }
+
+    #[test]
+    fn test_paired_borrow_and_repay() {
+        let solve = Pubkey::new_unique();
+        let instructions = [borrow(solve, 100, 200), repay(solve, 100, 200)];
+        with_instructions_sysvar(&instructions, 0, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert!(verify_flash_repay_follows(sysvar, &solve, 100, 200).is_ok());
+        });
+        with_instructions_sysvar(&instructions, 1, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert!(verify_flash_borrow_precedes(sysvar, &solve, 100, 200).is_ok());
+        });
+    }
+
+    #[test]
+    fn test_unpaired_borrow() {
+        let solve = Pubkey::new_unique();
+        // A repay for another solve does not pair with the borrow
+        let instructions = [
+            borrow(solve, 100, 200),
+            repay(Pubkey::new_unique(), 100, 200),
+        ];
+        with_instructions_sysvar(&instructions, 0, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert_eq!(
+                verify_flash_repay_follows(sysvar, &solve, 100, 200)
+                    .err()
+                    .unwrap(),
+                ErrorCode::FlashRepayNotFound.into()
+            );
+        });
+    }
+
+    #[test]
+    fn test_unpaired_repay() {
+        let solve = Pubkey::new_unique();
+        let instructions = [repay(solve, 100, 200)];
+        with_instructions_sysvar(&instructions, 0, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert_eq!(
+                verify_flash_borrow_precedes(sysvar, &solve, 100, 200)
+                    .err()
+                    .unwrap(),
+                ErrorCode::FlashBorrowNotFound.into()
+            );
+        });
+    }
+
+    #[test]
+    fn test_second_borrow_before_repay() {
+        // The repay can only pair with one borrow
+        let solve = Pubkey::new_unique();
+        let instructions = [
+            borrow(solve, 100, 200),
+            borrow(solve, 100, 200),
+            repay(solve, 100, 200),
+        ];
+        with_instructions_sysvar(&instructions, 0, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert_eq!(
+                verify_flash_repay_follows(sysvar, &solve, 100, 200)
+                    .err()
+                    .unwrap(),
+                ErrorCode::FlashRepayNotFound.into()
+            );
+        });
+    }
+
+    #[test]
+    fn test_short_repay() {
+        let solve = Pubkey::new_unique();
+        let instructions = [borrow(solve, 100, 200), repay(solve, 100, 199)];
+        with_instructions_sysvar(&instructions, 0, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert_eq!(
+                verify_flash_repay_follows(sysvar, &solve, 100, 200)
+                    .err()
+                    .unwrap(),
+                ErrorCode::FlashLoanAmountMismatch.into()
+            );
+        });
+        with_instructions_sysvar(&instructions, 1, TRANSACTION_LEVEL_STACK_HEIGHT, |sysvar| {
+            assert_eq!(
+                verify_flash_borrow_precedes(sysvar, &solve, 100, 199)
+                    .err()
+                    .unwrap(),
+                ErrorCode::FlashLoanAmountMismatch.into()
+            );
+        });
+    }
+
+    #[test]
+    fn test_cpi_not_allowed() {
+        let solve = Pubkey::new_unique();
+        let instructions = [borrow(solve, 100, 200), repay(solve, 100, 200)];
+        with_instructions_sysvar(
+            &instructions,
+            0,
+            TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+            |sysvar| {
+                assert_eq!(
+                    verify_flash_repay_follows(sysvar, &solve, 100, 200)
+                        .err()
+                        .unwrap(),
+                    ErrorCode::FlashLoanCpiNotAllowed.into()
+                );
+            },
+        );
+        with_instructions_sysvar(
+            &instructions,
+            1,
+            TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+            |sysvar| {
+                assert_eq!(
+                    verify_flash_borrow_precedes(sysvar, &solve, 100, 200)
+                        .err()
+                        .unwrap(),
+                    ErrorCode::FlashLoanCpiNotAllowed.into()
+                );
+            },
+        );
+    }
+}
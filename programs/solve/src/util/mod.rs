@@ -1,3 +1,4 @@
+pub mod flash_loan;
 pub mod shared;
 pub mod sparse_swap;
 pub mod swap_tick_sequence;
@@ -6,6 +7,10 @@ pub mod token;
 pub mod token_2022;
 pub mod v2;
 
+#[cfg(test)]
+pub mod serialized_account;
+
+pub use flash_loan::*;
 pub use shared::*;
 pub use sparse_swap::*;
 pub use swap_tick_sequence::*;
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// AccountInfo::realloc relies on the layout of the accounts serialized by the runtime:
+// the original data length precedes the key and the data length precedes the data,
+// which can grow by MAX_PERMITTED_DATA_INCREASE.
+const KEY_OFFSET: usize = 8;
+const DATA_OFFSET: usize = 48;
+
+pub struct SerializedAccount {
+    buffer: Vec<u64>,
+    data_len: usize,
+    lamports: u64,
+    owner: Pubkey,
+}
+
+impl SerializedAccount {
+    pub fn new(key: Pubkey, data: &[u8], lamports: u64, owner: Pubkey) -> Self {
+        let mut buffer =
+            vec![0u64; (DATA_OFFSET + data.len() + MAX_PERMITTED_DATA_INCREASE) / 8 + 1];
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut buffer);
+        bytes[KEY_OFFSET - 4..KEY_OFFSET].copy_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes[KEY_OFFSET..KEY_OFFSET + 32].copy_from_slice(key.as_ref());
+        bytes[DATA_OFFSET - 8..DATA_OFFSET].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes[DATA_OFFSET..DATA_OFFSET + data.len()].copy_from_slice(data);
+        Self {
+            buffer,
+            data_len: data.len(),
+            lamports,
+            owner,
+        }
+    }
+
+    pub fn account_info(&mut self) -> AccountInfo<'_> {
+        let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut self.buffer);
+        let ptr = bytes.as_mut_ptr();
+        // The key and the data are disjoint parts of the buffer
+        let (key, data) = unsafe {
+            (
+                &*(ptr.add(KEY_OFFSET) as *const Pubkey),
+                std::slice::from_raw_parts_mut(ptr.add(DATA_OFFSET), self.data_len),
+            )
+        };
+        AccountInfo {
+            key,
+            is_signer: false,
+            is_writable: true,
+            lamports: Rc::new(RefCell::new(&mut self.lamports)),
+            data: Rc::new(RefCell::new(data)),
+            owner: &self.owner,
+            rent_epoch: 0,
+            executable: false,
+        }
+    }
+}
@@ -8,6 +8,9 @@ use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
 use crate::errors::ErrorCode;
+use crate::state::{
+    LockConfig, PausableOperation, Position, Solve, SolveTail, SolvesConfigExtension,
+};
 
 pub fn verify_position_bundle_authority(
     position_bundle_token_account: &TokenAccount,
@@ -75,3 +78,343 @@ pub fn is_locked_position(
 ) -> bool {
     position_token_account.is_frozen()
 }
+
+// Locked positions can only release the liquidity vested by a vesting lock.
+// The LockConfig account of the position must be provided to decrease the liquidity.
+pub fn verify_locked_position_decrease(
+    lock_config_info: &Option<Vec<AccountInfo<'_>>>,
+    position: &Account<'_, Position>,
+    liquidity_amount: u128,
+    timestamp: u64,
+) -> Result<()> {
+    let lock_config_info = match lock_config_info {
+        Some(accounts) if accounts.len() == 1 => &accounts[0],
+        _ => return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into()),
+    };
+
+    if lock_config_info.owner != &crate::ID {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+    let lock_config =
+        LockConfig::try_deserialize(&mut lock_config_info.try_borrow_data()?.as_ref())?;
+    if lock_config.position != position.key() {
+        return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into());
+    }
+
+    let unvested_liquidity = match lock_config.get_unvested_liquidity(timestamp)? {
+        Some(unvested_liquidity) => unvested_liquidity,
+        None => return Err(ErrorCode::OperationNotAllowedOnLockedPosition.into()),
+    };
+
+    let liquidity_after = position
+        .liquidity
+        .checked_sub(liquidity_amount)
+        .ok_or(ErrorCode::LiquidityUnderflow)?;
+    if liquidity_after < unvested_liquidity {
+        return Err(ErrorCode::VestedLiquidityExceeded.into());
+    }
+
+    Ok(())
+}
+
+// The pause flags of a pool, and the copy of the config pause flags synced by sync_pool_pause_flags,
+// are stored in the tail of the Solve account, which may not be allocated.
+pub fn verify_pool_not_paused(solve: &Account<Solve>, operation: PausableOperation) -> Result<()> {
+    let solve_tail = SolveTail::load(&solve.to_account_info())?;
+    solve_tail.verify_not_locked()?;
+    operation.verify_not_paused(solve_tail.pause_flags | solve_tail.config_pause_flags)
+}
+
+// Paused operations are also checked for the lock (see verify_pool_not_paused),
+// withdrawals that modify the state of the pool check it with this function.
+pub fn verify_pool_not_locked(solve: &Account<Solve>) -> Result<()> {
+    SolveTail::load(&solve.to_account_info())?.verify_not_locked()
+}
+
+// Instructions that take the config extension read the config level pause flags directly,
+// so they do not depend on the copy in the tail being synced.
+// The config extension may be uninitialized, its address must be verified by the caller.
+pub fn verify_pool_and_config_not_paused(
+    solve: &Account<Solve>,
+    solves_config_extension: &AccountInfo,
+    operation: PausableOperation,
+) -> Result<()> {
+    let config_pause_flags = SolvesConfigExtension::load_pause_flags(solves_config_extension)?;
+    let solve_tail = SolveTail::load(&solve.to_account_info())?;
+    solve_tail.verify_not_locked()?;
+    operation.verify_not_paused(config_pause_flags | solve_tail.pause_flags)
+}
+
+// Fee changes must be queued while the admin timelock is enabled.
+// The config extension may be uninitialized, in which case there is no timelock.
+// Its address must be verified by the caller.
+pub fn verify_admin_timelock_disabled(solves_config_extension: &AccountInfo) -> Result<()> {
+    if SolvesConfigExtension::try_load(solves_config_extension)?
+        .is_some_and(|solves_config_extension| solves_config_extension.is_admin_timelock_enabled())
+    {
+        return Err(ErrorCode::AdminTimelockEnabled.into());
+    }
+    Ok(())
+}
+
+// Protocol fees can only be collected by the authority while the fee distribution table is empty.
+// The config extension is an optional remaining account so that existing clients keep working.
+// Omitting it skips the check, which is no more than the collect_protocol_fees_authority can do
+// itself by clearing the table.
+pub fn verify_protocol_fee_recipients_not_set(
+    solves_config: &Pubkey,
+    solves_config_extension: Option<&AccountInfo>,
+) -> Result<()> {
+    let Some(solves_config_extension) = solves_config_extension else {
+        return Ok(());
+    };
+    // an uninitialized account has an empty table
+    let Some(solves_config_extension) = SolvesConfigExtension::try_load(solves_config_extension)?
+    else {
+        return Ok(());
+    };
+    if solves_config_extension.solves_config != *solves_config {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    if !solves_config_extension
+        .get_protocol_fee_recipients()
+        .is_empty()
+    {
+        return Err(ErrorCode::ProtocolFeeRecipientsSet.into());
+    }
+    Ok(())
+}
+
+// Instructions that move liquidity between positions without touching the ticks cannot move the
+// extension rewards and the boost of a position with a PositionRewardExtension.
+pub fn verify_position_without_reward_extension(position: &Account<Position>) -> Result<()> {
+    if Position::has_reward_extension(&position.to_account_info()) {
+        return Err(ErrorCode::RewardExtensionNotSupported.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod shared_tests {
+    use super::*;
+    use crate::state::{LockType, LockTypeLabel};
+
+    const LOCKED_TIMESTAMP: u64 = 1_000;
+    const LIQUIDITY: u128 = 1_000;
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>, owner: Pubkey) -> Self {
+            Self {
+                key,
+                owner,
+                lamports: 1_000_000,
+                data,
+            }
+        }
+
+        fn account_info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn new_position_account() -> TestAccount {
+        let position = Position {
+            liquidity: LIQUIDITY,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Position::LEN];
+        position.try_serialize(&mut data.as_mut_slice()).unwrap();
+        TestAccount::new(Pubkey::new_unique(), data, crate::ID)
+    }
+
+    fn new_lock_config_account(position: Pubkey, lock_type: LockType) -> TestAccount {
+        let mut lock_config = LockConfig {
+            position: Pubkey::default(),
+            position_owner: Pubkey::default(),
+            solve: Pubkey::default(),
+            locked_timestamp: 0,
+            lock_type: LockTypeLabel::Permanent,
+            unlock_timestamp: 0,
+            vesting_start_timestamp: 0,
+            locked_liquidity: 0,
+        };
+        lock_config
+            .initialize(
+                position,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                LOCKED_TIMESTAMP,
+                LIQUIDITY,
+                lock_type,
+            )
+            .unwrap();
+        let mut data = vec![0u8; LockConfig::LEN];
+        lock_config.try_serialize(&mut data.as_mut_slice()).unwrap();
+        TestAccount::new(Pubkey::new_unique(), data, crate::ID)
+    }
+
+    // The liquidity is fully vested from LOCKED_TIMESTAMP to LOCKED_TIMESTAMP + 100
+    fn vesting() -> LockType {
+        LockType::Vesting {
+            start_timestamp: LOCKED_TIMESTAMP,
+            end_timestamp: LOCKED_TIMESTAMP + 100,
+        }
+    }
+
+    fn verify_decrease(
+        lock_config_account: Option<&mut TestAccount>,
+        position_account: &mut TestAccount,
+        liquidity_amount: u128,
+        timestamp: u64,
+    ) -> Result<()> {
+        let position_info = position_account.account_info();
+        let position: Account<Position> = Account::try_from(&position_info)?;
+        let lock_config_info = lock_config_account.map(|account| vec![account.account_info()]);
+        verify_locked_position_decrease(&lock_config_info, &position, liquidity_amount, timestamp)
+    }
+
+    #[test]
+    fn test_verify_locked_position_decrease_vested() {
+        let mut position_account = new_position_account();
+        let mut lock_config_account = new_lock_config_account(position_account.key, vesting());
+
+        // Half of the liquidity is vested
+        let timestamp = LOCKED_TIMESTAMP + 50;
+        assert!(verify_decrease(
+            Some(&mut lock_config_account),
+            &mut position_account,
+            500,
+            timestamp
+        )
+        .is_ok());
+        assert_eq!(
+            verify_decrease(
+                Some(&mut lock_config_account),
+                &mut position_account,
+                501,
+                timestamp
+            )
+            .unwrap_err(),
+            ErrorCode::VestedLiquidityExceeded.into()
+        );
+        assert_eq!(
+            verify_decrease(
+                Some(&mut lock_config_account),
+                &mut position_account,
+                LIQUIDITY + 1,
+                LOCKED_TIMESTAMP + 100
+            )
+            .unwrap_err(),
+            ErrorCode::LiquidityUnderflow.into()
+        );
+
+        // All of the liquidity is vested
+        assert!(verify_decrease(
+            Some(&mut lock_config_account),
+            &mut position_account,
+            LIQUIDITY,
+            LOCKED_TIMESTAMP + 100
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_verify_locked_position_decrease_without_lock_config() {
+        let mut position_account = new_position_account();
+        assert_eq!(
+            verify_decrease(None, &mut position_account, 1, LOCKED_TIMESTAMP + 100).unwrap_err(),
+            ErrorCode::OperationNotAllowedOnLockedPosition.into()
+        );
+
+        // The LockConfig must be the only account of the slice
+        let position_info = position_account.account_info();
+        let position: Account<Position> = Account::try_from(&position_info).unwrap();
+        let mut lock_config_account = new_lock_config_account(position.key(), vesting());
+        let mut other_account = new_lock_config_account(position.key(), vesting());
+        let lock_config_info = Some(vec![
+            lock_config_account.account_info(),
+            other_account.account_info(),
+        ]);
+        assert_eq!(
+            verify_locked_position_decrease(
+                &lock_config_info,
+                &position,
+                1,
+                LOCKED_TIMESTAMP + 100
+            )
+            .unwrap_err(),
+            ErrorCode::OperationNotAllowedOnLockedPosition.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_locked_position_decrease_invalid_lock_config() {
+        let mut position_account = new_position_account();
+
+        // Not owned by the program
+        let mut lock_config_account = new_lock_config_account(position_account.key, vesting());
+        lock_config_account.owner = Pubkey::new_unique();
+        assert_eq!(
+            verify_decrease(
+                Some(&mut lock_config_account),
+                &mut position_account,
+                1,
+                LOCKED_TIMESTAMP + 100
+            )
+            .unwrap_err(),
+            ErrorCode::OperationNotAllowedOnLockedPosition.into()
+        );
+
+        // LockConfig of another position
+        let mut lock_config_account = new_lock_config_account(Pubkey::new_unique(), vesting());
+        assert_eq!(
+            verify_decrease(
+                Some(&mut lock_config_account),
+                &mut position_account,
+                1,
+                LOCKED_TIMESTAMP + 100
+            )
+            .unwrap_err(),
+            ErrorCode::OperationNotAllowedOnLockedPosition.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_locked_position_decrease_not_vesting() {
+        let mut position_account = new_position_account();
+        for lock_type in [
+            LockType::Permanent,
+            LockType::Timed {
+                unlock_timestamp: LOCKED_TIMESTAMP + 100,
+            },
+        ] {
+            let mut lock_config_account = new_lock_config_account(position_account.key, lock_type);
+            assert_eq!(
+                verify_decrease(
+                    Some(&mut lock_config_account),
+                    &mut position_account,
+                    1,
+                    u64::MAX
+                )
+                .unwrap_err(),
+                ErrorCode::OperationNotAllowedOnLockedPosition.into()
+            );
+        }
+    }
+}
@@ -9,10 +9,12 @@ use crate::{
     util::SwapTickSequence,
 };
 
-use crate::state::{load_tick_array_mut, LoadedTickArrayMut};
+use crate::state::{load_tick_array, load_tick_array_mut, LoadedTickArray, LoadedTickArrayMut};
 
 pub(crate) enum ProxiedTickArray<'a> {
     Initialized(LoadedTickArrayMut<'a>),
+    // Used for quoting. Updates on crossed ticks are discarded.
+    ReadOnly(LoadedTickArray<'a>),
     Uninitialized(ZeroedTickArray),
 }
 
@@ -21,6 +23,10 @@ impl<'a> ProxiedTickArray<'a> {
         ProxiedTickArray::Initialized(refmut)
     }
 
+    pub fn new_read_only(reference: LoadedTickArray<'a>) -> Self {
+        ProxiedTickArray::ReadOnly(reference)
+    }
+
     pub fn new_uninitialized(start_tick_index: i32) -> Self {
         ProxiedTickArray::Uninitialized(ZeroedTickArray::new(start_tick_index))
     }
@@ -49,7 +55,12 @@ impl<'a> ProxiedTickArray<'a> {
         tick_spacing: u16,
         update: &TickUpdate,
     ) -> Result<()> {
-        self.as_mut().update_tick(tick_index, tick_spacing, update)
+        if let ProxiedTickArray::ReadOnly(_) = self {
+            // A tick is crossed at most once in a single swap, so the update is never read again
+            return Ok(());
+        }
+        self.try_as_mut()?
+            .update_tick(tick_index, tick_spacing, update)
     }
 
     pub fn is_min_tick_array(&self) -> bool {
@@ -63,22 +74,25 @@ impl<'a> ProxiedTickArray<'a> {
     pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<isize> {
         self.as_ref().tick_offset(tick_index, tick_spacing)
     }
-}
 
-impl<'a> AsRef<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
-    fn as_ref(&self) -> &(dyn TickArrayType + 'a) {
+    // ReadOnly tick arrays are loaded without write access, so they cannot be borrowed mutably
+    fn try_as_mut(&mut self) -> Result<&mut (dyn TickArrayType + 'a)> {
         match self {
-            ProxiedTickArray::Initialized(ref array) => &**array,
-            ProxiedTickArray::Uninitialized(ref array) => array,
+            ProxiedTickArray::Initialized(ref mut array) => Ok(&mut **array),
+            ProxiedTickArray::ReadOnly(_) => {
+                Err(anchor_lang::error::ErrorCode::AccountNotMutable.into())
+            }
+            ProxiedTickArray::Uninitialized(ref mut array) => Ok(array),
         }
     }
 }
 
-impl<'a> AsMut<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
-    fn as_mut(&mut self) -> &mut (dyn TickArrayType + 'a) {
+impl<'a> AsRef<dyn TickArrayType + 'a> for ProxiedTickArray<'a> {
+    fn as_ref(&self) -> &(dyn TickArrayType + 'a) {
         match self {
-            ProxiedTickArray::Initialized(ref mut array) => &mut **array,
-            ProxiedTickArray::Uninitialized(ref mut array) => array,
+            ProxiedTickArray::Initialized(ref array) => &**array,
+            ProxiedTickArray::ReadOnly(ref array) => &**array,
+            ProxiedTickArray::Uninitialized(ref array) => array,
         }
     }
 }
@@ -131,9 +145,28 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
         solve: &Account<Solve>,
         a_to_b: bool,
     ) -> Result<SwapTickSequence<'a>> {
-        let mut loaded_tick_arrays: Vec<LoadedTickArrayMut> = Vec::with_capacity(3);
+        self.try_build_with_access(solve, a_to_b, false)
+    }
+
+    /// Same as `try_build`, but TickArray accounts are loaded without write access.
+    /// The resulting SwapTickSequence never modifies the TickArray accounts, so it can be used to quote a swap.
+    pub fn try_build_read_only<'a>(
+        &'a self,
+        solve: &Account<Solve>,
+        a_to_b: bool,
+    ) -> Result<SwapTickSequence<'a>> {
+        self.try_build_with_access(solve, a_to_b, true)
+    }
+
+    fn try_build_with_access<'a>(
+        &'a self,
+        solve: &Account<Solve>,
+        a_to_b: bool,
+        read_only: bool,
+    ) -> Result<SwapTickSequence<'a>> {
+        let mut loaded_tick_arrays: Vec<ProxiedTickArray> = Vec::with_capacity(3);
         for account_info in &self.tick_array_accounts {
-            let tick_array = maybe_load_tick_array(account_info, solve)?;
+            let tick_array = maybe_load_tick_array(account_info, solve, read_only)?;
             if let Some(tick_array) = tick_array {
                 loaded_tick_arrays.push(tick_array);
             }
@@ -147,7 +180,7 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
                 .position(|tick_array| tick_array.start_tick_index() == *start_tick_index);
             if let Some(pos) = pos {
                 let tick_array = loaded_tick_arrays.remove(pos);
-                required_tick_arrays.push_back(ProxiedTickArray::new_initialized(tick_array));
+                required_tick_arrays.push_back(tick_array);
                 continue;
             }
 
@@ -179,13 +212,19 @@ impl<'info> SparseSwapTickSequenceBuilder<'info> {
 fn maybe_load_tick_array<'a>(
     account_info: &'a AccountInfo<'_>,
     solve: &Account<Solve>,
-) -> Result<Option<LoadedTickArrayMut<'a>>> {
+    read_only: bool,
+) -> Result<Option<ProxiedTickArray<'a>>> {
     if *account_info.owner == system_program::ID && account_info.data_is_empty() {
         return Ok(None);
     }
 
+    if read_only {
+        let tick_array = load_tick_array(account_info, &solve.key())?;
+        return Ok(Some(ProxiedTickArray::new_read_only(tick_array)));
+    }
+
     let tick_array = load_tick_array_mut(account_info, &solve.key())?;
-    Ok(Some(tick_array))
+    Ok(Some(ProxiedTickArray::new_initialized(tick_array)))
 }
 
 fn derive_tick_array_pda(solve: &Account<Solve>, start_tick_index: i32) -> Pubkey {
@@ -234,3 +273,58 @@ fn get_start_tick_indexes(solve: &Account<Solve>, a_to_b: bool) -> Vec<i32> {
     start_tick_indexes
 }
 
+#[cfg(test)]
+mod sparse_swap_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    const TICK_SPACING: u16 = 64;
+
+    #[test]
+    fn test_read_only_tick_array() {
+        let solve_key = Pubkey::new_unique();
+        let mut data = vec![0u8; FixedTickArray::LEN];
+        data[0..8].copy_from_slice(&FixedTickArray::discriminator());
+        data[9956..9988].copy_from_slice(&solve_key.to_bytes());
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let owner = crate::ID;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        let mut tick_array =
+            ProxiedTickArray::new_read_only(load_tick_array(&account_info, &solve_key).unwrap());
+        assert_eq!(
+            tick_array.try_as_mut().err().unwrap(),
+            anchor_lang::error::ErrorCode::AccountNotMutable.into()
+        );
+
+        // Updates of crossed ticks are discarded
+        tick_array
+            .update_tick(
+                0,
+                TICK_SPACING,
+                &TickUpdate {
+                    initialized: true,
+                    liquidity_gross: 1,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            tick_array.get_tick(0, TICK_SPACING).unwrap(),
+            Tick::default()
+        );
+
+        let mut tick_array = ProxiedTickArray::new_uninitialized(0);
+        assert!(tick_array.try_as_mut().is_ok());
+    }
+}
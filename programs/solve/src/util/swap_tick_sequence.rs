@@ -85,6 +85,23 @@ impl<'a> SwapTickSequence<'a> {
         }
     }
 
+    /// Get the index of the array that stores the given tick-index
+    ///
+    /// # Parameters
+    /// - `tick_index` - the tick index to look up
+    /// - `tick_spacing` - A u8 integer of the tick spacing for this solve
+    ///
+    /// # Returns
+    /// - `Some(usize)`: The array_index of the array storing the tick-index
+    /// - `None`: None of the arrays in this sequence store the tick-index
+    pub fn get_array_index(&self, tick_index: i32, tick_spacing: u16) -> Option<usize> {
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        self.arrays.iter().position(|array| {
+            let start_tick_index = array.start_tick_index();
+            tick_index >= start_tick_index && tick_index < start_tick_index + ticks_in_array
+        })
+    }
+
     pub fn get_tick_offset(
         &self,
         array_index: usize,
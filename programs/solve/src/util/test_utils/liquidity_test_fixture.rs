@@ -5,7 +5,8 @@ use crate::math::{add_liquidity_delta, Q64_RESOLUTION};
 use crate::state::position_builder::PositionBuilder;
 use crate::state::{solve_builder::SolveBuilder, tick_builder::TickBuilder, Solve};
 use crate::state::{
-    Position, PositionRewardInfo, PositionUpdate, SolveRewardInfo, Tick, TickUpdate, NUM_REWARDS,
+    Position, PositionRewardInfo, PositionUpdate, RewardSchedule, SolveRewardInfo, Tick,
+    TickUpdate, NUM_REWARDS,
 };
 use anchor_lang::prelude::*;
 
@@ -108,7 +109,12 @@ impl LiquidityTestFixture {
 
     pub fn increment_solve_reward_growths_by_time(&mut self, seconds: u64) {
         let next_timestamp = self.solve.reward_last_updated_timestamp + seconds;
-        self.solve.reward_infos = next_solve_reward_infos(&self.solve, next_timestamp).unwrap();
+        self.solve.reward_infos = next_solve_reward_infos(
+            &self.solve,
+            &[RewardSchedule::default(); NUM_REWARDS],
+            next_timestamp,
+        )
+        .unwrap();
         self.solve.reward_last_updated_timestamp = next_timestamp;
     }
 
@@ -5,7 +5,8 @@ use crate::state::{
     solve_builder::SolveBuilder, tick_array::*, tick_builder::TickBuilder, FixedTickArray, Solve,
 };
 use crate::state::{
-    AdaptiveFeeInfo, SolveRewardInfo, Tick, TickUpdate, MAX_TICK_INDEX, MIN_TICK_INDEX, NUM_REWARDS,
+    AdaptiveFeeInfo, RewardSchedule, SolveRewardInfo, Tick, TickUpdate, MAX_TICK_INDEX,
+    MIN_TICK_INDEX, NUM_REWARDS,
 };
 use crate::util::SwapTickSequence;
 use anchor_lang::prelude::*;
@@ -223,6 +224,7 @@ impl SwapTestFixture {
             self.sqrt_price_limit,
             self.amount_specified_is_input,
             self.a_to_b,
+            &[RewardSchedule::default(); NUM_REWARDS],
             next_timestamp,
             &self.adaptive_fee_info,
         )
@@ -241,6 +243,7 @@ impl SwapTestFixture {
             self.sqrt_price_limit,
             self.amount_specified_is_input,
             self.a_to_b,
+            &[RewardSchedule::default(); NUM_REWARDS],
             next_timestamp,
             &self.adaptive_fee_info,
         )
@@ -316,6 +316,78 @@ pub fn burn_and_close_user_position_token_2022<'info>(
     Ok(())
 }
 
+// Burns and closes a position token owned by either the Token or the Token-2022 program.
+// Only Token-2022 position mints can be closed, legacy position mints are left as they are.
+pub fn burn_and_close_user_position_token_interface<'info>(
+    token_authority: &Signer<'info>,
+    receiver: &UncheckedAccount<'info>,
+    position_mint: &InterfaceAccount<'info, Mint>,
+    position_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    position: &Account<'info, Position>,
+    position_seeds: &[&[u8]],
+) -> Result<()> {
+    // Burn a single token in user account
+    invoke(
+        &spl_token_2022::instruction::burn_checked(
+            token_program.key,
+            position_token_account.to_account_info().key,
+            position_mint.to_account_info().key,
+            token_authority.key,
+            &[],
+            1,
+            position_mint.decimals,
+        )?,
+        &[
+            token_program.to_account_info(),
+            position_token_account.to_account_info(),
+            position_mint.to_account_info(),
+            token_authority.to_account_info(),
+        ],
+    )?;
+
+    // Close user account
+    invoke(
+        &spl_token_2022::instruction::close_account(
+            token_program.key,
+            position_token_account.to_account_info().key,
+            receiver.key,
+            token_authority.key,
+            &[],
+        )?,
+        &[
+            token_program.to_account_info(),
+            position_token_account.to_account_info(),
+            receiver.to_account_info(),
+            token_authority.to_account_info(),
+        ],
+    )?;
+
+    if token_program.key() != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    // Close mint
+    invoke_signed(
+        &spl_token_2022::instruction::close_account(
+            token_program.key,
+            position_mint.to_account_info().key,
+            receiver.key,
+            &position.key(),
+            &[],
+        )?,
+        &[
+            token_program.to_account_info(),
+            position_mint.to_account_info(),
+            receiver.to_account_info(),
+            position.to_account_info(),
+        ],
+        &[position_seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn build_position_token_metadata<'info>(
     position_mint: &Signer<'info>,
     position: &Account<'info, Position>,
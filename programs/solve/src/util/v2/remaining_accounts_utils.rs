@@ -14,6 +14,15 @@ pub enum AccountsType {
     SupplementalTickArrays,
     SupplementalTickArraysOne,
     SupplementalTickArraysTwo,
+    FlashSwapCallback,
+    LockConfig,
+    RewardExtension,
+    RewardExtensionOne,
+    RewardExtensionTwo,
+    ProtocolFeeDestinations,
+    ConfigExtension,
+    ReferralTokenAccount,
+    TransferHookReferral,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -38,6 +47,15 @@ pub struct ParsedRemainingAccounts<'info> {
     pub supplemental_tick_arrays: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_one: Option<Vec<AccountInfo<'info>>>,
     pub supplemental_tick_arrays_two: Option<Vec<AccountInfo<'info>>>,
+    pub flash_swap_callback: Option<Vec<AccountInfo<'info>>>,
+    pub lock_config: Option<Vec<AccountInfo<'info>>>,
+    pub reward_extension: Option<Vec<AccountInfo<'info>>>,
+    pub reward_extension_one: Option<Vec<AccountInfo<'info>>>,
+    pub reward_extension_two: Option<Vec<AccountInfo<'info>>>,
+    pub protocol_fee_destinations: Option<Vec<AccountInfo<'info>>>,
+    pub config_extension: Option<Vec<AccountInfo<'info>>>,
+    pub referral_token_account: Option<Vec<AccountInfo<'info>>>,
+    pub transfer_hook_referral: Option<Vec<AccountInfo<'info>>>,
 }
 
 pub fn parse_remaining_accounts<'info>(
@@ -147,8 +165,89 @@ pub fn parse_remaining_accounts<'info>(
                 }
                 parsed_remaining_accounts.supplemental_tick_arrays_two = Some(accounts);
             }
+            AccountsType::FlashSwapCallback => {
+                if parsed_remaining_accounts.flash_swap_callback.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.flash_swap_callback = Some(accounts);
+            }
+            AccountsType::LockConfig => {
+                if parsed_remaining_accounts.lock_config.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.lock_config = Some(accounts);
+            }
+            AccountsType::RewardExtension => {
+                if parsed_remaining_accounts.reward_extension.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.reward_extension = Some(accounts);
+            }
+            AccountsType::RewardExtensionOne => {
+                if parsed_remaining_accounts.reward_extension_one.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.reward_extension_one = Some(accounts);
+            }
+            AccountsType::RewardExtensionTwo => {
+                if parsed_remaining_accounts.reward_extension_two.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.reward_extension_two = Some(accounts);
+            }
+            AccountsType::ProtocolFeeDestinations => {
+                if parsed_remaining_accounts
+                    .protocol_fee_destinations
+                    .is_some()
+                {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.protocol_fee_destinations = Some(accounts);
+            }
+            AccountsType::ConfigExtension => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+                if parsed_remaining_accounts.config_extension.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.config_extension = Some(accounts);
+            }
+            AccountsType::ReferralTokenAccount => {
+                if accounts.len() != 1 {
+                    return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+                }
+                if parsed_remaining_accounts.referral_token_account.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.referral_token_account = Some(accounts);
+            }
+            AccountsType::TransferHookReferral => {
+                if parsed_remaining_accounts.transfer_hook_referral.is_some() {
+                    return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                }
+                parsed_remaining_accounts.transfer_hook_referral = Some(accounts);
+            }
         }
     }
 
     Ok(parsed_remaining_accounts)
 }
+
+// Returns the accounts of a slice validated by parse_remaining_accounts without cloning them,
+// for accounts that must be borrowed for 'info (e.g. deserialized with InterfaceAccount::try_from).
+pub fn get_remaining_accounts_slice<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    remaining_accounts_info: &Option<RemainingAccountsInfo>,
+    accounts_type: AccountsType,
+) -> Option<&'info [AccountInfo<'info>]> {
+    let mut offset = 0;
+    for slice in remaining_accounts_info.as_ref()?.slices.iter() {
+        let length = slice.length as usize;
+        if slice.accounts_type == accounts_type {
+            return remaining_accounts.get(offset..offset + length);
+        }
+        offset += length;
+    }
+    None
+}
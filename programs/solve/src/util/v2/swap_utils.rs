@@ -2,9 +2,15 @@ use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{manager::swap_manager::PostSwapUpdate, state::Solve};
+use crate::{
+    errors::ErrorCode, manager::swap_manager::PostSwapUpdate, math::MAX_REFERRAL_FEE_RATE,
+    state::Solve,
+};
 
-use super::{transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2};
+use super::{
+    get_remaining_accounts_slice, transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2,
+    AccountsType, RemainingAccountsInfo,
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn update_and_swap_solve_v2<'info>(
@@ -258,3 +264,109 @@ pub fn update_and_two_hop_swap_solve_v2<'info>(
 
     Ok(())
 }
+
+// The referral token account is passed in the ReferralTokenAccount slice of the remaining accounts,
+// so that the accounts of swap_v2 and two_hop_swap_v2 are unchanged.
+pub fn load_referral_token_account<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    remaining_accounts_info: &Option<RemainingAccountsInfo>,
+) -> Result<Option<InterfaceAccount<'info, TokenAccount>>> {
+    match get_remaining_accounts_slice(
+        remaining_accounts,
+        remaining_accounts_info,
+        AccountsType::ReferralTokenAccount,
+    ) {
+        Some([referral_token_account]) => {
+            Ok(Some(InterfaceAccount::try_from(referral_token_account)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+// The referral fee is charged in the input token, so the referral token account must hold it.
+pub fn verify_referral_fee(
+    referral_token_account: Option<&InterfaceAccount<'_, TokenAccount>>,
+    token_mint_input: &Pubkey,
+    referral_fee_rate: u16,
+) -> Result<()> {
+    if referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+        return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+    }
+
+    match referral_token_account {
+        Some(referral_token_account) if referral_token_account.mint != *token_mint_input => {
+            Err(ErrorCode::InvalidReferralTokenAccount.into())
+        }
+        None if referral_fee_rate > 0 => Err(ErrorCode::InvalidReferralTokenAccount.into()),
+        _ => Ok(()),
+    }
+}
+
+// The referral token account has its own transfer hook accounts, as the extra accounts of a
+// transfer hook may depend on the destination.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_referral_fee_v2<'info>(
+    token_authority: &Signer<'info>,
+    token_mint_input: &InterfaceAccount<'info, Mint>,
+    token_owner_account_input: &InterfaceAccount<'info, TokenAccount>,
+    referral_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program_input: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts_referral: &Option<Vec<AccountInfo<'info>>>,
+    referral_fee: u64,
+) -> Result<()> {
+    match referral_token_account {
+        Some(referral_token_account) if referral_fee > 0 => transfer_from_owner_to_vault_v2(
+            token_authority,
+            token_mint_input,
+            token_owner_account_input,
+            referral_token_account,
+            token_program_input,
+            memo_program,
+            transfer_hook_accounts_referral,
+            referral_fee,
+        ),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod swap_utils_tests {
+    use super::*;
+    use crate::util::RemainingAccountsSlice;
+
+    #[test]
+    fn test_verify_referral_fee_rate() {
+        let token_mint_input = Pubkey::new_unique();
+        assert!(verify_referral_fee(None, &token_mint_input, 0).is_ok());
+        assert_eq!(
+            verify_referral_fee(None, &token_mint_input, MAX_REFERRAL_FEE_RATE + 1).unwrap_err(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_verify_referral_fee_without_referral_token_account() {
+        let token_mint_input = Pubkey::new_unique();
+        for referral_fee_rate in [1, MAX_REFERRAL_FEE_RATE] {
+            assert_eq!(
+                verify_referral_fee(None, &token_mint_input, referral_fee_rate).unwrap_err(),
+                ErrorCode::InvalidReferralTokenAccount.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_load_referral_token_account_without_slice() {
+        let remaining_accounts_info = Some(RemainingAccountsInfo {
+            slices: vec![RemainingAccountsSlice {
+                accounts_type: AccountsType::TransferHookA,
+                length: 0,
+            }],
+        });
+        assert!(load_referral_token_account(&[], &remaining_accounts_info)
+            .unwrap()
+            .is_none());
+        assert!(load_referral_token_account(&[], &None).unwrap().is_none());
+    }
+}